@@ -0,0 +1,124 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{PrefixMapping, Registry};
+
+/// A curated subset of widely used entries of the
+/// [Bioregistry](https://bioregistry.io/), with its normalized,
+/// lower-case prefixes.
+///
+/// These are picked by hand rather than generated from the registry,
+/// so they don't follow its releases. The complete dataset can be
+/// loaded into a [`Registry`] by collecting its prefixes and
+/// namespaces.
+const BIOREGISTRY: &[(&str, &str)] = &[
+    ("bfo", "http://purl.obolibrary.org/obo/BFO_"),
+    ("chebi", "http://purl.obolibrary.org/obo/CHEBI_"),
+    ("cl", "http://purl.obolibrary.org/obo/CL_"),
+    ("clo", "http://purl.obolibrary.org/obo/CLO_"),
+    ("doid", "http://purl.obolibrary.org/obo/DOID_"),
+    ("doi", "https://doi.org/"),
+    ("drugbank", "https://go.drugbank.com/drugs/"),
+    ("eco", "http://purl.obolibrary.org/obo/ECO_"),
+    ("efo", "http://www.ebi.ac.uk/efo/EFO_"),
+    ("ensembl", "https://www.ensembl.org/id/"),
+    ("envo", "http://purl.obolibrary.org/obo/ENVO_"),
+    ("go", "http://purl.obolibrary.org/obo/GO_"),
+    ("hp", "http://purl.obolibrary.org/obo/HP_"),
+    ("iao", "http://purl.obolibrary.org/obo/IAO_"),
+    ("mesh", "http://id.nlm.nih.gov/mesh/"),
+    ("mi", "http://purl.obolibrary.org/obo/MI_"),
+    ("mondo", "http://purl.obolibrary.org/obo/MONDO_"),
+    ("mp", "http://purl.obolibrary.org/obo/MP_"),
+    ("ncbigene", "https://www.ncbi.nlm.nih.gov/gene/"),
+    ("ncbitaxon", "http://purl.obolibrary.org/obo/NCBITaxon_"),
+    ("ncit", "http://purl.obolibrary.org/obo/NCIT_"),
+    ("obi", "http://purl.obolibrary.org/obo/OBI_"),
+    ("orcid", "https://orcid.org/"),
+    ("pato", "http://purl.obolibrary.org/obo/PATO_"),
+    ("pmc", "http://europepmc.org/articles/"),
+    ("pr", "http://purl.obolibrary.org/obo/PR_"),
+    (
+        "pubchem.compound",
+        "https://pubchem.ncbi.nlm.nih.gov/compound/",
+    ),
+    ("pubmed", "https://www.ncbi.nlm.nih.gov/pubmed/"),
+    ("reactome", "https://reactome.org/content/detail/"),
+    ("ro", "http://purl.obolibrary.org/obo/RO_"),
+    ("ror", "https://ror.org/"),
+    ("so", "http://purl.obolibrary.org/obo/SO_"),
+    ("uberon", "http://purl.obolibrary.org/obo/UBERON_"),
+    ("uniprot", "http://purl.uniprot.org/uniprot/"),
+    ("uo", "http://purl.obolibrary.org/obo/UO_"),
+    ("wikidata", "http://www.wikidata.org/entity/"),
+];
+
+impl Registry {
+    /// Construct a registry of a curated subset of widely used
+    /// [Bioregistry] prefixes, not the whole registry.
+    ///
+    /// This requires the `bioregistry-common` feature.
+    ///
+    /// [Bioregistry]: https://bioregistry.io/
+    #[must_use]
+    pub fn bioregistry_common() -> Self {
+        BIOREGISTRY.iter().copied().collect()
+    }
+}
+
+impl PrefixMapping {
+    /// Construct a mapping with the prefixes of
+    /// [`Registry::bioregistry_common()`], a curated subset of widely
+    /// used [Bioregistry] prefixes.
+    ///
+    /// The prefixes are Bioregistry's normalized, lower-case prefixes,
+    /// unlike the conventional OBO prefixes of
+    /// [`presets::bio()`](crate::presets).
+    ///
+    /// This requires the `bioregistry-common` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "bioregistry-common")] {
+    /// use curie::PrefixMapping;
+    ///
+    /// let mapping = PrefixMapping::bioregistry_common();
+    /// assert_eq!(mapping.expand_curie_string("go:0008150"),
+    ///            Ok(String::from("http://purl.obolibrary.org/obo/GO_0008150")));
+    /// # }
+    /// ```
+    ///
+    /// [Bioregistry]: https://bioregistry.io/
+    #[must_use]
+    pub fn bioregistry_common() -> Self {
+        let mut mapping = PrefixMapping::with_capacity(BIOREGISTRY.len());
+        mapping
+            .add_prefixes(Registry::bioregistry_common().iter())
+            .expect("Bioregistry prefixes are valid");
+        mapping
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Curie;
+
+    #[test]
+    fn bioregistry_common() {
+        let mapping = PrefixMapping::bioregistry_common();
+        assert_eq!(mapping.mappings().count(), BIOREGISTRY.len());
+        assert_eq!(
+            mapping.shrink_iri("http://purl.obolibrary.org/obo/UBERON_0002107"),
+            Ok(Curie::new(Some("uberon"), "0002107"))
+        );
+        assert_eq!(
+            Registry::bioregistry_common().prefix_for("https://orcid.org/0000-0002-1825-0097"),
+            Some(("orcid", "https://orcid.org/"))
+        );
+    }
+}
@@ -0,0 +1,400 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{syntax, Curie, CurieBuf, PrefixMapping, UnknownPrefix};
+use std::fmt;
+use std::str::FromStr;
+
+/// Errors that might occur when building a [`Curie`] with a
+/// [`CurieBuilder`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum CurieBuildError {
+    /// No reference was given.
+    MissingReference,
+    /// The prefix is not an `NCName`.
+    InvalidPrefix(String),
+    /// The reference contains a character that can never appear
+    /// in an IRI.
+    InvalidReference {
+        /// The reference.
+        reference: String,
+        /// The byte offset of the invalid character.
+        offset: usize,
+        /// The invalid character.
+        character: char,
+    },
+    /// The prefix is not mapped by the mapping given to
+    /// [`CurieBuilder::validate()`].
+    UnknownPrefix {
+        /// The prefix.
+        prefix: String,
+        /// Similar prefixes that are mapped, most similar first.
+        suggestions: Vec<String>,
+    },
+    /// There is no prefix and the mapping given to
+    /// [`CurieBuilder::validate()`] has no default.
+    MissingDefault,
+}
+
+impl fmt::Display for CurieBuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CurieBuildError::MissingReference => f.write_str("no reference was given"),
+            CurieBuildError::InvalidPrefix(prefix) => {
+                write!(f, "the prefix `{prefix}` is not a valid NCName")
+            }
+            CurieBuildError::InvalidReference {
+                reference,
+                offset,
+                character,
+            } => write!(
+                f,
+                "the reference `{reference}` contains {character:?} at byte {offset}"
+            ),
+            CurieBuildError::UnknownPrefix {
+                prefix,
+                suggestions,
+            } => {
+                UnknownPrefix::new(prefix, suggestions.iter().map(String::as_str).collect()).fmt(f)
+            }
+            CurieBuildError::MissingDefault => f.write_str("no default prefix has been set"),
+        }
+    }
+}
+
+impl std::error::Error for CurieBuildError {}
+
+/// A builder for [`Curie`]s that checks the components are valid.
+///
+/// This is intended for constructing CURIEs from untrusted input.
+/// The prefix, if any, must be an `NCName` and the reference must not
+/// contain characters that can never appear in an IRI. Optionally, the
+/// prefix can also be checked against a [`PrefixMapping`].
+///
+/// # Examples
+///
+/// ```
+/// use curie::{Curie, CurieBuildError, PrefixMapping};
+///
+/// let mut mapping = PrefixMapping::default();
+/// mapping.add_prefix("foaf", "http://xmlns.com/foaf/0.1/").unwrap();
+///
+/// let curie = Curie::builder()
+///     .prefix("foaf")
+///     .reference("Agent")
+///     .validate(&mapping)
+///     .build();
+/// assert_eq!(curie, Ok(Curie::new(Some("foaf"), "Agent")));
+///
+/// let curie = Curie::builder()
+///     .prefix("fof")
+///     .reference("Agent")
+///     .validate(&mapping)
+///     .build();
+/// assert_eq!(curie, Err(CurieBuildError::UnknownPrefix {
+///     prefix: String::from("fof"),
+///     suggestions: vec![String::from("foaf")],
+/// }));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CurieBuilder<'a> {
+    prefix: Option<&'a str>,
+    reference: Option<&'a str>,
+    mapping: Option<&'a PrefixMapping>,
+}
+
+impl<'a> CurieBuilder<'a> {
+    /// Set the prefix.
+    ///
+    /// Without a prefix, the CURIE uses the default prefix.
+    #[must_use]
+    pub fn prefix(mut self, prefix: &'a str) -> Self {
+        self.prefix = Some(prefix);
+        self
+    }
+
+    /// Set the reference.
+    #[must_use]
+    pub fn reference(mut self, reference: &'a str) -> Self {
+        self.reference = Some(reference);
+        self
+    }
+
+    /// Check that the prefix, or the default when there is no prefix,
+    /// is mapped by `mapping`.
+    #[must_use]
+    pub fn validate(mut self, mapping: &'a PrefixMapping) -> Self {
+        self.mapping = Some(mapping);
+        self
+    }
+
+    /// Check the components and build the [`Curie`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`CurieBuildError`] describing the first problem found.
+    pub fn build(self) -> Result<Curie<'a>, CurieBuildError> {
+        let reference = self.reference.ok_or(CurieBuildError::MissingReference)?;
+        if let Some(prefix) = self.prefix {
+            if !syntax::is_ncname(prefix) {
+                return Err(CurieBuildError::InvalidPrefix(String::from(prefix)));
+            }
+        }
+        if let Some((offset, character)) = syntax::find_invalid_iri_char(reference) {
+            return Err(CurieBuildError::InvalidReference {
+                reference: String::from(reference),
+                offset,
+                character,
+            });
+        }
+        if let Some(mapping) = self.mapping {
+            match self.prefix {
+                Some(prefix) if !mapping.mapping.contains_key(prefix) => {
+                    return Err(CurieBuildError::UnknownPrefix {
+                        prefix: String::from(prefix),
+                        suggestions: mapping
+                            .suggest_prefixes(prefix)
+                            .into_iter()
+                            .map(String::from)
+                            .collect(),
+                    });
+                }
+                None if mapping.default.is_none() => {
+                    return Err(CurieBuildError::MissingDefault);
+                }
+                _ => {}
+            }
+        }
+        Ok(Curie::new(self.prefix, reference))
+    }
+}
+
+impl<'c> Curie<'c> {
+    /// Start building a `Curie` with validation of its components.
+    ///
+    /// # See also
+    ///
+    /// * [`CurieBuilder`]
+    #[must_use]
+    pub fn builder() -> CurieBuilder<'c> {
+        CurieBuilder::default()
+    }
+
+    /// Parse a CURIE, checking its syntax.
+    ///
+    /// Unlike [`PrefixMapping::expand_curie_string()`], this only
+    /// parses the CURIE, so it can be checked before a mapping is
+    /// available. The text before the first `:` is the prefix, which
+    /// must be empty or an `NCName`, and the rest is the reference,
+    /// which must not contain characters that can never appear in an
+    /// IRI. Without a `:`, the whole text is the reference.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CurieBuildError::InvalidPrefix`] or
+    /// [`CurieBuildError::InvalidReference`] if the CURIE is malformed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::{Curie, CurieBuildError};
+    ///
+    /// assert_eq!(Curie::try_parse("foaf:Agent"), Ok(Curie::new(Some("foaf"), "Agent")));
+    /// assert_eq!(Curie::try_parse(":Agent"), Ok(Curie::new(Some(""), "Agent")));
+    /// assert_eq!(Curie::try_parse("1st:Agent"),
+    ///            Err(CurieBuildError::InvalidPrefix(String::from("1st"))));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`CurieBuf`]'s implementation of [`FromStr`]
+    pub fn try_parse(curie_str: &'c str) -> Result<Curie<'c>, CurieBuildError> {
+        let curie = Curie::split(curie_str);
+        curie.validate()?;
+        Ok(curie)
+    }
+
+    /// Check the syntax of this CURIE, as [`Curie::try_parse()`] does.
+    ///
+    /// This is useful for a `Curie` made with [`Curie::new()`], which
+    /// doesn't check its parts.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CurieBuildError::InvalidPrefix`] or
+    /// [`CurieBuildError::InvalidReference`] if the CURIE is malformed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::{Curie, CurieBuildError};
+    ///
+    /// assert_eq!(Curie::new(Some("foaf"), "Agent").validate(), Ok(()));
+    /// assert_eq!(Curie::new(Some("my prefix"), "Agent").validate(),
+    ///            Err(CurieBuildError::InvalidPrefix(String::from("my prefix"))));
+    /// ```
+    pub fn validate(&self) -> Result<(), CurieBuildError> {
+        match self.prefix {
+            // The empty prefix is allowed, as in `:Agent`.
+            Some("") => {}
+            Some(prefix) if !syntax::is_ncname(prefix) => {
+                return Err(CurieBuildError::InvalidPrefix(String::from(prefix)));
+            }
+            _ => {}
+        }
+        if let Some((offset, character)) = syntax::find_invalid_iri_char(self.reference) {
+            return Err(CurieBuildError::InvalidReference {
+                reference: String::from(self.reference),
+                offset,
+                character,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Check the syntax of a [CURIE or safe CURIE], without needing a
+/// [`PrefixMapping`](crate::PrefixMapping).
+///
+/// A safe CURIE is a CURIE in square brackets. The CURIE itself is
+/// checked as by [`Curie::try_parse()`].
+///
+/// # Examples
+///
+/// ```
+/// use curie::is_valid_curie;
+///
+/// assert!(is_valid_curie("foaf:Agent"));
+/// assert!(is_valid_curie("[foaf:Agent]"));
+/// assert!(is_valid_curie("Agent"));
+/// assert!(!is_valid_curie("foaf:the Agent"));
+/// assert!(!is_valid_curie("[foaf:Agent"));
+/// ```
+///
+/// [CURIE or safe CURIE]: https://www.w3.org/TR/curie/#s_syntax
+#[must_use]
+pub fn is_valid_curie(curie_str: &str) -> bool {
+    let curie_str = curie_str
+        .strip_prefix('[')
+        .and_then(|inner| inner.strip_suffix(']'))
+        .unwrap_or(curie_str);
+    Curie::try_parse(curie_str).is_ok()
+}
+
+impl<'c> TryFrom<&'c str> for Curie<'c> {
+    type Error = CurieBuildError;
+
+    fn try_from(curie_str: &'c str) -> Result<Self, Self::Error> {
+        Curie::try_parse(curie_str)
+    }
+}
+
+impl FromStr for CurieBuf {
+    type Err = CurieBuildError;
+
+    /// Parse a CURIE, as with [`Curie::try_parse()`].
+    fn from_str(curie_str: &str) -> Result<Self, Self::Err> {
+        Curie::try_parse(curie_str).map(CurieBuf::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_errors() {
+        assert_eq!(
+            Curie::builder().prefix("foaf").build(),
+            Err(CurieBuildError::MissingReference)
+        );
+        assert_eq!(
+            Curie::builder().prefix("fo af").reference("Agent").build(),
+            Err(CurieBuildError::InvalidPrefix(String::from("fo af")))
+        );
+        assert_eq!(
+            Curie::builder().reference("Green Goblin").build(),
+            Err(CurieBuildError::InvalidReference {
+                reference: String::from("Green Goblin"),
+                offset: 5,
+                character: ' ',
+            })
+        );
+        assert_eq!(
+            Curie::builder()
+                .reference("Agent")
+                .validate(&PrefixMapping::default())
+                .build(),
+            Err(CurieBuildError::MissingDefault)
+        );
+    }
+
+    #[test]
+    fn build_without_validation() {
+        assert_eq!(
+            Curie::builder().prefix("foaf").reference("Agent").build(),
+            Ok(Curie::new(Some("foaf"), "Agent"))
+        );
+        assert_eq!(
+            Curie::builder().reference("").build(),
+            Ok(Curie::new(None, ""))
+        );
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(Curie::try_parse("Agent"), Ok(Curie::new(None, "Agent")));
+        assert_eq!(
+            Curie::try_parse("ex:a:b"),
+            Ok(Curie::new(Some("ex"), "a:b"))
+        );
+        assert_eq!(
+            Curie::try_from("fo af:Agent"),
+            Err(CurieBuildError::InvalidPrefix(String::from("fo af")))
+        );
+        assert_eq!(
+            "ex:a b".parse::<CurieBuf>(),
+            Err(CurieBuildError::InvalidReference {
+                reference: String::from("a b"),
+                offset: 1,
+                character: ' ',
+            })
+        );
+        assert_eq!(
+            "ex:a".parse::<CurieBuf>(),
+            Ok(CurieBuf::new(Some("ex"), "a"))
+        );
+    }
+
+    #[test]
+    fn validate() {
+        assert!(is_valid_curie(":Agent"));
+        assert!(is_valid_curie("[]"));
+        assert!(is_valid_curie("[ex:a:b]"));
+        assert!(!is_valid_curie("[[ex:a]]"));
+        assert!(!is_valid_curie("1st:a"));
+        assert_eq!(
+            Curie::new(None, "a\tb").validate(),
+            Err(CurieBuildError::InvalidReference {
+                reference: String::from("a\tb"),
+                offset: 1,
+                character: '\t',
+            })
+        );
+    }
+
+    #[test]
+    fn display_unknown_prefix() {
+        let err = CurieBuildError::UnknownPrefix {
+            prefix: String::from("ex"),
+            suggestions: vec![String::from("ex1"), String::from("ex2")],
+        };
+        assert_eq!(
+            err.to_string(),
+            "`ex` not found; did you mean `ex1` or `ex2`?"
+        );
+    }
+}
@@ -0,0 +1,128 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{Curie, ExpansionError, PrefixMapping};
+use bumpalo::Bump;
+
+impl PrefixMapping {
+    /// Expand a CURIE, allocating the complete IRI in an arena.
+    ///
+    /// This avoids a heap allocation for each expansion when expanding
+    /// many CURIEs whose IRIs can all be freed together.
+    ///
+    /// This requires the `bumpalo` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpalo::Bump;
+    /// use curie::PrefixMapping;
+    ///
+    /// let mut mapping = PrefixMapping::default();
+    /// mapping.add_prefix("foaf", "http://xmlns.com/foaf/0.1/").unwrap();
+    ///
+    /// let bump = Bump::new();
+    /// let iri: &str = mapping.expand_curie_string_in("foaf:Agent", &bump).unwrap();
+    /// assert_eq!(iri, "http://xmlns.com/foaf/0.1/Agent");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This will return [`ExpansionError`] if the expansion fails.
+    ///
+    /// # See also
+    ///
+    /// * [`PrefixMapping::expand_curie_string()`]
+    /// * [`PrefixMapping::expand_curie_in()`]
+    pub fn expand_curie_string_in<'b>(
+        &self,
+        curie_str: &str,
+        bump: &'b Bump,
+    ) -> Result<&'b str, ExpansionError> {
+        self.expand_curie_in(&Curie::split(curie_str), bump)
+    }
+
+    /// Expand a parsed [`Curie`], allocating the complete IRI in an arena.
+    ///
+    /// This requires the `bumpalo` feature.
+    ///
+    /// # Errors
+    ///
+    /// This will return [`ExpansionError`] if the expansion fails.
+    ///
+    /// # See also
+    ///
+    /// * [`PrefixMapping::expand_curie()`]
+    /// * [`PrefixMapping::expand_curie_string_in()`]
+    pub fn expand_curie_in<'b>(
+        &self,
+        curie: &Curie,
+        bump: &'b Bump,
+    ) -> Result<&'b str, ExpansionError> {
+        let resolved = self.resolve(curie)?;
+        let mut iri = bumpalo::collections::String::with_capacity_in(resolved.len(), bump);
+        for part in resolved.parts() {
+            iri.push_str(part);
+        }
+        Ok(iri.into_bump_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expand_in_bump() {
+        let mut mapping = PrefixMapping::default();
+        mapping.set_default("http://example.com/");
+
+        let bump = Bump::new();
+        let first = mapping.expand_curie_in(&Curie::new(None, "a"), &bump);
+        let second = mapping.expand_curie_string_in("b", &bump);
+        assert_eq!(first, Ok("http://example.com/a"));
+        assert_eq!(second, Ok("http://example.com/b"));
+        assert_eq!(
+            mapping.expand_curie_string_in("foaf:Agent", &bump),
+            Err(ExpansionError::UnknownPrefix(String::from("foaf")))
+        );
+    }
+
+    #[test]
+    fn expand_template_in_bump() {
+        let mut mapping = PrefixMapping::default();
+        mapping
+            .add_prefix_template("doi", "https://doi.org/{id}")
+            .unwrap();
+        mapping
+            .add_prefix_template("c", "https://example.com/entry/$1.html")
+            .unwrap();
+
+        let bump = Bump::new();
+        for curie in [
+            Curie::new(Some("doi"), "10.1/x"),
+            Curie::new(Some("c"), "42"),
+        ] {
+            assert_eq!(
+                mapping.expand_curie_in(&curie, &bump).map(String::from),
+                mapping.expand_curie(&curie)
+            );
+        }
+    }
+
+    #[test]
+    fn expand_term_in_bump() {
+        let mut mapping = PrefixMapping::default();
+        mapping.add_term("name", "http://xmlns.com/foaf/0.1/name");
+        let curie = Curie::new(None, "name");
+
+        let bump = Bump::new();
+        assert_eq!(
+            mapping.expand_curie_in(&curie, &bump).map(String::from),
+            mapping.expand_curie(&curie)
+        );
+    }
+}
@@ -0,0 +1,368 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{InvalidPrefixError, PrefixMapping};
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq)]
+enum LayerEntry {
+    Bind {
+        prefix: String,
+        namespace: String,
+        protected: bool,
+    },
+    Remove(String),
+}
+
+/// One context to be applied by [`PrefixMapping::compose()`].
+///
+/// A layer binds prefixes, possibly protecting them, removes prefixes,
+/// as with `"foo": null` in a JSON-LD context, and may change the
+/// default prefix. Entries are applied in the order they were added.
+///
+/// # Examples
+///
+/// ```
+/// use curie::ContextLayer;
+///
+/// let layer = ContextLayer::new()
+///     .bind_protected("rdf", "http://www.w3.org/1999/02/22-rdf-syntax-ns#")
+///     .bind("ex", "http://example.com/")
+///     .remove("tmp")
+///     .default_namespace(Some("http://schema.org/"));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ContextLayer {
+    entries: Vec<LayerEntry>,
+    default: Option<Option<String>>,
+}
+
+impl ContextLayer {
+    /// Construct an empty layer.
+    #[must_use]
+    pub fn new() -> Self {
+        ContextLayer::default()
+    }
+
+    /// Bind a prefix to a namespace.
+    #[must_use]
+    pub fn bind(mut self, prefix: &str, namespace: &str) -> Self {
+        self.entries.push(LayerEntry::Bind {
+            prefix: String::from(prefix),
+            namespace: String::from(namespace),
+            protected: false,
+        });
+        self
+    }
+
+    /// Bind a prefix to a namespace and protect it from being changed
+    /// by later layers.
+    #[must_use]
+    pub fn bind_protected(mut self, prefix: &str, namespace: &str) -> Self {
+        self.entries.push(LayerEntry::Bind {
+            prefix: String::from(prefix),
+            namespace: String::from(namespace),
+            protected: true,
+        });
+        self
+    }
+
+    /// Remove the binding of a prefix made by an earlier layer.
+    #[must_use]
+    pub fn remove(mut self, prefix: &str) -> Self {
+        self.entries.push(LayerEntry::Remove(String::from(prefix)));
+        self
+    }
+
+    /// Set the default prefix, or clear it with `None`.
+    ///
+    /// A layer that doesn't call this leaves the default unchanged.
+    #[must_use]
+    pub fn default_namespace(mut self, namespace: Option<&str>) -> Self {
+        self.default = Some(namespace.map(String::from));
+        self
+    }
+}
+
+impl From<&PrefixMapping> for ContextLayer {
+    /// A layer binding everything in a mapping, including its default
+    /// and with its protected prefixes protected.
+    fn from(mapping: &PrefixMapping) -> Self {
+        ContextLayer {
+            entries: mapping
+                .mapping
+                .iter()
+                .map(|(prefix, namespace)| LayerEntry::Bind {
+                    prefix: prefix.clone(),
+                    namespace: namespace.clone(),
+                    protected: mapping.is_protected(prefix),
+                })
+                .collect(),
+            default: mapping
+                .default
+                .as_ref()
+                .map(|default| Some(default.clone())),
+        }
+    }
+}
+
+/// A change made by a [`ContextLayer`] during [`PrefixMapping::compose()`],
+/// or found by [`PrefixMapping::diff()`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PrefixChange {
+    /// A prefix that was not bound was bound.
+    Added {
+        /// The prefix.
+        prefix: String,
+        /// The namespace it was bound to.
+        namespace: String,
+    },
+    /// A bound prefix was bound to a different namespace.
+    Rebound {
+        /// The prefix.
+        prefix: String,
+        /// The namespace it was bound to before.
+        previous: String,
+        /// The namespace it is bound to now.
+        namespace: String,
+    },
+    /// A bound prefix was removed.
+    Removed {
+        /// The prefix.
+        prefix: String,
+        /// The namespace it was bound to.
+        namespace: String,
+    },
+    /// The default prefix was changed.
+    DefaultChanged {
+        /// The previous default, if any.
+        previous: Option<String>,
+        /// The new default, if any.
+        default: Option<String>,
+    },
+}
+
+/// The error returned by [`PrefixMapping::compose()`] when a layer
+/// makes a change that is not allowed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ComposeError {
+    /// The index of the layer that made the change.
+    pub layer: usize,
+    /// The prefix that was changed.
+    pub prefix: String,
+    /// Why the change is not allowed.
+    pub error: InvalidPrefixError,
+}
+
+impl fmt::Display for ComposeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let reason = match self.error {
+            InvalidPrefixError::ReservedPrefix => "is reserved",
+            InvalidPrefixError::ProtectedPrefix => "is protected",
+            InvalidPrefixError::InvalidName => "is not an NCName",
+            InvalidPrefixError::InvalidNamespace => "would be bound to an invalid namespace",
+            InvalidPrefixError::AlreadyBound => "is already bound",
+        };
+        write!(
+            f,
+            "layer {} can't change `{}`, which {reason}",
+            self.layer, self.prefix
+        )
+    }
+}
+
+impl std::error::Error for ComposeError {}
+
+impl PrefixMapping {
+    /// Compose a mapping from several contexts, applied in order.
+    ///
+    /// The rules are:
+    ///
+    /// * A later layer overrides the bindings and default of earlier layers.
+    /// * A removal removes the binding made by earlier layers, if any.
+    /// * A protected prefix can't be rebound to a different namespace or
+    ///   removed by a later layer. Binding it to the same namespace again
+    ///   is allowed and is not a change. Protected prefixes remain
+    ///   protected in the resulting mapping.
+    ///
+    /// Along with the mapping, the changes that each layer made are
+    /// returned, with one entry per layer.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ComposeError`] for the first change that breaks the
+    /// rules above or binds the reserved prefix `_`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::{ContextLayer, PrefixChange, PrefixMapping};
+    ///
+    /// let platform = ContextLayer::new()
+    ///     .bind_protected("schema", "http://schema.org/");
+    /// let user = ContextLayer::new()
+    ///     .bind("ex", "http://example.com/")
+    ///     .bind("schema", "http://schema.org/");
+    ///
+    /// let (mapping, changes) = PrefixMapping::compose(&[platform, user]).unwrap();
+    /// assert_eq!(mapping.expand_curie_string("ex:a"),
+    ///            Ok(String::from("http://example.com/a")));
+    /// assert_eq!(changes[1], [PrefixChange::Added {
+    ///     prefix: String::from("ex"),
+    ///     namespace: String::from("http://example.com/"),
+    /// }]);
+    ///
+    /// let hostile = ContextLayer::new().bind("schema", "http://example.com/schema/");
+    /// let err = PrefixMapping::compose(&[ContextLayer::from(&mapping), hostile]).unwrap_err();
+    /// assert_eq!((err.layer, err.prefix.as_str()), (1, "schema"));
+    /// ```
+    pub fn compose(
+        layers: &[ContextLayer],
+    ) -> Result<(PrefixMapping, Vec<Vec<PrefixChange>>), ComposeError> {
+        let mut mapping = PrefixMapping::default();
+        let mut changes = Vec::with_capacity(layers.len());
+        for (index, layer) in layers.iter().enumerate() {
+            let layer_changes =
+                mapping
+                    .apply_layer(layer)
+                    .map_err(|(prefix, error)| ComposeError {
+                        layer: index,
+                        prefix,
+                        error,
+                    })?;
+            changes.push(layer_changes);
+        }
+        Ok((mapping, changes))
+    }
+
+    fn apply_layer(
+        &mut self,
+        layer: &ContextLayer,
+    ) -> Result<Vec<PrefixChange>, (String, InvalidPrefixError)> {
+        let mut changes = Vec::new();
+        if let Some(ref default) = layer.default {
+            if self.default != *default {
+                changes.push(PrefixChange::DefaultChanged {
+                    previous: self.default.take(),
+                    default: default.clone(),
+                });
+                self.default.clone_from(default);
+            }
+        }
+        for entry in &layer.entries {
+            match entry {
+                LayerEntry::Bind {
+                    prefix,
+                    namespace,
+                    protected,
+                } => {
+                    let previous = self.mapping.get(prefix).cloned();
+                    self.add_prefix(prefix, namespace)
+                        .map_err(|error| (prefix.clone(), error))?;
+                    if *protected {
+                        self.protect_prefix(prefix);
+                    }
+                    match previous {
+                        None => changes.push(PrefixChange::Added {
+                            prefix: prefix.clone(),
+                            namespace: namespace.clone(),
+                        }),
+                        Some(previous) if previous != *namespace => {
+                            changes.push(PrefixChange::Rebound {
+                                prefix: prefix.clone(),
+                                previous,
+                                namespace: namespace.clone(),
+                            });
+                        }
+                        Some(_) => {}
+                    }
+                }
+                LayerEntry::Remove(prefix) => {
+                    let previous = self
+                        .remove_prefix(prefix)
+                        .map_err(|error| (prefix.clone(), error))?;
+                    if let Some(namespace) = previous {
+                        changes.push(PrefixChange::Removed {
+                            prefix: prefix.clone(),
+                            namespace,
+                        });
+                    }
+                }
+            }
+        }
+        Ok(changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn override_and_remove() {
+        let base = ContextLayer::new()
+            .bind("a", "http://example.com/a/")
+            .bind("b", "http://example.com/b/")
+            .default_namespace(Some("http://example.com/"));
+        let update = ContextLayer::new()
+            .bind("a", "http://example.org/a/")
+            .remove("b")
+            .remove("c")
+            .default_namespace(None);
+
+        let (mapping, changes) = PrefixMapping::compose(&[base, update]).unwrap();
+        assert_eq!(
+            mapping.mappings().collect::<Vec<_>>(),
+            [("a", "http://example.org/a/")]
+        );
+        assert_eq!(changes[0].len(), 3);
+        assert_eq!(
+            changes[1],
+            [
+                PrefixChange::DefaultChanged {
+                    previous: Some(String::from("http://example.com/")),
+                    default: None,
+                },
+                PrefixChange::Rebound {
+                    prefix: String::from("a"),
+                    previous: String::from("http://example.com/a/"),
+                    namespace: String::from("http://example.org/a/"),
+                },
+                PrefixChange::Removed {
+                    prefix: String::from("b"),
+                    namespace: String::from("http://example.com/b/"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn protection() {
+        let platform = ContextLayer::new().bind_protected("p", "http://example.com/p/");
+
+        let (mapping, changes) = PrefixMapping::compose(&[
+            platform.clone(),
+            ContextLayer::new().bind("p", "http://example.com/p/"),
+        ])
+        .unwrap();
+        assert!(mapping.is_protected("p"));
+        assert!(changes[1].is_empty());
+
+        let err = PrefixMapping::compose(&[platform, ContextLayer::new().remove("p")]).unwrap_err();
+        assert_eq!(
+            err,
+            ComposeError {
+                layer: 1,
+                prefix: String::from("p"),
+                error: InvalidPrefixError::ProtectedPrefix,
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "layer 1 can't change `p`, which is protected"
+        );
+    }
+}
@@ -0,0 +1,169 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{Curie, CurieBuf, ExpansionError, InvalidPrefixError, PrefixMapping, ShrinkError};
+use std::sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// A [`PrefixMapping`] that can be read and changed from many threads
+/// at once.
+///
+/// Expansion and shrinking take a shared lock, so any number of threads
+/// can do them concurrently; changes take an exclusive lock. This suits
+/// long-running servers where prefixes are occasionally added, say by
+/// an administrative endpoint, while request handlers expand terms.
+///
+/// A panic while holding the lock doesn't make the mapping unusable:
+/// the mapping is used in whatever state the panic left it in.
+///
+/// # Examples
+///
+/// ```
+/// use curie::ConcurrentPrefixMapping;
+/// use std::sync::Arc;
+///
+/// let mapping = Arc::new(ConcurrentPrefixMapping::default());
+/// mapping.add_prefix("foaf", "http://xmlns.com/foaf/0.1/").unwrap();
+///
+/// let reader = Arc::clone(&mapping);
+/// let handle = std::thread::spawn(move || reader.expand_curie_string("foaf:Agent"));
+/// mapping.add_prefix("ex", "http://example.com/").unwrap();
+///
+/// assert_eq!(handle.join().unwrap(),
+///            Ok(String::from("http://xmlns.com/foaf/0.1/Agent")));
+/// ```
+#[derive(Debug, Default)]
+pub struct ConcurrentPrefixMapping {
+    inner: RwLock<PrefixMapping>,
+}
+
+impl ConcurrentPrefixMapping {
+    /// Construct a concurrent mapping starting from `mapping`.
+    #[must_use]
+    pub fn new(mapping: PrefixMapping) -> Self {
+        ConcurrentPrefixMapping {
+            inner: RwLock::new(mapping),
+        }
+    }
+
+    /// Lock the mapping for reading, to make several lookups against
+    /// the same state.
+    ///
+    /// Changes wait until the guard is dropped.
+    pub fn read(&self) -> RwLockReadGuard<'_, PrefixMapping> {
+        self.inner.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Lock the mapping for writing, to make several changes at once.
+    ///
+    /// Readers wait until the guard is dropped, so this should be held
+    /// briefly.
+    pub fn write(&self) -> RwLockWriteGuard<'_, PrefixMapping> {
+        self.inner.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Set a default prefix.
+    ///
+    /// See [`PrefixMapping::set_default()`].
+    pub fn set_default(&self, default: &str) {
+        self.write().set_default(default);
+    }
+
+    /// Add a prefix to the mapping.
+    ///
+    /// # Errors
+    ///
+    /// See [`PrefixMapping::add_prefix()`].
+    pub fn add_prefix(&self, prefix: &str, value: &str) -> Result<(), InvalidPrefixError> {
+        self.write().add_prefix(prefix, value)
+    }
+
+    /// Remove a prefix from the mapping.
+    ///
+    /// # Errors
+    ///
+    /// See [`PrefixMapping::remove_prefix()`].
+    pub fn remove_prefix(&self, prefix: &str) -> Result<Option<String>, InvalidPrefixError> {
+        self.write().remove_prefix(prefix)
+    }
+
+    /// Expand a CURIE, returning a complete IRI.
+    ///
+    /// # Errors
+    ///
+    /// See [`PrefixMapping::expand_curie_string()`].
+    pub fn expand_curie_string(&self, curie_str: &str) -> Result<String, ExpansionError> {
+        self.read().expand_curie_string(curie_str)
+    }
+
+    /// Expand a parsed [`Curie`], returning a complete IRI.
+    ///
+    /// # Errors
+    ///
+    /// See [`PrefixMapping::expand_curie()`].
+    pub fn expand_curie(&self, curie: &Curie) -> Result<String, ExpansionError> {
+        self.read().expand_curie(curie)
+    }
+
+    /// Shrink an IRI, returning an owned CURIE.
+    ///
+    /// # Errors
+    ///
+    /// See [`PrefixMapping::shrink_iri()`].
+    pub fn shrink_iri(&self, iri: &str) -> Result<CurieBuf, ShrinkError> {
+        self.read().shrink_iri(iri).map(CurieBuf::from)
+    }
+
+    /// Consume this, returning the mapping.
+    #[must_use]
+    pub fn into_inner(self) -> PrefixMapping {
+        self.inner
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl From<PrefixMapping> for ConcurrentPrefixMapping {
+    fn from(mapping: PrefixMapping) -> Self {
+        ConcurrentPrefixMapping::new(mapping)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shared_mutation() {
+        let mapping = ConcurrentPrefixMapping::default();
+        std::thread::scope(|scope| {
+            for n in 0..4 {
+                let mapping = &mapping;
+                scope.spawn(move || {
+                    let prefix = format!("p{n}");
+                    let namespace = format!("http://example.com/{n}/");
+                    mapping.add_prefix(&prefix, &namespace).unwrap();
+                    assert_eq!(
+                        mapping.expand_curie_string(&format!("{prefix}:a")),
+                        Ok(format!("{namespace}a"))
+                    );
+                });
+            }
+        });
+        assert_eq!(mapping.read().mappings().count(), 4);
+        assert_eq!(
+            mapping.shrink_iri("http://example.com/2/a"),
+            Ok(CurieBuf::new(Some("p2"), "a"))
+        );
+
+        mapping.remove_prefix("p2").unwrap();
+        mapping.set_default("http://example.com/");
+        assert_eq!(
+            mapping.shrink_iri("http://example.com/2/a"),
+            Ok(CurieBuf::new(None, "2/a"))
+        );
+        assert_eq!(mapping.into_inner().mappings().count(), 3);
+    }
+}
@@ -0,0 +1,126 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests of conformance with the [CURIE Syntax 1.0] recommendation,
+//! using the examples and rules of its syntax section.
+//!
+//! [CURIE Syntax 1.0]: https://www.w3.org/TR/curie/
+
+use crate::{is_valid_curie, ExpansionError, InvalidPrefixError, PrefixMapping};
+
+/// A strict mapping with the prefixes used by the examples.
+fn mapping() -> PrefixMapping {
+    let mut mapping = PrefixMapping::default();
+    mapping.set_strict(true);
+    mapping.add_prefix("isbn", "urn:ISBN:").unwrap();
+    mapping
+        .add_prefix("dbr", "http://dbpedia.org/resource/")
+        .unwrap();
+    mapping
+        .add_prefix("foaf", "http://xmlns.com/foaf/0.1/")
+        .unwrap();
+    mapping
+        .add_prefix("", "http://www.w3.org/1999/xhtml/vocab#")
+        .unwrap();
+    mapping
+}
+
+/// Expected expansions of CURIEs and safe CURIEs.
+const EXPANSIONS: &[(&str, &str)] = &[
+    ("isbn:0321154991", "urn:ISBN:0321154991"),
+    ("[isbn:0321154991]", "urn:ISBN:0321154991"),
+    (
+        "dbr:Albert_Einstein",
+        "http://dbpedia.org/resource/Albert_Einstein",
+    ),
+    (
+        "[dbr:Albert_Einstein]",
+        "http://dbpedia.org/resource/Albert_Einstein",
+    ),
+    // The reference is an `irelative-ref`, which may be empty or
+    // contain further colons.
+    ("foaf:", "http://xmlns.com/foaf/0.1/"),
+    (
+        "dbr:Category:Physicists",
+        "http://dbpedia.org/resource/Category:Physicists",
+    ),
+    // A CURIE with an empty prefix uses the "no prefix" mapping.
+    (":next", "http://www.w3.org/1999/xhtml/vocab#next"),
+    ("[:next]", "http://www.w3.org/1999/xhtml/vocab#next"),
+];
+
+/// Strings that are not CURIEs or safe CURIEs.
+const INVALID: &[&str] = &[
+    // A prefix must be an `NCName`.
+    "1st:thing",
+    "-a:thing",
+    "a b:thing",
+    // The brackets of a safe CURIE must be balanced.
+    "[isbn:0321154991",
+    "[[isbn:0321154991]]",
+    // A reference can't contain characters that can't be in an IRI.
+    "isbn:0321 154991",
+    "foaf:<name>",
+];
+
+#[test]
+fn expansions() {
+    let mapping = mapping();
+    for &(curie, iri) in EXPANSIONS {
+        assert!(is_valid_curie(curie), "{curie} should be valid");
+        assert_eq!(
+            mapping.expand_safe_curie(curie),
+            Ok(String::from(iri)),
+            "{curie} should expand to {iri}"
+        );
+    }
+}
+
+#[test]
+fn invalid_syntax() {
+    for &curie in INVALID {
+        assert!(!is_valid_curie(curie), "{curie} should be invalid");
+    }
+}
+
+#[test]
+fn default_prefix() {
+    // A CURIE without a separator uses the default prefix mapping,
+    // which must be set.
+    let mut mapping = mapping();
+    assert_eq!(
+        mapping.expand_curie_string("thing"),
+        Err(ExpansionError::MissingDefault)
+    );
+    mapping.set_default("http://example.com/");
+    assert_eq!(
+        mapping.expand_curie_string("thing"),
+        Ok(String::from("http://example.com/thing"))
+    );
+}
+
+#[test]
+fn prefix_rules() {
+    let mut mapping = mapping();
+    // `_` is reserved for blank nodes.
+    assert_eq!(
+        mapping.add_prefix("_", "http://example.com/"),
+        Err(InvalidPrefixError::ReservedPrefix)
+    );
+    assert_eq!(
+        mapping.add_prefix("a:b", "http://example.com/"),
+        Err(InvalidPrefixError::InvalidName)
+    );
+    // Prefixes are case sensitive.
+    assert_eq!(
+        mapping.expand_curie_string("ISBN:0321154991"),
+        Err(ExpansionError::UnknownPrefix(String::from("ISBN")))
+    );
+    assert!(matches!(
+        mapping.expand_curie_string("isbn:0321 154991"),
+        Err(ExpansionError::InvalidCharacter { .. })
+    ));
+}
@@ -0,0 +1,238 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{Curie, PrefixMapping};
+use indexmap::{IndexMap, IndexSet};
+use std::fmt;
+
+/// How the prefixes of a [`PrefixMapping`] cover a corpus of IRIs and
+/// CURIEs.
+///
+/// This is created by [`PrefixMapping::coverage()`].
+///
+/// # Examples
+///
+/// ```
+/// use curie::PrefixMapping;
+///
+/// let mut mapping = PrefixMapping::default();
+/// mapping.add_prefix("foaf", "http://xmlns.com/foaf/0.1/").unwrap();
+/// mapping.add_prefix("dc", "http://purl.org/dc/terms/").unwrap();
+///
+/// let report = mapping.coverage([
+///     "foaf:name",
+///     "http://xmlns.com/foaf/0.1/knows",
+///     "http://schema.org/Person",
+///     "http://schema.org/name",
+/// ]);
+///
+/// assert_eq!(report.used_prefixes().collect::<Vec<_>>(), [("foaf", 2)]);
+/// assert_eq!(report.unused_prefixes().collect::<Vec<_>>(), ["dc"]);
+/// assert_eq!(report.to_string(), "\
+/// used prefixes:
+///   foaf: 2
+/// unused prefixes:
+///   dc
+/// unmatched namespaces:
+///   http://schema.org/: 2 IRIs
+/// ");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CoverageReport {
+    used: IndexMap<String, usize>,
+    default_uses: usize,
+    unused: Vec<String>,
+    unmatched: IndexMap<String, IndexSet<String>>,
+}
+
+impl CoverageReport {
+    /// The prefixes that were used, with the number of uses, in the
+    /// order of the mapping.
+    pub fn used_prefixes(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.used
+            .iter()
+            .map(|(prefix, &uses)| (prefix.as_str(), uses))
+    }
+
+    /// The number of uses of the default namespace.
+    #[must_use]
+    pub fn default_uses(&self) -> usize {
+        self.default_uses
+    }
+
+    /// The prefixes of the mapping that were not used, in the order of
+    /// the mapping.
+    pub fn unused_prefixes(&self) -> impl Iterator<Item = &str> {
+        self.unused.iter().map(String::as_str)
+    }
+
+    /// The IRIs that no prefix matched, grouped by their inferred
+    /// namespace, in the order they were first seen.
+    ///
+    /// The namespace of an IRI is inferred to be everything up to its
+    /// last `#`, or otherwise its last `/` or `:`. Each IRI is listed
+    /// once.
+    pub fn unmatched(&self) -> impl Iterator<Item = (&str, impl Iterator<Item = &str>)> {
+        self.unmatched
+            .iter()
+            .map(|(namespace, iris)| (namespace.as_str(), iris.iter().map(String::as_str)))
+    }
+
+    /// The inferred namespaces of the IRIs that no prefix matched, in
+    /// the order they were first seen.
+    pub fn unmatched_namespaces(&self) -> impl Iterator<Item = &str> {
+        self.unmatched.keys().map(String::as_str)
+    }
+}
+
+impl fmt::Display for CoverageReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.used.is_empty() || self.default_uses > 0 {
+            writeln!(f, "used prefixes:")?;
+            for (prefix, uses) in &self.used {
+                writeln!(f, "  {prefix}: {uses}")?;
+            }
+            if self.default_uses > 0 {
+                writeln!(f, "  (default): {}", self.default_uses)?;
+            }
+        }
+        if !self.unused.is_empty() {
+            writeln!(f, "unused prefixes:")?;
+            for prefix in &self.unused {
+                writeln!(f, "  {prefix}")?;
+            }
+        }
+        if !self.unmatched.is_empty() {
+            writeln!(f, "unmatched namespaces:")?;
+            for (namespace, iris) in &self.unmatched {
+                let plural = if iris.len() == 1 { "" } else { "s" };
+                writeln!(f, "  {namespace}: {} IRI{plural}", iris.len())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl PrefixMapping {
+    /// Report how the prefixes of this mapping cover a corpus of IRIs
+    /// and CURIEs.
+    ///
+    /// An item whose prefix is mapped is counted as a use of that
+    /// prefix. Anything else is taken to be an IRI and shrunk, counting
+    /// a use of the prefix or default that shrinks it, or recording it
+    /// as unmatched.
+    #[must_use]
+    pub fn coverage<I>(&self, items: I) -> CoverageReport
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let mut uses: IndexMap<&str, usize> = self
+            .mapping
+            .keys()
+            .map(|prefix| (prefix.as_str(), 0))
+            .collect();
+        let mut report = CoverageReport::default();
+        for item in items {
+            let item = item.as_ref();
+            let curie = Curie::split(item);
+            if let Some(count) = curie.prefix.and_then(|prefix| uses.get_mut(prefix)) {
+                *count += 1;
+                continue;
+            }
+            match self.shrink_iri(item) {
+                Ok(Curie {
+                    prefix: Some(prefix),
+                    ..
+                }) => {
+                    if let Some(count) = uses.get_mut(prefix) {
+                        *count += 1;
+                    }
+                }
+                Ok(Curie { prefix: None, .. }) => report.default_uses += 1,
+                Err(_) => {
+                    report
+                        .unmatched
+                        .entry(String::from(infer_namespace(item)))
+                        .or_default()
+                        .insert(String::from(item));
+                }
+            }
+        }
+        for (prefix, count) in uses {
+            if count == 0 {
+                report.unused.push(String::from(prefix));
+            } else {
+                report.used.insert(String::from(prefix), count);
+            }
+        }
+        report
+    }
+}
+
+/// Guess the namespace of an IRI: everything up to its last `#`, or
+/// otherwise its last `/` or `:`.
+pub(crate) fn infer_namespace(iri: &str) -> &str {
+    let end = iri
+        .rfind('#')
+        .or_else(|| iri.rfind('/'))
+        .or_else(|| iri.rfind(':'))
+        .map_or(0, |idx| idx + 1);
+    &iri[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coverage() {
+        let mut mapping = PrefixMapping::default();
+        mapping.add_prefix("ex", "http://example.com/").unwrap();
+        mapping
+            .add_prefix("unused", "http://example.com/unused/")
+            .unwrap();
+        mapping.set_default("http://example.com/default/");
+
+        let report = mapping.coverage(vec![
+            String::from("ex:a"),
+            String::from("http://example.com/default/b"),
+            String::from("http://example.org/v#a"),
+            String::from("http://example.org/v#a"),
+            String::from("urn:isbn:123"),
+            String::from("other:thing"),
+        ]);
+        assert_eq!(report.used_prefixes().collect::<Vec<_>>(), [("ex", 1)]);
+        assert_eq!(report.default_uses(), 1);
+        assert_eq!(report.unused_prefixes().collect::<Vec<_>>(), ["unused"]);
+        assert_eq!(
+            report
+                .unmatched()
+                .map(|(namespace, iris)| (namespace, iris.count()))
+                .collect::<Vec<_>>(),
+            [
+                ("http://example.org/v#", 1),
+                ("urn:isbn:", 1),
+                ("other:", 1)
+            ]
+        );
+        assert!(report.to_string().contains("  (default): 1\n"));
+    }
+
+    #[test]
+    fn namespaces() {
+        assert_eq!(
+            infer_namespace("http://example.com/a/b"),
+            "http://example.com/a/"
+        );
+        assert_eq!(
+            infer_namespace("http://example.com/a#b/c"),
+            "http://example.com/a#"
+        );
+        assert_eq!(infer_namespace("urn:isbn:123"), "urn:isbn:");
+        assert_eq!(infer_namespace("plain"), "");
+    }
+}
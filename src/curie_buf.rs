@@ -0,0 +1,190 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::Curie;
+use std::cmp::Ordering;
+use std::fmt;
+
+/// An owned CURIE.
+///
+/// Unlike [`Curie`], which borrows its prefix and reference from the
+/// text being parsed, a `CurieBuf` owns them, so it can be stored in
+/// long-lived data structures.
+///
+/// A `CurieBuf` relates to a [`Curie`] much as a `String` does to a
+/// `str`, without the standard traits that tie those two together.
+/// `Deref` and `Borrow` must return a reference, `&Curie`, to a value
+/// stored inside the `CurieBuf`, but a `Curie` is a pair of borrowed
+/// strings that is built when it is needed, so there is none to
+/// return. `ToOwned` for `Curie` needs its owned type to implement
+/// `Borrow<Curie>`, so it can't be provided either, and a map keyed by
+/// `CurieBuf` can't be looked up with a `Curie`.
+///
+/// Instead, use [`CurieBuf::as_curie()`] or `Curie::from(&buf)` to
+/// borrow one, and [`Curie::to_curie_buf()`] to make an owned copy.
+/// The two types can be compared directly, and they hash and order
+/// the same way.
+///
+/// # Examples
+///
+/// ```
+/// use curie::{Curie, CurieBuf, PrefixMapping};
+///
+/// let curie = CurieBuf::from(Curie::new(Some("foaf"), "Agent"));
+/// assert_eq!(curie.to_string(), "foaf:Agent");
+///
+/// let mut mapping = PrefixMapping::default();
+/// mapping.add_prefix("foaf", "http://xmlns.com/foaf/0.1/").unwrap();
+/// assert_eq!(mapping.expand_curie(&curie.as_curie()),
+///            Ok(String::from("http://xmlns.com/foaf/0.1/Agent")));
+/// ```
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct CurieBuf {
+    prefix: Option<String>,
+    reference: String,
+}
+
+impl CurieBuf {
+    /// Construct a `CurieBuf` from a prefix and reference.
+    #[must_use]
+    pub fn new(prefix: Option<&str>, reference: &str) -> Self {
+        CurieBuf {
+            prefix: prefix.map(String::from),
+            reference: String::from(reference),
+        }
+    }
+
+    /// The prefix, or `None` if this uses the default namespace.
+    #[must_use]
+    pub fn prefix(&self) -> Option<&str> {
+        self.prefix.as_deref()
+    }
+
+    /// The reference, which follows the prefix.
+    #[must_use]
+    pub fn reference(&self) -> &str {
+        &self.reference
+    }
+
+    /// Split this into its prefix and reference.
+    #[must_use]
+    pub fn into_parts(self) -> (Option<String>, String) {
+        (self.prefix, self.reference)
+    }
+
+    /// Borrow this as a [`Curie`].
+    #[must_use]
+    pub fn as_curie(&self) -> Curie<'_> {
+        Curie::new(self.prefix.as_deref(), &self.reference)
+    }
+}
+
+impl From<Curie<'_>> for CurieBuf {
+    fn from(c: Curie<'_>) -> CurieBuf {
+        CurieBuf::new(c.prefix, c.reference)
+    }
+}
+
+impl From<&Curie<'_>> for CurieBuf {
+    fn from(c: &Curie<'_>) -> CurieBuf {
+        CurieBuf::new(c.prefix, c.reference)
+    }
+}
+
+impl<'a> From<&'a CurieBuf> for Curie<'a> {
+    fn from(c: &'a CurieBuf) -> Curie<'a> {
+        c.as_curie()
+    }
+}
+
+impl Curie<'_> {
+    /// Copy this into an owned [`CurieBuf`].
+    #[must_use]
+    pub fn to_curie_buf(&self) -> CurieBuf {
+        CurieBuf::from(self)
+    }
+}
+
+impl PartialEq<Curie<'_>> for CurieBuf {
+    fn eq(&self, other: &Curie<'_>) -> bool {
+        self.as_curie() == *other
+    }
+}
+
+impl PartialEq<CurieBuf> for Curie<'_> {
+    fn eq(&self, other: &CurieBuf) -> bool {
+        *self == other.as_curie()
+    }
+}
+
+impl PartialOrd<Curie<'_>> for CurieBuf {
+    fn partial_cmp(&self, other: &Curie<'_>) -> Option<Ordering> {
+        self.as_curie().partial_cmp(other)
+    }
+}
+
+impl PartialOrd<CurieBuf> for Curie<'_> {
+    fn partial_cmp(&self, other: &CurieBuf) -> Option<Ordering> {
+        self.partial_cmp(&other.as_curie())
+    }
+}
+
+impl From<CurieBuf> for String {
+    fn from(c: CurieBuf) -> String {
+        format!("{c}")
+    }
+}
+
+impl fmt::Display for CurieBuf {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_curie().fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn curie_buf_conversions() {
+        let curie = Curie::new(Some("foaf"), "Agent");
+        let buf = CurieBuf::from(&curie);
+        assert_eq!(buf.as_curie(), curie);
+        assert_eq!(String::from(buf), "foaf:Agent");
+
+        let buf = CurieBuf::new(None, "Agent");
+        assert_eq!(buf.as_curie(), Curie::new(None, "Agent"));
+        assert_eq!(buf.to_string(), "Agent");
+        assert_eq!((buf.prefix(), buf.reference()), (None, "Agent"));
+        assert_eq!(buf.into_parts(), (None, String::from("Agent")));
+
+        let curie = Curie::new(Some("foaf"), "Agent");
+        let buf = curie.to_curie_buf();
+        assert_eq!(buf, curie);
+        assert_eq!(curie, buf);
+        assert_eq!(Curie::from(&buf), curie);
+        assert_ne!(buf, Curie::new(None, "Agent"));
+    }
+
+    #[test]
+    fn curie_buf_matches_curie() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let hash = |value: &dyn Fn(&mut DefaultHasher)| {
+            let mut hasher = DefaultHasher::new();
+            value(&mut hasher);
+            hasher.finish()
+        };
+        let curie = Curie::new(Some("foaf"), "Agent");
+        let buf = curie.to_curie_buf();
+        assert_eq!(hash(&|h| curie.hash(h)), hash(&|h| buf.hash(h)));
+
+        assert!(buf < Curie::new(Some("foaf"), "Person"));
+        assert!(Curie::new(None, "Agent") < buf);
+        assert_eq!(buf.partial_cmp(&curie), Some(Ordering::Equal));
+    }
+}
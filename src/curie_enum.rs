@@ -0,0 +1,141 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/// Define an enum whose variants each stand for a CURIE.
+///
+/// Each variant is followed by `=>` and its CURIE as a string literal.
+/// Attributes, including doc comments and derives, are passed through
+/// to the enum and its variants.
+///
+/// The generated enum has:
+///
+/// * `ALL`, a slice of every variant in declaration order.
+/// * `as_str()`, returning the CURIE as written.
+/// * `to_curie()`, returning the CURIE as a [`Curie`](crate::Curie).
+/// * `from_curie()`, finding the variant for a [`Curie`](crate::Curie).
+/// * `expand()`, expanding the CURIE with a [`PrefixMapping`](crate::PrefixMapping).
+///
+/// # Examples
+///
+/// ```
+/// use curie::{curie_enum, Curie, PrefixMapping};
+///
+/// curie_enum! {
+///     /// Classes from FOAF.
+///     #[derive(Clone, Copy, Debug, PartialEq)]
+///     pub enum FoafClass {
+///         /// A person.
+///         Person => "foaf:Person",
+///         /// An agent.
+///         Agent => "foaf:Agent",
+///     }
+/// }
+///
+/// assert_eq!(FoafClass::Person.to_curie(), Curie::new(Some("foaf"), "Person"));
+/// assert_eq!(FoafClass::from_curie(&Curie::new(Some("foaf"), "Agent")),
+///            Some(FoafClass::Agent));
+/// assert_eq!(FoafClass::ALL, [FoafClass::Person, FoafClass::Agent]);
+///
+/// let mut mapping = PrefixMapping::default();
+/// mapping.add_prefix("foaf", "http://xmlns.com/foaf/0.1/").unwrap();
+/// assert_eq!(FoafClass::Agent.expand(&mapping),
+///            Ok(String::from("http://xmlns.com/foaf/0.1/Agent")));
+/// ```
+#[macro_export]
+macro_rules! curie_enum {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident => $curie:literal
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $(
+                $(#[$variant_meta])*
+                $variant,
+            )*
+        }
+
+        impl $name {
+            /// Every variant, in declaration order.
+            pub const ALL: &'static [$name] = &[$($name::$variant),*];
+
+            /// The CURIE for this variant, as written.
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $($name::$variant => $curie,)*
+                }
+            }
+
+            /// The CURIE for this variant.
+            pub fn to_curie(&self) -> $crate::Curie<'static> {
+                $crate::__private::split_curie(self.as_str())
+            }
+
+            /// Find the variant for a CURIE.
+            pub fn from_curie(curie: &$crate::Curie<'_>) -> ::core::option::Option<Self> {
+                $(
+                    if *curie == $crate::__private::split_curie($curie) {
+                        return ::core::option::Option::Some($name::$variant);
+                    }
+                )*
+                ::core::option::Option::None
+            }
+
+            /// Expand the CURIE for this variant.
+            ///
+            /// # Errors
+            ///
+            /// This will return an error if the expansion fails.
+            pub fn expand(
+                &self,
+                mapping: &$crate::PrefixMapping,
+            ) -> ::core::result::Result<::std::string::String, $crate::ExpansionError> {
+                mapping.expand_curie(&self.to_curie())
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Curie, ExpansionError, PrefixMapping};
+
+    curie_enum! {
+        #[derive(Debug, PartialEq)]
+        enum Term {
+            Label => "rdfs:label",
+            Thing => "Thing",
+        }
+    }
+
+    #[test]
+    fn curie_enum() {
+        assert_eq!(Term::ALL, [Term::Label, Term::Thing]);
+        assert_eq!(Term::Label.as_str(), "rdfs:label");
+        assert_eq!(Term::Thing.to_curie(), Curie::new(None, "Thing"));
+        assert_eq!(
+            Term::from_curie(&Curie::new(None, "Thing")),
+            Some(Term::Thing)
+        );
+        assert_eq!(Term::from_curie(&Curie::new(Some("rdfs"), "Thing")), None);
+
+        let mut mapping = PrefixMapping::default();
+        assert_eq!(
+            Term::Label.expand(&mapping),
+            Err(ExpansionError::UnknownPrefix(String::from("rdfs")))
+        );
+        mapping.set_default("http://example.com/");
+        assert_eq!(
+            Term::Thing.expand(&mapping),
+            Ok(String::from("http://example.com/Thing"))
+        );
+    }
+}
@@ -0,0 +1,197 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{Curie, ExpansionError, PrefixMapping, ShrinkError};
+
+/// Prefix mappings for a dataset of named graphs, as found in TriG
+/// and N-Quads.
+///
+/// There is a mapping for the dataset as a whole, and each named graph
+/// may have its own mapping. Lookups for a graph consult its mapping
+/// first and then fall back to the mapping of the dataset.
+///
+/// # Examples
+///
+/// ```
+/// use curie::DatasetPrefixes;
+///
+/// let mut prefixes = DatasetPrefixes::default();
+/// prefixes.dataset_mapping_mut()
+///     .add_prefix("foaf", "http://xmlns.com/foaf/0.1/").unwrap();
+/// prefixes.graph_mut("http://example.com/g1")
+///     .add_prefix("ex", "http://example.com/g1/").unwrap();
+///
+/// // Bindings of the dataset are visible from every graph.
+/// assert_eq!(prefixes.expand_curie_string(Some("http://example.com/g1"), "foaf:Agent"),
+///            Ok(String::from("http://xmlns.com/foaf/0.1/Agent")));
+/// assert_eq!(prefixes.expand_curie_string(Some("http://example.com/g1"), "ex:thing"),
+///            Ok(String::from("http://example.com/g1/thing")));
+///
+/// // Bindings of a graph are not visible from the dataset.
+/// assert!(prefixes.expand_curie_string(None, "ex:thing").is_err());
+/// ```
+#[derive(Debug, Default, PartialEq)]
+pub struct DatasetPrefixes {
+    dataset: PrefixMapping,
+    graphs: indexmap::IndexMap<String, PrefixMapping>,
+}
+
+impl DatasetPrefixes {
+    /// The mapping shared by the whole dataset.
+    #[must_use]
+    pub fn dataset_mapping(&self) -> &PrefixMapping {
+        &self.dataset
+    }
+
+    /// The mapping shared by the whole dataset, for modification.
+    pub fn dataset_mapping_mut(&mut self) -> &mut PrefixMapping {
+        &mut self.dataset
+    }
+
+    /// The mapping specific to a named graph, if there is one.
+    #[must_use]
+    pub fn graph(&self, graph: &str) -> Option<&PrefixMapping> {
+        self.graphs.get(graph)
+    }
+
+    /// The mapping specific to a named graph, for modification.
+    ///
+    /// An empty mapping is created for the graph if it has none.
+    pub fn graph_mut(&mut self, graph: &str) -> &mut PrefixMapping {
+        self.graphs.entry(String::from(graph)).or_default()
+    }
+
+    /// Remove the mapping specific to a named graph, returning it.
+    pub fn remove_graph(&mut self, graph: &str) -> Option<PrefixMapping> {
+        self.graphs.shift_remove(graph)
+    }
+
+    /// Iterate over the names of the graphs with their own mappings,
+    /// in the order they were created.
+    pub fn graphs(&self) -> impl Iterator<Item = &str> {
+        self.graphs.keys().map(String::as_str)
+    }
+
+    /// Expand a CURIE in the context of a graph, or of the dataset when
+    /// `graph` is `None`, returning a complete IRI.
+    ///
+    /// # Errors
+    ///
+    /// This will return [`ExpansionError`] if neither the mapping of the
+    /// graph nor the mapping of the dataset can expand the CURIE.
+    ///
+    /// # See also
+    ///
+    /// * [`DatasetPrefixes::expand_curie()`]
+    pub fn expand_curie_string(
+        &self,
+        graph: Option<&str>,
+        curie_str: &str,
+    ) -> Result<String, ExpansionError> {
+        self.expand_curie(graph, &Curie::split(curie_str))
+    }
+
+    /// Expand a parsed [`Curie`] in the context of a graph, or of the
+    /// dataset when `graph` is `None`, returning a complete IRI.
+    ///
+    /// # Errors
+    ///
+    /// This will return [`ExpansionError`] if neither the mapping of the
+    /// graph nor the mapping of the dataset can expand the CURIE.
+    pub fn expand_curie(
+        &self,
+        graph: Option<&str>,
+        curie: &Curie,
+    ) -> Result<String, ExpansionError> {
+        if let Some(mapping) = graph.and_then(|graph| self.graphs.get(graph)) {
+            if let Ok(iri) = mapping.expand_curie(curie) {
+                return Ok(iri);
+            }
+        }
+        self.dataset.expand_curie(curie)
+    }
+
+    /// Shrink an IRI in the context of a graph, or of the dataset when
+    /// `graph` is `None`, returning a [`Curie`].
+    ///
+    /// The mapping of the graph is tried before the mapping of the
+    /// dataset.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if neither mapping would allow the IRI to
+    /// be shortened.
+    pub fn shrink_iri<'a>(
+        &'a self,
+        graph: Option<&str>,
+        iri: &'a str,
+    ) -> Result<Curie<'a>, ShrinkError> {
+        if let Some(mapping) = graph.and_then(|graph| self.graphs.get(graph)) {
+            if let Ok(curie) = mapping.shrink_iri(iri) {
+                return Ok(curie);
+            }
+        }
+        self.dataset.shrink_iri(iri)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const G1: &str = "http://example.com/g1";
+
+    #[test]
+    fn graph_shadows_dataset() {
+        let mut prefixes = DatasetPrefixes::default();
+        prefixes
+            .dataset_mapping_mut()
+            .add_prefix("ex", "http://example.com/dataset/")
+            .unwrap();
+        prefixes
+            .graph_mut(G1)
+            .add_prefix("ex", "http://example.com/g1/")
+            .unwrap();
+
+        assert_eq!(
+            prefixes.expand_curie_string(Some(G1), "ex:a"),
+            Ok(String::from("http://example.com/g1/a"))
+        );
+        assert_eq!(
+            prefixes.expand_curie_string(Some("http://example.com/g2"), "ex:a"),
+            Ok(String::from("http://example.com/dataset/a"))
+        );
+        assert_eq!(
+            prefixes.shrink_iri(Some(G1), "http://example.com/dataset/a"),
+            Ok(Curie::new(Some("ex"), "a"))
+        );
+        assert_eq!(prefixes.graphs().collect::<Vec<_>>(), [G1]);
+
+        prefixes.remove_graph(G1);
+        assert_eq!(
+            prefixes.expand_curie_string(Some(G1), "ex:a"),
+            Ok(String::from("http://example.com/dataset/a"))
+        );
+    }
+
+    #[test]
+    fn default_falls_back() {
+        let mut prefixes = DatasetPrefixes::default();
+        prefixes.graph_mut(G1);
+        assert_eq!(
+            prefixes.expand_curie_string(Some(G1), "a"),
+            Err(ExpansionError::MissingDefault)
+        );
+
+        prefixes
+            .dataset_mapping_mut()
+            .set_default("http://example.com/");
+        assert_eq!(
+            prefixes.expand_curie_string(Some(G1), "a"),
+            Ok(String::from("http://example.com/a"))
+        );
+    }
+}
@@ -0,0 +1,179 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{PrefixChange, PrefixMapping};
+use std::collections::HashSet;
+
+impl PrefixMapping {
+    /// The changes that turn this mapping into `other`.
+    ///
+    /// A change of the default prefix comes first. It is followed by
+    /// the prefixes that are only in this mapping, as
+    /// [`PrefixChange::Removed`], or bound to a different namespace in
+    /// `other`, as [`PrefixChange::Rebound`], in the order of this
+    /// mapping, and then the prefixes that are only in `other`, as
+    /// [`PrefixChange::Added`], in its order. Only namespaces are
+    /// compared, not URI templates, terms or settings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::{PrefixChange, PrefixMapping};
+    ///
+    /// let mut old = PrefixMapping::default();
+    /// old.add_prefix("ex", "http://example.com/").unwrap();
+    /// old.add_prefix("dc", "http://purl.org/dc/elements/1.1/").unwrap();
+    ///
+    /// let mut new = old.clone();
+    /// new.add_prefix("dc", "http://purl.org/dc/terms/").unwrap();
+    /// new.add_prefix("foaf", "http://xmlns.com/foaf/0.1/").unwrap();
+    ///
+    /// assert_eq!(old.diff(&new), [
+    ///     PrefixChange::Rebound {
+    ///         prefix: String::from("dc"),
+    ///         previous: String::from("http://purl.org/dc/elements/1.1/"),
+    ///         namespace: String::from("http://purl.org/dc/terms/"),
+    ///     },
+    ///     PrefixChange::Added {
+    ///         prefix: String::from("foaf"),
+    ///         namespace: String::from("http://xmlns.com/foaf/0.1/"),
+    ///     },
+    /// ]);
+    /// assert!(new.diff(&new).is_empty());
+    /// ```
+    #[must_use]
+    pub fn diff(&self, other: &PrefixMapping) -> Vec<PrefixChange> {
+        let mut changes = Vec::new();
+        if self.default != other.default {
+            changes.push(PrefixChange::DefaultChanged {
+                previous: self.default.clone(),
+                default: other.default.clone(),
+            });
+        }
+        for (prefix, namespace) in &self.mapping {
+            match other.mapping.get(prefix) {
+                None => changes.push(PrefixChange::Removed {
+                    prefix: prefix.clone(),
+                    namespace: namespace.clone(),
+                }),
+                Some(other_namespace) if other_namespace != namespace => {
+                    changes.push(PrefixChange::Rebound {
+                        prefix: prefix.clone(),
+                        previous: namespace.clone(),
+                        namespace: other_namespace.clone(),
+                    });
+                }
+                Some(_) => {}
+            }
+        }
+        for (prefix, namespace) in &other.mapping {
+            if !self.mapping.contains_key(prefix) {
+                changes.push(PrefixChange::Added {
+                    prefix: prefix.clone(),
+                    namespace: namespace.clone(),
+                });
+            }
+        }
+        changes
+    }
+
+    /// Whether this mapping and `other` are interchangeable, regardless
+    /// of the names of their prefixes.
+    ///
+    /// They are if every prefix bound in both is bound to the same
+    /// namespace and they bind the same set of namespaces, counting the
+    /// default prefix. Two mappings that use different prefixes for the
+    /// same namespaces are equivalent, since they expand the same IRIs
+    /// even though they write them differently.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::PrefixMapping;
+    ///
+    /// let mut mapping = PrefixMapping::default();
+    /// mapping.add_prefix("foaf", "http://xmlns.com/foaf/0.1/").unwrap();
+    /// let mut other = PrefixMapping::default();
+    /// other.add_prefix("f", "http://xmlns.com/foaf/0.1/").unwrap();
+    /// assert!(mapping.is_equivalent(&other));
+    ///
+    /// other.add_prefix("foaf", "http://example.com/foaf/").unwrap();
+    /// assert!(!mapping.is_equivalent(&other));
+    /// ```
+    #[must_use]
+    pub fn is_equivalent(&self, other: &PrefixMapping) -> bool {
+        let shared_agree = self.mapping.iter().all(|(prefix, namespace)| {
+            other
+                .mapping
+                .get(prefix)
+                .is_none_or(|other_namespace| other_namespace == namespace)
+        });
+        shared_agree && self.namespaces() == other.namespaces()
+    }
+
+    /// The namespaces bound by the mapping, including the default.
+    fn namespaces(&self) -> HashSet<&str> {
+        self.default
+            .iter()
+            .chain(self.mapping.values())
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn removed_and_default() {
+        let mut old = PrefixMapping::default();
+        old.add_prefix("a", "http://example.com/a/").unwrap();
+        old.add_prefix("b", "http://example.com/b/").unwrap();
+        let mut new = PrefixMapping::default();
+        new.set_default("http://example.com/");
+        new.add_prefix("b", "http://example.com/b/").unwrap();
+
+        assert_eq!(
+            old.diff(&new),
+            [
+                PrefixChange::DefaultChanged {
+                    previous: None,
+                    default: Some(String::from("http://example.com/")),
+                },
+                PrefixChange::Removed {
+                    prefix: String::from("a"),
+                    namespace: String::from("http://example.com/a/"),
+                },
+            ]
+        );
+        assert!(!old.is_equivalent(&new));
+        assert_eq!(
+            new.diff(&old)[1],
+            PrefixChange::Added {
+                prefix: String::from("a"),
+                namespace: String::from("http://example.com/a/"),
+            }
+        );
+    }
+
+    #[test]
+    fn equivalence() {
+        let mut mapping = PrefixMapping::default();
+        mapping.set_default("http://example.com/");
+        mapping.add_prefix("a", "http://example.com/a/").unwrap();
+        let mut other = PrefixMapping::default();
+        other.add_prefix("ex", "http://example.com/").unwrap();
+        other.add_prefix("x", "http://example.com/a/").unwrap();
+        other.add_prefix("y", "http://example.com/a/").unwrap();
+        assert!(mapping.is_equivalent(&other));
+        assert!(other.is_equivalent(&mapping));
+
+        other.add_prefix("b", "http://example.com/b/").unwrap();
+        assert!(!mapping.is_equivalent(&other));
+        assert!(!other.is_equivalent(&mapping));
+    }
+}
@@ -0,0 +1,125 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{Curie, ExpansionError, PrefixMapping};
+use std::fmt;
+
+/// The result of expanding a CURIE that may be a blank node.
+///
+/// In RDF syntaxes, a CURIE with the reserved prefix `_` is a blank
+/// node label rather than an abbreviated IRI. This is created by
+/// [`PrefixMapping::expand_term()`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ExpandedTerm {
+    /// The CURIE expanded to an IRI.
+    Iri(String),
+    /// The CURIE is a blank node, with this label.
+    BlankNode(String),
+}
+
+impl ExpandedTerm {
+    /// Is this a blank node?
+    #[must_use]
+    pub fn is_blank_node(&self) -> bool {
+        matches!(self, ExpandedTerm::BlankNode(_))
+    }
+
+    /// The IRI, or `None` for a blank node.
+    #[must_use]
+    pub fn into_iri(self) -> Option<String> {
+        match self {
+            ExpandedTerm::Iri(iri) => Some(iri),
+            ExpandedTerm::BlankNode(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for ExpandedTerm {
+    /// Formats an IRI as it is and a blank node as `_:label`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExpandedTerm::Iri(iri) => f.write_str(iri),
+            ExpandedTerm::BlankNode(label) => write!(f, "_:{label}"),
+        }
+    }
+}
+
+impl PrefixMapping {
+    /// Expand a CURIE, treating one with the prefix `_` as a blank node
+    /// label rather than an error.
+    ///
+    /// # Errors
+    ///
+    /// This will return [`ExpansionError`] if the CURIE isn't a blank
+    /// node and its expansion fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::{ExpandedTerm, PrefixMapping};
+    ///
+    /// let mut mapping = PrefixMapping::default();
+    /// mapping.add_prefix("foaf", "http://xmlns.com/foaf/0.1/").unwrap();
+    ///
+    /// assert_eq!(mapping.expand_term_string("_:b1"),
+    ///            Ok(ExpandedTerm::BlankNode(String::from("b1"))));
+    /// assert_eq!(mapping.expand_term_string("foaf:Agent"),
+    ///            Ok(ExpandedTerm::Iri(String::from("http://xmlns.com/foaf/0.1/Agent"))));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`PrefixMapping::expand_term_string()`]
+    pub fn expand_term(&self, curie: &Curie) -> Result<ExpandedTerm, ExpansionError> {
+        match curie.prefix() {
+            Some("_") => Ok(ExpandedTerm::BlankNode(String::from(curie.reference()))),
+            _ => self.expand_curie(curie).map(ExpandedTerm::Iri),
+        }
+    }
+
+    /// Expand a CURIE string, treating one with the prefix `_` as a
+    /// blank node label.
+    ///
+    /// # Errors
+    ///
+    /// This will return [`ExpansionError`] if the CURIE isn't a blank
+    /// node and its expansion fails.
+    ///
+    /// # See also
+    ///
+    /// * [`PrefixMapping::expand_term()`]
+    pub fn expand_term_string(&self, curie_str: &str) -> Result<ExpandedTerm, ExpansionError> {
+        self.expand_term(&Curie::split(curie_str))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blank_nodes() {
+        let mut mapping = PrefixMapping::default();
+        mapping.set_default("http://example.com/");
+
+        let blank = mapping.expand_term_string("_:node").unwrap();
+        assert!(blank.is_blank_node());
+        assert_eq!(blank.to_string(), "_:node");
+        assert_eq!(blank.into_iri(), None);
+
+        let iri = mapping.expand_term_string("thing").unwrap();
+        assert_eq!(iri.to_string(), "http://example.com/thing");
+        assert_eq!(
+            iri.into_iri(),
+            Some(String::from("http://example.com/thing"))
+        );
+
+        assert_eq!(
+            mapping.expand_term_string("ex:thing"),
+            Err(ExpansionError::UnknownPrefix(String::from("ex")))
+        );
+    }
+}
@@ -0,0 +1,129 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{syntax, Curie, ExpansionError, PrefixMapping, ShrinkError};
+
+impl PrefixMapping {
+    /// Expand a parsed [`Curie`] with some additional prefixes that
+    /// only apply to this call.
+    ///
+    /// This is useful when a document declares its own prefixes and is
+    /// processed against a shared mapping, which doesn't need to be
+    /// copied or changed. The prefixes in `extra` take precedence over
+    /// those in the mapping, and when a prefix appears more than once
+    /// in `extra`, the last binding is used.
+    ///
+    /// # Errors
+    ///
+    /// This will return [`ExpansionError`] if the expansion fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::{Curie, PrefixMapping};
+    ///
+    /// let mut mapping = PrefixMapping::default();
+    /// mapping.add_prefix("foaf", "http://xmlns.com/foaf/0.1/").unwrap();
+    ///
+    /// let extra = [("ex", "http://example.com/")];
+    /// assert_eq!(mapping.expand_with_extra(&Curie::new(Some("ex"), "a"), &extra),
+    ///            Ok(String::from("http://example.com/a")));
+    /// assert_eq!(mapping.expand_with_extra(&Curie::new(Some("foaf"), "name"), &extra),
+    ///            Ok(String::from("http://xmlns.com/foaf/0.1/name")));
+    ///
+    /// // The mapping is unchanged.
+    /// assert!(mapping.expand_curie_string("ex:a").is_err());
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`PrefixMapping::expand_curie()`]
+    /// * [`PrefixMapping::shrink_iri_with_extra()`]
+    pub fn expand_with_extra(
+        &self,
+        curie: &Curie,
+        extra: &[(&str, &str)],
+    ) -> Result<String, ExpansionError> {
+        let namespace = curie.prefix.and_then(|prefix| {
+            extra
+                .iter()
+                .rev()
+                .find(|&&(extra_prefix, _)| extra_prefix == prefix)
+                .map(|&(_, namespace)| namespace)
+        });
+        match namespace {
+            Some(namespace) => {
+                self.check_reference(namespace, curie.reference)?;
+                Ok(String::from(namespace) + curie.reference)
+            }
+            None => self.expand_curie(curie),
+        }
+    }
+
+    /// Shrink an IRI with some additional prefixes that only apply to
+    /// this call.
+    ///
+    /// The prefixes in `extra` are tried first, in order, and then the
+    /// mapping is used as with [`PrefixMapping::shrink_iri()`].
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if neither `extra` nor the mapping would
+    /// allow the IRI to be shortened.
+    ///
+    /// # See also
+    ///
+    /// * [`PrefixMapping::expand_with_extra()`]
+    pub fn shrink_iri_with_extra<'a>(
+        &'a self,
+        iri: &'a str,
+        extra: &[(&'a str, &'a str)],
+    ) -> Result<Curie<'a>, ShrinkError> {
+        extra
+            .iter()
+            .find_map(|&(prefix, namespace)| {
+                syntax::strip_namespace(iri, namespace)
+                    .map(|reference| Curie::new(Some(prefix), reference))
+            })
+            .map_or_else(|| self.shrink_iri(iri), Ok)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extra_prefixes() {
+        let mut mapping = PrefixMapping::default();
+        mapping.add_prefix("ex", "http://example.com/").unwrap();
+        let extra = [
+            ("ex", "http://example.org/"),
+            ("ex", "http://example.net/"),
+            ("doc", "http://example.com/doc/"),
+        ];
+
+        assert_eq!(
+            mapping.expand_with_extra(&Curie::new(Some("ex"), "a"), &extra),
+            Ok(String::from("http://example.net/a"))
+        );
+        assert_eq!(
+            mapping.expand_with_extra(&Curie::new(None, "a"), &extra),
+            Err(ExpansionError::MissingDefault)
+        );
+        assert_eq!(
+            mapping.shrink_iri_with_extra("http://example.com/doc/a", &extra),
+            Ok(Curie::new(Some("doc"), "a"))
+        );
+        assert_eq!(
+            mapping.shrink_iri_with_extra("http://example.com/a", &extra),
+            Ok(Curie::new(Some("ex"), "a"))
+        );
+        assert!(mapping
+            .shrink_iri_with_extra("http://example.edu/a", &extra)
+            .is_err());
+    }
+}
@@ -0,0 +1,436 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::PrefixMapping;
+use std::fmt;
+
+/// The syntax of the declarations written by a [`PrefixFormatter`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DeclarationStyle {
+    /// Turtle and TriG: `@prefix foaf: <http://xmlns.com/foaf/0.1/> .`
+    Turtle,
+    /// SPARQL, also accepted by Turtle 1.1:
+    /// `PREFIX foaf: <http://xmlns.com/foaf/0.1/>`
+    Sparql,
+    /// The value of an RDFa `prefix` attribute:
+    /// `foaf: http://xmlns.com/foaf/0.1/`
+    Rdfa,
+}
+
+/// The order in which a [`PrefixFormatter`] writes declarations.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum PrefixOrder {
+    /// The order in which the prefixes were added to the mapping.
+    #[default]
+    Insertion,
+    /// Sorted by prefix.
+    Alphabetical,
+    /// Sorted by namespace, then by prefix.
+    Namespace,
+}
+
+/// Writes the prefixes of a [`PrefixMapping`] as declarations, laid out
+/// for people to read.
+///
+/// By default, declarations are written in insertion order, one per
+/// line, or all on one line for [`DeclarationStyle::Rdfa`]. The default
+/// prefix of the mapping is not written.
+///
+/// # Examples
+///
+/// ```
+/// use curie::{DeclarationStyle, PrefixFormatter, PrefixMapping, PrefixOrder};
+///
+/// let mut mapping = PrefixMapping::default();
+/// mapping.add_prefix("rdfs", "http://www.w3.org/2000/01/rdf-schema#").unwrap();
+/// mapping.add_prefix("foaf", "http://xmlns.com/foaf/0.1/").unwrap();
+/// mapping.add_prefix("rdf", "http://www.w3.org/1999/02/22-rdf-syntax-ns#").unwrap();
+///
+/// let formatter = PrefixFormatter::new(DeclarationStyle::Turtle)
+///     .order(PrefixOrder::Alphabetical)
+///     .align(true)
+///     .group_by_domain(true);
+/// assert_eq!(formatter.format(&mapping), "\
+/// @prefix foaf: <http://xmlns.com/foaf/0.1/> .
+///
+/// @prefix rdf:  <http://www.w3.org/1999/02/22-rdf-syntax-ns#> .
+/// @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+/// ");
+/// ```
+#[derive(Clone, Debug)]
+pub struct PrefixFormatter {
+    style: DeclarationStyle,
+    order: PrefixOrder,
+    align: bool,
+    group_by_domain: bool,
+    max_width: Option<usize>,
+}
+
+impl PrefixFormatter {
+    /// Construct a formatter for a syntax.
+    #[must_use]
+    pub fn new(style: DeclarationStyle) -> Self {
+        PrefixFormatter {
+            style,
+            order: PrefixOrder::default(),
+            align: false,
+            group_by_domain: false,
+            max_width: None,
+        }
+    }
+
+    /// Set the order of the declarations.
+    #[must_use]
+    pub fn order(mut self, order: PrefixOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Align the namespaces in a column.
+    ///
+    /// With [`DeclarationStyle::Rdfa`], this also puts each declaration
+    /// on its own line.
+    #[must_use]
+    pub fn align(mut self, align: bool) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Group the declarations by the domain of their namespace, keeping
+    /// the groups apart.
+    ///
+    /// Groups are separated by an empty line, or start a new line with
+    /// [`DeclarationStyle::Rdfa`]. The groups are ordered by their
+    /// first declaration.
+    #[must_use]
+    pub fn group_by_domain(mut self, group: bool) -> Self {
+        self.group_by_domain = group;
+        self
+    }
+
+    /// Limit the width of lines, in characters.
+    ///
+    /// Declarations are never split, so a single declaration may still
+    /// be wider. Otherwise, alignment padding is dropped from lines that
+    /// would be too wide, and with [`DeclarationStyle::Rdfa`],
+    /// declarations are wrapped onto further lines.
+    #[must_use]
+    pub fn max_width(mut self, width: usize) -> Self {
+        self.max_width = Some(width);
+        self
+    }
+
+    /// Format the prefixes of `mapping`.
+    #[must_use]
+    pub fn format(&self, mapping: &PrefixMapping) -> String {
+        let mut out = String::new();
+        // Writing to a `String` can't fail.
+        let _ = self.write(mapping, &mut out);
+        out
+    }
+
+    /// Write the prefixes of `mapping`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to `out` fails.
+    pub fn write<W: fmt::Write + ?Sized>(
+        &self,
+        mapping: &PrefixMapping,
+        out: &mut W,
+    ) -> fmt::Result {
+        let mut entries: Vec<(&str, &str)> = mapping.mappings().collect();
+        match self.order {
+            PrefixOrder::Insertion => {}
+            PrefixOrder::Alphabetical => entries.sort_by(|a, b| a.0.cmp(b.0)),
+            PrefixOrder::Namespace => entries.sort_by(|a, b| (a.1, a.0).cmp(&(b.1, b.0))),
+        }
+        let label_width = if self.align {
+            entries
+                .iter()
+                .map(|(prefix, _)| prefix.chars().count() + 1)
+                .max()
+                .unwrap_or(0)
+        } else {
+            0
+        };
+        let groups = if self.group_by_domain {
+            let mut groups = indexmap::IndexMap::<&str, Vec<_>>::new();
+            for entry in entries {
+                groups.entry(domain(entry.1)).or_default().push(entry);
+            }
+            groups.into_values().collect()
+        } else {
+            vec![entries]
+        };
+
+        match self.style {
+            DeclarationStyle::Turtle | DeclarationStyle::Sparql => {
+                for (index, group) in groups.iter().enumerate() {
+                    if index > 0 {
+                        out.write_char('\n')?;
+                    }
+                    for &(prefix, namespace) in group {
+                        out.write_str(&self.declaration(prefix, namespace, label_width))?;
+                        out.write_char('\n')?;
+                    }
+                }
+            }
+            DeclarationStyle::Rdfa => {
+                let mut line_width = 0;
+                for (index, group) in groups.iter().enumerate() {
+                    for (position, &(prefix, namespace)) in group.iter().enumerate() {
+                        let declaration = self.declaration(prefix, namespace, label_width);
+                        let width = declaration.chars().count();
+                        if index > 0 || position > 0 {
+                            let wrap = self.align
+                                || position == 0
+                                || self
+                                    .max_width
+                                    .is_some_and(|max| line_width + 1 + width > max);
+                            if wrap {
+                                out.write_char('\n')?;
+                                line_width = 0;
+                            } else {
+                                out.write_char(' ')?;
+                                line_width += 1;
+                            }
+                        }
+                        out.write_str(&declaration)?;
+                        line_width += width;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// A single declaration, with its prefix padded to `label_width`
+    /// when that keeps it within the maximum width.
+    fn declaration(&self, prefix: &str, namespace: &str, label_width: usize) -> String {
+        let (before, open, close) = match self.style {
+            DeclarationStyle::Turtle => ("@prefix ", "<", "> ."),
+            DeclarationStyle::Sparql => ("PREFIX ", "<", ">"),
+            DeclarationStyle::Rdfa => ("", "", ""),
+        };
+        let label = prefix.chars().count() + 1;
+        let unpadded =
+            before.len() + label + 1 + open.len() + namespace.chars().count() + close.len();
+        let mut padding = label_width.saturating_sub(label);
+        if self.max_width.is_some_and(|max| unpadded + padding > max) {
+            padding = 0;
+        }
+        format!("{before}{prefix}:{:padding$} {open}{namespace}{close}", "")
+    }
+}
+
+impl PrefixMapping {
+    /// Write the base IRI and the prefixes of this mapping as a Turtle
+    /// prologue.
+    ///
+    /// An `@base` directive comes first if a
+    /// [base IRI](PrefixMapping::set_base()) is set, followed by an
+    /// `@prefix` directive for each prefix, sorted by prefix. The
+    /// default prefix is not written. For more control over the
+    /// layout, use a [`PrefixFormatter`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to `out` fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::PrefixMapping;
+    ///
+    /// let mut mapping = PrefixMapping::default();
+    /// mapping.set_base("http://example.com/");
+    /// mapping.add_prefix("rdfs", "http://www.w3.org/2000/01/rdf-schema#").unwrap();
+    /// mapping.add_prefix("foaf", "http://xmlns.com/foaf/0.1/").unwrap();
+    ///
+    /// let mut out = String::new();
+    /// mapping.write_turtle_prefixes(&mut out).unwrap();
+    /// assert_eq!(out, "\
+    /// @base <http://example.com/> .
+    /// @prefix foaf: <http://xmlns.com/foaf/0.1/> .
+    /// @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+    /// ");
+    /// ```
+    pub fn write_turtle_prefixes<W: fmt::Write + ?Sized>(&self, out: &mut W) -> fmt::Result {
+        if let Some(base) = self.base() {
+            writeln!(out, "@base <{base}> .")?;
+        }
+        PrefixFormatter::new(DeclarationStyle::Turtle)
+            .order(PrefixOrder::Alphabetical)
+            .write(self, out)
+    }
+
+    /// Write the base IRI and the prefixes of this mapping as the
+    /// prologue of a SPARQL query.
+    ///
+    /// A `BASE` declaration comes first if a
+    /// [base IRI](PrefixMapping::set_base()) is set, followed by a
+    /// `PREFIX` declaration for each prefix, sorted by prefix. As each
+    /// prefix is bound once, each is declared once. The default prefix
+    /// is not written, as SPARQL has no equivalent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::PrefixMapping;
+    ///
+    /// let mut mapping = PrefixMapping::default();
+    /// mapping.add_prefix("rdfs", "http://www.w3.org/2000/01/rdf-schema#").unwrap();
+    /// mapping.add_prefix("foaf", "http://xmlns.com/foaf/0.1/").unwrap();
+    ///
+    /// let query = mapping.to_sparql_prologue() + "SELECT ?name WHERE { ?p foaf:name ?name }";
+    /// assert_eq!(query, "\
+    /// PREFIX foaf: <http://xmlns.com/foaf/0.1/>
+    /// PREFIX rdfs: <http://www.w3.org/2000/01/rdf-schema#>
+    /// SELECT ?name WHERE { ?p foaf:name ?name }");
+    /// ```
+    #[must_use]
+    pub fn to_sparql_prologue(&self) -> String {
+        let mut out = String::new();
+        if let Some(base) = self.base() {
+            out.push_str("BASE <");
+            out.push_str(base);
+            out.push_str(">\n");
+        }
+        // Writing to a `String` can't fail.
+        let _ = PrefixFormatter::new(DeclarationStyle::Sparql)
+            .order(PrefixOrder::Alphabetical)
+            .write(self, &mut out);
+        out
+    }
+}
+
+/// The domain of a namespace, used for grouping.
+///
+/// This is the host of a hierarchical IRI, or the scheme otherwise.
+fn domain(namespace: &str) -> &str {
+    let Some((scheme, rest)) = namespace.split_once(':') else {
+        return "";
+    };
+    match rest.strip_prefix("//") {
+        Some(authority) => {
+            let end = authority.find(['/', '?', '#']).unwrap_or(authority.len());
+            let authority = &authority[..end];
+            let host = authority
+                .rsplit_once('@')
+                .map_or(authority, |(_, host)| host);
+            host.split_once(':').map_or(host, |(host, _)| host)
+        }
+        None => scheme,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapping() -> PrefixMapping {
+        let mut mapping = PrefixMapping::default();
+        mapping.add_prefix("ex", "http://example.com/").unwrap();
+        mapping
+            .add_prefix("dc", "http://purl.org/dc/terms/")
+            .unwrap();
+        mapping
+            .add_prefix("exv", "http://example.com:8080/vocab#")
+            .unwrap();
+        mapping.add_prefix("isbn", "urn:isbn:").unwrap();
+        mapping
+    }
+
+    #[test]
+    fn sparql_by_namespace() {
+        let formatter =
+            PrefixFormatter::new(DeclarationStyle::Sparql).order(PrefixOrder::Namespace);
+        assert_eq!(
+            formatter.format(&mapping()),
+            "PREFIX ex: <http://example.com/>\n\
+             PREFIX exv: <http://example.com:8080/vocab#>\n\
+             PREFIX dc: <http://purl.org/dc/terms/>\n\
+             PREFIX isbn: <urn:isbn:>\n"
+        );
+    }
+
+    #[test]
+    fn turtle_prologue() {
+        let mut out = String::new();
+        PrefixMapping::default()
+            .write_turtle_prefixes(&mut out)
+            .unwrap();
+        assert_eq!(out, "");
+
+        mapping().write_turtle_prefixes(&mut out).unwrap();
+        assert_eq!(
+            out,
+            "@prefix dc: <http://purl.org/dc/terms/> .\n\
+             @prefix ex: <http://example.com/> .\n\
+             @prefix exv: <http://example.com:8080/vocab#> .\n\
+             @prefix isbn: <urn:isbn:> .\n"
+        );
+    }
+
+    #[test]
+    fn sparql_prologue() {
+        assert_eq!(PrefixMapping::default().to_sparql_prologue(), "");
+
+        let mut mapping = mapping();
+        mapping.set_base("http://example.com/base/");
+        assert_eq!(
+            PrefixMapping::from_sparql(&mapping.to_sparql_prologue()).unwrap(),
+            mapping
+        );
+        assert!(mapping
+            .to_sparql_prologue()
+            .starts_with("BASE <http://example.com/base/>\nPREFIX dc: "));
+    }
+
+    #[test]
+    fn align_within_width() {
+        let formatter = PrefixFormatter::new(DeclarationStyle::Turtle)
+            .align(true)
+            .max_width(40);
+        assert_eq!(
+            formatter.format(&mapping()),
+            "@prefix ex:   <http://example.com/> .\n\
+             @prefix dc: <http://purl.org/dc/terms/> .\n\
+             @prefix exv: <http://example.com:8080/vocab#> .\n\
+             @prefix isbn: <urn:isbn:> .\n"
+        );
+    }
+
+    #[test]
+    fn rdfa_wrapping() {
+        let formatter = PrefixFormatter::new(DeclarationStyle::Rdfa);
+        assert_eq!(
+            formatter.format(&mapping()),
+            "ex: http://example.com/ dc: http://purl.org/dc/terms/ \
+             exv: http://example.com:8080/vocab# isbn: urn:isbn:"
+        );
+        assert_eq!(
+            formatter.clone().max_width(60).format(&mapping()),
+            "ex: http://example.com/ dc: http://purl.org/dc/terms/\n\
+             exv: http://example.com:8080/vocab# isbn: urn:isbn:"
+        );
+        assert_eq!(
+            formatter.group_by_domain(true).format(&mapping()),
+            "ex: http://example.com/ exv: http://example.com:8080/vocab#\n\
+             dc: http://purl.org/dc/terms/\n\
+             isbn: urn:isbn:"
+        );
+    }
+
+    #[test]
+    fn domains() {
+        assert_eq!(domain("http://user@example.com:80/a"), "example.com");
+        assert_eq!(domain("https://example.com#"), "example.com");
+        assert_eq!(domain("urn:isbn:"), "urn");
+        assert_eq!(domain("relative/"), "");
+    }
+}
@@ -0,0 +1,152 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{telemetry, Curie, ExpansionError, PrefixMapping};
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use std::sync::Arc;
+
+/// A pool of expanded IRIs that can be shared by many threads.
+///
+/// Each distinct IRI is stored once, and every expansion of it returns
+/// a handle to the same `Arc<str>`. This lets loaders running on
+/// several threads deduplicate identical IRIs across all of them rather
+/// than per thread. The pool is sharded, so threads interning different
+/// IRIs rarely contend.
+///
+/// IRIs stay in the pool until it is cleared or dropped.
+///
+/// This requires the `dashmap` feature.
+///
+/// # Examples
+///
+/// ```
+/// use curie::{PrefixMapping, SharedInterner};
+/// use std::sync::Arc;
+///
+/// let mut mapping = PrefixMapping::default();
+/// mapping.add_prefix("foaf", "http://xmlns.com/foaf/0.1/").unwrap();
+///
+/// let interner = SharedInterner::new();
+/// let (a, b) = std::thread::scope(|scope| {
+///     let a = scope.spawn(|| interner.expand_curie_string(&mapping, "foaf:Agent"));
+///     let b = scope.spawn(|| interner.expand_curie_string(&mapping, "foaf:Agent"));
+///     (a.join().unwrap().unwrap(), b.join().unwrap().unwrap())
+/// });
+///
+/// assert_eq!(&*a, "http://xmlns.com/foaf/0.1/Agent");
+/// assert!(Arc::ptr_eq(&a, &b));
+/// assert_eq!(interner.len(), 1);
+/// ```
+#[derive(Debug, Default)]
+pub struct SharedInterner {
+    iris: DashMap<Arc<str>, ()>,
+}
+
+impl SharedInterner {
+    /// Construct an empty pool.
+    #[must_use]
+    pub fn new() -> Self {
+        SharedInterner::default()
+    }
+
+    /// Return the pooled copy of `iri`, adding it if it isn't pooled yet.
+    pub fn intern(&self, iri: &str) -> Arc<str> {
+        if let Some(entry) = self.iris.get(iri) {
+            telemetry::record_interner_lookup(true);
+            return Arc::clone(entry.key());
+        }
+        // Another thread may have added it since the lookup.
+        match self.iris.entry(Arc::from(iri)) {
+            Entry::Occupied(entry) => {
+                telemetry::record_interner_lookup(true);
+                Arc::clone(entry.key())
+            }
+            Entry::Vacant(entry) => {
+                telemetry::record_interner_lookup(false);
+                let iri = Arc::clone(entry.key());
+                entry.insert(());
+                iri
+            }
+        }
+    }
+
+    /// Expand a CURIE, returning the pooled copy of the complete IRI.
+    ///
+    /// # Errors
+    ///
+    /// This will return [`ExpansionError`] if the expansion fails.
+    pub fn expand_curie_string(
+        &self,
+        mapping: &PrefixMapping,
+        curie_str: &str,
+    ) -> Result<Arc<str>, ExpansionError> {
+        self.expand_curie(mapping, &Curie::split(curie_str))
+    }
+
+    /// Expand a parsed [`Curie`], returning the pooled copy of the
+    /// complete IRI.
+    ///
+    /// When the IRI is already pooled, this doesn't allocate.
+    ///
+    /// # Errors
+    ///
+    /// This will return [`ExpansionError`] if the expansion fails.
+    pub fn expand_curie(
+        &self,
+        mapping: &PrefixMapping,
+        curie: &Curie,
+    ) -> Result<Arc<str>, ExpansionError> {
+        mapping.expand_curie_reuse(curie, |iri| self.intern(iri))
+    }
+
+    /// The number of distinct IRIs in the pool.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.iris.len()
+    }
+
+    /// Is the pool empty?
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.iris.is_empty()
+    }
+
+    /// Remove every IRI from the pool.
+    ///
+    /// Handles that were already returned remain valid, but are no
+    /// longer shared with later expansions.
+    pub fn clear(&self) {
+        self.iris.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interning() {
+        let mut mapping = PrefixMapping::default();
+        mapping.add_prefix("ex", "http://example.com/").unwrap();
+        let interner = SharedInterner::new();
+        assert!(interner.is_empty());
+
+        let a = interner.intern("http://example.com/a");
+        let b = interner
+            .expand_curie(&mapping, &Curie::new(Some("ex"), "a"))
+            .unwrap();
+        assert!(Arc::ptr_eq(&a, &b));
+        assert_eq!(
+            interner.expand_curie_string(&mapping, "missing:a"),
+            Err(ExpansionError::UnknownPrefix(String::from("missing")))
+        );
+        assert_eq!(interner.len(), 1);
+
+        interner.clear();
+        assert!(!Arc::ptr_eq(&a, &interner.intern("http://example.com/a")));
+    }
+}
@@ -0,0 +1,176 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{Curie, ExpansionError, PrefixMapping};
+use std::borrow::Borrow;
+use std::fmt;
+use std::ops::Deref;
+
+/// An owned IRI, such as the result of expanding a CURIE.
+///
+/// This is a thin wrapper around a `String` that makes clear that the
+/// string is an IRI. It can be used as the key of a `HashMap` or
+/// `BTreeMap` and looked up with a `&str`.
+///
+/// # Examples
+///
+/// ```
+/// use curie::{Curie, IriBuf, PrefixMapping};
+/// use std::collections::HashSet;
+///
+/// let mut mapping = PrefixMapping::default();
+/// mapping.add_prefix("foaf", "http://xmlns.com/foaf/0.1/").unwrap();
+///
+/// let iri: IriBuf = mapping.expand_curie_iri(&Curie::new(Some("foaf"), "Agent")).unwrap();
+/// assert_eq!(iri, "http://xmlns.com/foaf/0.1/Agent");
+///
+/// let mut seen = HashSet::new();
+/// seen.insert(iri);
+/// assert!(seen.contains("http://xmlns.com/foaf/0.1/Agent"));
+/// ```
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct IriBuf(String);
+
+impl IriBuf {
+    /// Borrow the IRI as a string slice.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Convert into the underlying `String`.
+    #[must_use]
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl From<String> for IriBuf {
+    fn from(iri: String) -> IriBuf {
+        IriBuf(iri)
+    }
+}
+
+impl From<&str> for IriBuf {
+    fn from(iri: &str) -> IriBuf {
+        IriBuf(String::from(iri))
+    }
+}
+
+impl From<IriBuf> for String {
+    fn from(iri: IriBuf) -> String {
+        iri.0
+    }
+}
+
+impl Deref for IriBuf {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for IriBuf {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for IriBuf {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for IriBuf {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl PartialEq<str> for IriBuf {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for IriBuf {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<String> for IriBuf {
+    fn eq(&self, other: &String) -> bool {
+        &self.0 == other
+    }
+}
+
+impl PartialEq<IriBuf> for str {
+    fn eq(&self, other: &IriBuf) -> bool {
+        self == other.0
+    }
+}
+
+impl PartialEq<IriBuf> for &str {
+    fn eq(&self, other: &IriBuf) -> bool {
+        *self == other.0
+    }
+}
+
+impl PartialEq<IriBuf> for String {
+    fn eq(&self, other: &IriBuf) -> bool {
+        *self == other.0
+    }
+}
+
+impl PrefixMapping {
+    /// Expand a parsed [`Curie`], returning a complete IRI as an [`IriBuf`].
+    ///
+    /// # Errors
+    ///
+    /// This will return [`ExpansionError`] if the expansion fails.
+    ///
+    /// # See also
+    ///
+    /// * [`PrefixMapping::expand_curie()`]
+    pub fn expand_curie_iri(&self, curie: &Curie) -> Result<IriBuf, ExpansionError> {
+        self.expand_curie(curie).map(IriBuf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn iri_comparisons() {
+        let iri = IriBuf::from("http://example.com/");
+        assert_eq!(iri, "http://example.com/");
+        assert_eq!("http://example.com/", iri);
+        assert_eq!(iri, String::from("http://example.com/"));
+        assert_eq!(iri.len(), 19);
+        assert_eq!(iri.to_string(), "http://example.com/");
+        assert_eq!(iri.into_string(), "http://example.com/");
+    }
+
+    #[test]
+    fn iri_map_keys() {
+        let mut mapping = PrefixMapping::default();
+        mapping.set_default("http://example.com/");
+
+        let mut labels = BTreeMap::new();
+        let iri = mapping.expand_curie_iri(&Curie::new(None, "a")).unwrap();
+        labels.insert(iri, "A");
+        assert_eq!(labels.get("http://example.com/a"), Some(&"A"));
+        assert_eq!(
+            mapping.expand_curie_iri(&Curie::new(Some("b"), "c")),
+            Err(ExpansionError::UnknownPrefix(String::from("b")))
+        );
+    }
+}
@@ -0,0 +1,275 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{InvalidPrefixError, PrefixMapping};
+use std::collections::HashMap;
+use std::iter::FusedIterator;
+
+/// An iterator over the prefixes of a [`PrefixMapping`] and their
+/// namespaces, or over its terms and their IRIs.
+///
+/// This is created by [`PrefixMapping::mappings()`] and
+/// [`PrefixMapping::terms()`], and by iterating over a
+/// `&PrefixMapping`.
+#[derive(Clone, Debug)]
+pub struct Mappings<'a> {
+    inner: indexmap::map::Iter<'a, String, String>,
+}
+
+impl<'a> Mappings<'a> {
+    pub(crate) fn new(map: &'a indexmap::IndexMap<String, String>) -> Self {
+        Mappings { inner: map.iter() }
+    }
+}
+
+impl<'a> Iterator for Mappings<'a> {
+    type Item = (&'a str, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Mappings<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next_back()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+}
+
+impl ExactSizeIterator for Mappings<'_> {}
+
+impl FusedIterator for Mappings<'_> {}
+
+/// Iterating over a mapping yields its prefixes and their namespaces,
+/// as with [`PrefixMapping::mappings()`].
+impl<'a> IntoIterator for &'a PrefixMapping {
+    type Item = (&'a str, &'a str);
+    type IntoIter = Mappings<'a>;
+
+    fn into_iter(self) -> Mappings<'a> {
+        self.mappings()
+    }
+}
+
+impl PrefixMapping {
+    /// Construct an empty mapping with room for at least `capacity`
+    /// prefixes.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut mapping = PrefixMapping::default();
+        mapping.mapping.reserve(capacity);
+        mapping.prefix_ids.reserve(capacity);
+        mapping
+    }
+
+    /// Add many prefixes at once, or none of them.
+    ///
+    /// Every prefix and namespace is checked as by
+    /// [`PrefixMapping::add_prefix()`] before any is added, and room is
+    /// made for all of them at once, which is faster than adding a
+    /// large number of prefixes one at a time. When a prefix appears
+    /// more than once, the last namespace is used.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from [`PrefixMapping::add_prefix()`] for the
+    /// first prefix that can't be added, in which case the mapping is
+    /// left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::{InvalidPrefixError, PrefixMapping};
+    ///
+    /// let registry = (0..1000).map(|n| (format!("p{n}"), format!("http://example.com/{n}/")));
+    /// let mut mapping = PrefixMapping::with_capacity(1000);
+    /// mapping.add_prefixes(registry).unwrap();
+    /// assert_eq!(mapping.len(), 1000);
+    ///
+    /// assert_eq!(mapping.add_prefixes([("ex", "http://example.com/"), ("_", "http://example.com/")]),
+    ///            Err(InvalidPrefixError::ReservedPrefix));
+    /// assert!(!mapping.contains_prefix("ex"));
+    /// ```
+    pub fn add_prefixes<P, N, I>(&mut self, iter: I) -> Result<(), InvalidPrefixError>
+    where
+        P: AsRef<str>,
+        N: AsRef<str>,
+        I: IntoIterator<Item = (P, N)>,
+    {
+        let prefixes: Vec<(P, N)> = iter.into_iter().collect();
+        let mut pending: HashMap<&str, &str> = HashMap::with_capacity(prefixes.len());
+        for (prefix, namespace) in &prefixes {
+            let (prefix, namespace) = (prefix.as_ref(), namespace.as_ref());
+            let bound = pending
+                .get(prefix)
+                .copied()
+                .or_else(|| self.get_expansion(prefix));
+            self.check_rebinding(prefix, bound, namespace)?;
+            self.check_prefix(prefix)?;
+            self.check_namespace(namespace)?;
+            pending.insert(prefix, namespace);
+        }
+
+        self.mapping.reserve(pending.len());
+        self.prefix_ids.reserve(pending.len());
+        for (prefix, namespace) in &prefixes {
+            self.force_add_prefix(prefix.as_ref(), namespace.as_ref())
+                .expect("prefixes are checked before they are added");
+        }
+        Ok(())
+    }
+
+    /// Add each prefix and namespace from `iter`, stopping at the first
+    /// that can't be added.
+    ///
+    /// Unlike [`Extend::extend()`], which skips the prefixes that can't
+    /// be added, this reports them. The prefixes before the one that
+    /// failed remain added.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from [`PrefixMapping::add_prefix()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::{InvalidPrefixError, PrefixMapping};
+    ///
+    /// let mut mapping: PrefixMapping = [
+    ///     ("foaf", "http://xmlns.com/foaf/0.1/"),
+    ///     ("_", "http://example.com/"),
+    /// ].into_iter().collect();
+    /// assert_eq!(mapping.mappings().count(), 1);
+    ///
+    /// assert_eq!(mapping.try_extend([("ex", "http://example.com/"), ("_", "http://example.com/")]),
+    ///            Err(InvalidPrefixError::ReservedPrefix));
+    /// assert_eq!(mapping.mappings().count(), 2);
+    /// ```
+    pub fn try_extend<P, N, I>(&mut self, iter: I) -> Result<(), InvalidPrefixError>
+    where
+        P: AsRef<str>,
+        N: AsRef<str>,
+        I: IntoIterator<Item = (P, N)>,
+    {
+        iter.into_iter().try_for_each(|(prefix, namespace)| {
+            self.add_prefix(prefix.as_ref(), namespace.as_ref())
+        })
+    }
+}
+
+/// Prefixes that can't be added, such as the reserved prefix `_`, are
+/// skipped.
+impl<P: AsRef<str>, N: AsRef<str>> FromIterator<(P, N)> for PrefixMapping {
+    fn from_iter<I: IntoIterator<Item = (P, N)>>(iter: I) -> Self {
+        let mut mapping = PrefixMapping::default();
+        mapping.extend(iter);
+        mapping
+    }
+}
+
+/// Prefixes that can't be added, such as the reserved prefix `_` or
+/// protected prefixes, are skipped. Use [`PrefixMapping::try_extend()`]
+/// to have them reported.
+impl<P: AsRef<str>, N: AsRef<str>> Extend<(P, N)> for PrefixMapping {
+    fn extend<I: IntoIterator<Item = (P, N)>>(&mut self, iter: I) {
+        for (prefix, namespace) in iter {
+            let _ = self.add_prefix(prefix.as_ref(), namespace.as_ref());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iterate() {
+        let mapping: PrefixMapping = [
+            ("a", "http://example.com/a/"),
+            ("b", "http://example.com/b/"),
+        ]
+        .into_iter()
+        .collect();
+        let mut iter = (&mapping).into_iter();
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.next_back(), Some(("b", "http://example.com/b/")));
+        assert_eq!(iter.next(), Some(("a", "http://example.com/a/")));
+        assert_eq!(iter.next(), None);
+
+        let mut prefixes = Vec::new();
+        for (prefix, _) in &mapping {
+            prefixes.push(prefix);
+        }
+        assert_eq!(prefixes, ["a", "b"]);
+    }
+
+    #[test]
+    fn add_prefixes_checks_first() {
+        let mut mapping = PrefixMapping::with_capacity(4);
+        mapping.add_prefix("a", "http://example.com/a/").unwrap();
+        mapping.protect_prefix("a");
+        mapping.set_strict_rebinding(true);
+
+        let before = mapping.clone();
+        assert_eq!(
+            mapping.add_prefixes([
+                ("b", "http://example.com/b/"),
+                ("b", "http://example.org/b/"),
+            ]),
+            Err(InvalidPrefixError::AlreadyBound)
+        );
+        assert_eq!(
+            mapping.add_prefixes([
+                ("b", "http://example.com/b/"),
+                ("a", "http://example.org/a/"),
+            ]),
+            Err(InvalidPrefixError::ProtectedPrefix)
+        );
+        assert_eq!(mapping, before);
+
+        mapping
+            .add_prefixes([
+                ("a", "http://example.com/a/"),
+                ("b", "http://example.com/b/"),
+                ("b", "http://example.com/b/"),
+            ])
+            .unwrap();
+        assert_eq!(mapping.len(), 2);
+    }
+
+    #[test]
+    fn collect_and_extend() {
+        let mut mapping: PrefixMapping = vec![
+            (String::from("a"), String::from("http://example.com/a/")),
+            (String::from("b"), String::from("http://example.com/b/")),
+        ]
+        .into_iter()
+        .collect();
+        mapping.protect_prefix("a");
+        mapping.extend([
+            ("a", "http://example.org/a/"),
+            ("c", "http://example.com/c/"),
+        ]);
+        assert_eq!(
+            mapping.expand_curie_string("a:x"),
+            Ok(String::from("http://example.com/a/x"))
+        );
+        assert_eq!(
+            mapping.try_extend([("a", "http://example.org/a/")]),
+            Err(InvalidPrefixError::ProtectedPrefix)
+        );
+        let prefixes: Vec<&str> = mapping.mappings().map(|(p, _)| p).collect();
+        assert_eq!(prefixes, ["a", "b", "c"]);
+    }
+}
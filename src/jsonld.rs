@@ -0,0 +1,1078 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Importing prefixes from [JSON-LD] contexts, and exporting them as
+//! contexts.
+//!
+//! This requires the `jsonld` feature.
+//!
+//! Only the parts of a context that affect prefixes are processed,
+//! following the JSON-LD 1.1 rules:
+//!
+//! * A term with a string value is only used as a prefix if it contains
+//!   neither `':'` nor `'/'` and its IRI ends with one of the URI
+//!   gen-delim characters (`:/?#[]@`).
+//! * A term with an expanded definition is only used as a prefix if
+//!   it has `"@prefix": true`.
+//! * Terms that are protected, either with `"@protected": true` on the
+//!   context or on their definition, can't be redefined or removed by
+//!   later contexts processed in the same call. Protected prefixes are
+//!   also protected in the mapping, as with
+//!   [`PrefixMapping::protect_prefix()`], so they survive later imports.
+//!   Prefixes protected in the mapping beforehand are treated the same
+//!   way.
+//! * Any other term with an IRI is added as a
+//!   [term](PrefixMapping::add_term()), which only matches the whole
+//!   term. Keyword aliases, such as `"type": "@type"`, are ignored.
+//! * `@vocab` sets the default prefix.
+//! * `@base` sets the [base IRI](PrefixMapping::set_base()), resolving
+//!   it against the current base if it is relative, and `null` removes
+//!   it.
+//! * A `null` context resets the mapping, removing the default prefix,
+//!   the base IRI, the prefixes, including those bound to URI
+//!   templates, and the terms.
+//! * The IRI of a term may be a CURIE using a prefix defined in the
+//!   same context or earlier, such as `"ex": "base:path/"`, or a
+//!   reference relative to `@vocab`.
+//!
+//! ```
+//! use curie::PrefixMapping;
+//!
+//! let mapping = PrefixMapping::from_jsonld_context(r#"{
+//!     "@context": {
+//!         "@vocab": "http://schema.org/",
+//!         "foaf": "http://xmlns.com/foaf/0.1/",
+//!         "name": "http://xmlns.com/foaf/0.1/name",
+//!         "dc": {"@id": "http://purl.org/dc/terms/", "@prefix": true}
+//!     }
+//! }"#).unwrap();
+//!
+//! assert_eq!(mapping.expand_curie_string("foaf:Person"),
+//!            Ok(String::from("http://xmlns.com/foaf/0.1/Person")));
+//! assert_eq!(mapping.expand_curie_string("dc:title"),
+//!            Ok(String::from("http://purl.org/dc/terms/title")));
+//! assert_eq!(mapping.expand_curie_string("Person"),
+//!            Ok(String::from("http://schema.org/Person")));
+//!
+//! // "name" doesn't end with a gen-delim, so it is a term rather than
+//! // a prefix.
+//! assert_eq!(mapping.expand_curie_string("name"),
+//!            Ok(String::from("http://xmlns.com/foaf/0.1/name")));
+//! assert!(mapping.expand_curie_string("name:x").is_err());
+//! ```
+//!
+//! [JSON-LD]: https://www.w3.org/TR/json-ld11/
+
+use crate::parse_error::token_end;
+use crate::turtle::Directive;
+use crate::{InvalidPrefixError, ParseError, ParseErrorKind, PrefixMapping};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::fmt;
+use std::iter::FusedIterator;
+
+/// The gen-delim characters from RFC 3986.
+const GEN_DELIMS: &[char] = &[':', '/', '?', '#', '[', ']', '@'];
+
+/// Errors that might occur when importing a JSON-LD context.
+///
+/// Where there is a corresponding JSON-LD error code, it is noted.
+#[derive(Clone, Debug, PartialEq)]
+pub enum JsonLdError {
+    /// The input is not valid JSON.
+    ///
+    /// The kind of the error is [`ParseErrorKind::InvalidJson`] or
+    /// [`ParseErrorKind::UnexpectedEnd`].
+    Json(ParseError),
+    /// The input is not valid YAML, when importing a YAML-LD context.
+    ///
+    /// The kind of the error is [`ParseErrorKind::InvalidYaml`].
+    Yaml(ParseError),
+    /// The YAML has no JSON equivalent, such as a mapping key that is
+    /// not a string or a value with a custom tag, when importing a
+    /// YAML-LD context.
+    UnsupportedYaml(String),
+    /// A context is not an object, an array, `null` or a string.
+    ///
+    /// This is the `invalid local context` error.
+    InvalidLocalContext,
+    /// A context is a reference to a remote context, which is not
+    /// supported.
+    RemoteContext(String),
+    /// The value of `@version` is not `1.1`.
+    ///
+    /// This is the `invalid @version value` error.
+    InvalidVersion,
+    /// The value of `@vocab` is not a string or `null`.
+    ///
+    /// This is the `invalid vocab mapping` error.
+    InvalidVocabMapping,
+    /// The value of `@base` is not a string or `null`.
+    ///
+    /// This is the `invalid base IRI` error.
+    InvalidBaseIri,
+    /// The value of `@protected` is not a boolean.
+    ///
+    /// This is the `invalid @protected value` error.
+    InvalidProtectedValue,
+    /// The definition of the term is not valid.
+    ///
+    /// This is the `invalid term definition` error.
+    InvalidTermDefinition(String),
+    /// The `@id` of the term definition is not a string or `null`.
+    ///
+    /// This is the `invalid IRI mapping` error.
+    InvalidIriMapping(String),
+    /// The `@prefix` of the term definition is not a boolean.
+    ///
+    /// This is the `invalid @prefix value` error.
+    InvalidPrefixValue(String),
+    /// The definition of the term depends on itself through the
+    /// prefixes used in its IRI.
+    ///
+    /// This is the `cyclic IRI mapping` error.
+    CyclicIriMapping(String),
+    /// The term is defined as a relative reference, but there is no
+    /// `@vocab` to resolve it against.
+    UnresolvableIri {
+        /// The term.
+        term: String,
+        /// The relative reference.
+        iri: String,
+    },
+    /// A protected term was redefined.
+    ///
+    /// This is the `protected term redefinition` error.
+    ProtectedTermRedefinition(String),
+    /// A prefix was redefined as a different IRI, when the mapping
+    /// doesn't allow rebinding. See
+    /// [`PrefixMapping::set_strict_rebinding()`].
+    RedefinedPrefix(String),
+    /// A `null` context was used while there are protected terms.
+    ///
+    /// This is the `invalid context nullification` error.
+    InvalidContextNullification,
+}
+
+impl fmt::Display for JsonLdError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JsonLdError::Json(err) | JsonLdError::Yaml(err) => err.fmt(f),
+            JsonLdError::UnsupportedYaml(what) => write!(f, "unsupported YAML: {what}"),
+            JsonLdError::InvalidLocalContext => f.write_str("invalid local context"),
+            JsonLdError::RemoteContext(url) => {
+                write!(f, "remote contexts are not supported: {url}")
+            }
+            JsonLdError::InvalidVersion => f.write_str("invalid @version value"),
+            JsonLdError::InvalidVocabMapping => f.write_str("invalid vocab mapping"),
+            JsonLdError::InvalidBaseIri => f.write_str("invalid base IRI"),
+            JsonLdError::InvalidProtectedValue => f.write_str("invalid @protected value"),
+            JsonLdError::InvalidTermDefinition(term) => {
+                write!(f, "invalid term definition for `{term}`")
+            }
+            JsonLdError::InvalidIriMapping(term) => write!(f, "invalid IRI mapping for `{term}`"),
+            JsonLdError::InvalidPrefixValue(term) => {
+                write!(f, "invalid @prefix value for `{term}`")
+            }
+            JsonLdError::CyclicIriMapping(term) => write!(f, "cyclic IRI mapping for `{term}`"),
+            JsonLdError::UnresolvableIri { term, iri } => write!(
+                f,
+                "`{term}` is defined as `{iri}`, which is relative and there is no @vocab"
+            ),
+            JsonLdError::ProtectedTermRedefinition(term) => {
+                write!(f, "protected term redefinition of `{term}`")
+            }
+            JsonLdError::RedefinedPrefix(term) => write!(f, "redefinition of the prefix `{term}`"),
+            JsonLdError::InvalidContextNullification => {
+                f.write_str("invalid context nullification")
+            }
+        }
+    }
+}
+
+impl std::error::Error for JsonLdError {}
+
+/// Locate a JSON syntax error in `json`.
+fn syntax_error(json: &str, err: &serde_json::Error) -> ParseError {
+    let kind = if err.is_eof() {
+        ParseErrorKind::UnexpectedEnd
+    } else {
+        ParseErrorKind::InvalidJson
+    };
+    // serde_json reports the line and the column in bytes, counting
+    // from 1, with column 0 when the error is at a line break.
+    let line_start = json
+        .split_inclusive('\n')
+        .take(err.line().saturating_sub(1))
+        .map(str::len)
+        .sum::<usize>();
+    let mut offset = (line_start + err.column().saturating_sub(1)).min(json.len());
+    while !json.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    let end = if kind == ParseErrorKind::UnexpectedEnd {
+        offset
+    } else {
+        token_end(json, offset)
+    };
+    ParseError::at(kind, json, offset, end)
+}
+
+/// What a term was defined as, for comparison against redefinitions
+/// of protected terms.
+#[derive(Clone, PartialEq)]
+struct Definition {
+    iri: Option<String>,
+    prefix: bool,
+}
+
+struct ContextProcessor<'m> {
+    mapping: &'m mut PrefixMapping,
+    protected: HashMap<String, Definition>,
+}
+
+impl ContextProcessor<'_> {
+    fn process(&mut self, context: &Value) -> Result<(), JsonLdError> {
+        match context {
+            Value::Null => {
+                if !self.protected.is_empty() || self.mapping.protected_prefixes().next().is_some()
+                {
+                    return Err(JsonLdError::InvalidContextNullification);
+                }
+                self.mapping.default = None;
+                self.mapping.base = None;
+                self.mapping.mapping.clear();
+                self.mapping.templates.clear();
+                self.mapping.terms.clear();
+                Ok(())
+            }
+            Value::Array(contexts) => contexts
+                .iter()
+                .try_for_each(|context| self.process(context)),
+            Value::Object(definitions) => self.process_definitions(definitions),
+            Value::String(url) => Err(JsonLdError::RemoteContext(url.clone())),
+            _ => Err(JsonLdError::InvalidLocalContext),
+        }
+    }
+
+    fn process_definitions(&mut self, definitions: &Map<String, Value>) -> Result<(), JsonLdError> {
+        match definitions.get("@version") {
+            None => {}
+            Some(Value::Number(version)) if version.as_f64() == Some(1.1) => {}
+            Some(_) => return Err(JsonLdError::InvalidVersion),
+        }
+        let protected = match definitions.get("@protected") {
+            None => false,
+            Some(Value::Bool(protected)) => *protected,
+            Some(_) => return Err(JsonLdError::InvalidProtectedValue),
+        };
+        match definitions.get("@base") {
+            None => {}
+            Some(Value::Null) => self.mapping.base = None,
+            Some(Value::String(base)) => self.mapping.set_relative_base(base),
+            Some(_) => return Err(JsonLdError::InvalidBaseIri),
+        }
+        match definitions.get("@vocab") {
+            None => {}
+            Some(Value::Null) => self.mapping.default = None,
+            Some(Value::String(vocab)) => self.mapping.set_default(vocab),
+            Some(_) => return Err(JsonLdError::InvalidVocabMapping),
+        }
+        let mut local = LocalContext {
+            definitions,
+            protected,
+            defined: HashMap::new(),
+        };
+        for term in definitions.keys() {
+            if !term.starts_with('@') {
+                self.define_term(&mut local, term)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn define_term<'d>(
+        &mut self,
+        local: &mut LocalContext<'d>,
+        term: &'d str,
+    ) -> Result<(), JsonLdError> {
+        match local.defined.get(term) {
+            Some(true) => return Ok(()),
+            Some(false) => return Err(JsonLdError::CyclicIriMapping(String::from(term))),
+            None => {}
+        }
+        local.defined.insert(term, false);
+
+        let value = &local.definitions[term];
+        let default_protected = local.protected;
+        let simple_term = !term.contains([':', '/']);
+        let (definition, protected) = match value {
+            Value::Null => (
+                Definition {
+                    iri: None,
+                    prefix: false,
+                },
+                default_protected,
+            ),
+            Value::String(iri) => {
+                let iri = self.expand_iri(local, term, iri)?;
+                let prefix = simple_term && (iri.ends_with(GEN_DELIMS) || iri.starts_with("_:"));
+                (
+                    Definition {
+                        iri: Some(iri),
+                        prefix,
+                    },
+                    default_protected,
+                )
+            }
+            Value::Object(expanded) => {
+                let iri = match expanded.get("@id") {
+                    None | Some(Value::Null) => None,
+                    Some(Value::String(iri)) => Some(self.expand_iri(local, term, iri)?),
+                    Some(_) => return Err(JsonLdError::InvalidIriMapping(String::from(term))),
+                };
+                let prefix = match expanded.get("@prefix") {
+                    None => false,
+                    Some(Value::Bool(prefix)) if simple_term => *prefix,
+                    Some(Value::Bool(_)) => {
+                        return Err(JsonLdError::InvalidTermDefinition(String::from(term)))
+                    }
+                    Some(_) => return Err(JsonLdError::InvalidPrefixValue(String::from(term))),
+                };
+                if prefix && iri.is_none() {
+                    return Err(JsonLdError::InvalidIriMapping(String::from(term)));
+                }
+                let protected = match expanded.get("@protected") {
+                    None => default_protected,
+                    Some(Value::Bool(protected)) => *protected,
+                    Some(_) => return Err(JsonLdError::InvalidProtectedValue),
+                };
+                (Definition { iri, prefix }, protected)
+            }
+            _ => return Err(JsonLdError::InvalidTermDefinition(String::from(term))),
+        };
+
+        local.defined.insert(term, true);
+        if let Some(previous) = self.protected.get(term) {
+            // Redefining a protected term is only allowed if the
+            // definition is unchanged.
+            if *previous != definition {
+                return Err(JsonLdError::ProtectedTermRedefinition(String::from(term)));
+            }
+            return Ok(());
+        }
+
+        // The prefix is changed first, as it may be protected, so that
+        // a term is only replaced once its definition has succeeded.
+        let result = match definition.iri {
+            Some(ref iri) if definition.prefix => self.mapping.add_prefix(term, iri),
+            _ => self.mapping.remove_prefix(term).map(|_| ()),
+        };
+        match result {
+            Ok(()) => {}
+            Err(InvalidPrefixError::ReservedPrefix | InvalidPrefixError::InvalidName) => {
+                return Err(JsonLdError::InvalidTermDefinition(String::from(term)))
+            }
+            Err(InvalidPrefixError::ProtectedPrefix) => {
+                return Err(JsonLdError::ProtectedTermRedefinition(String::from(term)))
+            }
+            Err(InvalidPrefixError::InvalidNamespace) => {
+                return Err(JsonLdError::InvalidIriMapping(String::from(term)))
+            }
+            Err(InvalidPrefixError::AlreadyBound) => {
+                return Err(JsonLdError::RedefinedPrefix(String::from(term)))
+            }
+        }
+        self.mapping.remove_term(term);
+        match definition.iri {
+            Some(ref iri) if !definition.prefix && !iri.starts_with('@') => {
+                self.mapping.add_term(term, iri);
+            }
+            _ => {}
+        }
+        if protected {
+            if definition.prefix {
+                self.mapping.protect_prefix(term);
+            }
+            self.protected.insert(String::from(term), definition);
+        }
+        Ok(())
+    }
+
+    /// Expand the IRI that a term is defined as.
+    ///
+    /// The IRI may be a CURIE using a prefix defined elsewhere in the
+    /// same context, which is defined first, or already in the mapping.
+    /// A relative reference is resolved against `@vocab`.
+    fn expand_iri<'d>(
+        &mut self,
+        local: &mut LocalContext<'d>,
+        term: &str,
+        iri: &'d str,
+    ) -> Result<String, JsonLdError> {
+        if iri.starts_with('@') {
+            // A keyword alias, which is never a prefix.
+            return Ok(String::from(iri));
+        }
+        if let Some((prefix, reference)) = iri.split_once(':') {
+            if prefix == "_" || reference.starts_with("//") {
+                return Ok(String::from(iri));
+            }
+            if local.definitions.contains_key(prefix) {
+                self.define_term(local, prefix)?;
+            }
+            return Ok(match self.mapping.namespace_for(Some(prefix)) {
+                Ok(namespace) => String::from(namespace) + reference,
+                // Not a CURIE, but an absolute IRI such as a URN.
+                Err(_) => String::from(iri),
+            });
+        }
+        match self.mapping.default {
+            Some(ref vocab) => Ok(String::clone(vocab) + iri),
+            None => Err(JsonLdError::UnresolvableIri {
+                term: String::from(term),
+                iri: String::from(iri),
+            }),
+        }
+    }
+}
+
+/// The state of processing a single context object.
+struct LocalContext<'d> {
+    definitions: &'d Map<String, Value>,
+    /// The value of `@protected` for the context.
+    protected: bool,
+    /// The terms that have been defined, or are being defined when
+    /// `false`, for detecting cycles.
+    defined: HashMap<&'d str, bool>,
+}
+
+/// Read the definitions of a JSON-LD context, without applying them to
+/// a mapping.
+///
+/// `json` may be a document with an `@context` entry or a context
+/// itself, and arrays of contexts are read in order. Within each
+/// context object, `@base` is yielded as a [`Directive::Base`] and
+/// `@vocab` as a [`Directive::DefaultPrefix`], before the terms. A term
+/// is yielded as a [`Directive::Prefix`] if it would be used as a
+/// prefix, judging by the IRI as written, and as a [`Directive::Term`]
+/// otherwise.
+///
+/// IRIs are yielded as written, so CURIEs and relative references in
+/// them are left for the caller to expand. Definitions that remove
+/// something, such as `null` contexts and terms defined as `null`, and
+/// keyword aliases are skipped. Reading stops after the first error.
+///
+/// This requires the `jsonld` feature.
+///
+/// # Examples
+///
+/// ```
+/// use curie::jsonld;
+/// use curie::turtle::Directive;
+///
+/// let directives = jsonld::directives(r#"{
+///     "@context": {
+///         "foaf": "http://xmlns.com/foaf/0.1/",
+///         "name": "foaf:name",
+///         "@vocab": "http://schema.org/"
+///     }
+/// }"#).collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(directives, [
+///     Directive::DefaultPrefix(String::from("http://schema.org/")),
+///     Directive::Prefix {
+///         prefix: String::from("foaf"),
+///         iri: String::from("http://xmlns.com/foaf/0.1/"),
+///     },
+///     Directive::Term {
+///         term: String::from("name"),
+///         iri: String::from("foaf:name"),
+///     },
+/// ]);
+/// ```
+pub fn directives(json: &str) -> Directives {
+    let mut directives = Directives {
+        contexts: Vec::new(),
+        entries: Vec::new().into_iter(),
+        error: None,
+    };
+    match serde_json::from_str(json) {
+        Ok(Value::Object(mut document)) if document.contains_key("@context") => {
+            directives.contexts.push(document["@context"].take());
+        }
+        Ok(context) => directives.contexts.push(context),
+        Err(err) => directives.error = Some(JsonLdError::Json(syntax_error(json, &err))),
+    }
+    directives
+}
+
+/// An iterator over the definitions of a JSON-LD context.
+///
+/// This is created by [`directives()`].
+#[derive(Debug)]
+pub struct Directives {
+    /// The contexts still to be read, last first.
+    contexts: Vec<Value>,
+    /// The entries of the context object being read.
+    entries: std::vec::IntoIter<(String, Value)>,
+    /// An error to yield before stopping.
+    error: Option<JsonLdError>,
+}
+
+impl Directives {
+    fn next_directive(&mut self) -> Result<Option<Directive>, JsonLdError> {
+        loop {
+            for (key, value) in self.entries.by_ref() {
+                if let Some(directive) = entry_directive(key, value)? {
+                    return Ok(Some(directive));
+                }
+            }
+            match self.contexts.pop() {
+                None | Some(Value::Null) => {}
+                Some(Value::Array(contexts)) => self.contexts.extend(contexts.into_iter().rev()),
+                Some(Value::Object(definitions)) => {
+                    let mut entries: Vec<_> = definitions.into_iter().collect();
+                    entries.sort_by_key(|(key, _)| match key.as_str() {
+                        "@base" => 0,
+                        "@vocab" => 1,
+                        _ => 2,
+                    });
+                    self.entries = entries.into_iter();
+                }
+                Some(Value::String(url)) => return Err(JsonLdError::RemoteContext(url)),
+                Some(_) => return Err(JsonLdError::InvalidLocalContext),
+            }
+            if self.contexts.is_empty() && self.entries.len() == 0 {
+                return Ok(None);
+            }
+        }
+    }
+}
+
+impl Iterator for Directives {
+    type Item = Result<Directive, JsonLdError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(err) = self.error.take() {
+            self.contexts.clear();
+            self.entries = Vec::new().into_iter();
+            return Some(Err(err));
+        }
+        match self.next_directive() {
+            Ok(directive) => directive.map(Ok),
+            Err(err) => {
+                self.error = Some(err);
+                self.next()
+            }
+        }
+    }
+}
+
+impl FusedIterator for Directives {}
+
+/// The directive for an entry of a context object, if any.
+fn entry_directive(key: String, value: Value) -> Result<Option<Directive>, JsonLdError> {
+    match key.as_str() {
+        "@base" => {
+            return match value {
+                Value::Null => Ok(None),
+                Value::String(base) => Ok(Some(Directive::Base(base))),
+                _ => Err(JsonLdError::InvalidBaseIri),
+            }
+        }
+        "@vocab" => {
+            return match value {
+                Value::Null => Ok(None),
+                Value::String(vocab) => Ok(Some(Directive::DefaultPrefix(vocab))),
+                _ => Err(JsonLdError::InvalidVocabMapping),
+            }
+        }
+        _ if key.starts_with('@') => return Ok(None),
+        _ => {}
+    }
+    let simple_term = !key.contains([':', '/']);
+    let (iri, prefix) = match value {
+        Value::Null => return Ok(None),
+        Value::String(iri) => {
+            let prefix = simple_term && (iri.ends_with(GEN_DELIMS) || iri.starts_with("_:"));
+            (iri, prefix)
+        }
+        Value::Object(expanded) => {
+            let prefix = match expanded.get("@prefix") {
+                None => false,
+                Some(Value::Bool(prefix)) if simple_term => *prefix,
+                Some(Value::Bool(_)) => return Err(JsonLdError::InvalidTermDefinition(key)),
+                Some(_) => return Err(JsonLdError::InvalidPrefixValue(key)),
+            };
+            match expanded.get("@id") {
+                None | Some(Value::Null) => return Ok(None),
+                Some(Value::String(iri)) => (iri.clone(), prefix),
+                Some(_) => return Err(JsonLdError::InvalidIriMapping(key)),
+            }
+        }
+        _ => return Err(JsonLdError::InvalidTermDefinition(key)),
+    };
+    Ok(if iri.starts_with('@') {
+        None
+    } else if prefix {
+        Some(Directive::Prefix { prefix: key, iri })
+    } else {
+        Some(Directive::Term { term: key, iri })
+    })
+}
+
+impl PrefixMapping {
+    /// Create a mapping from the prefixes defined in a JSON-LD context.
+    ///
+    /// See [`PrefixMapping::extend_from_jsonld_context()`] for details.
+    ///
+    /// This requires the `jsonld` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`JsonLdError`] if the JSON is not valid or the context
+    /// is not valid.
+    pub fn from_jsonld_context(json: &str) -> Result<PrefixMapping, JsonLdError> {
+        let mut mapping = PrefixMapping::default();
+        mapping.extend_from_jsonld_context(json)?;
+        Ok(mapping)
+    }
+
+    /// Add the prefixes defined in a JSON-LD context to this mapping.
+    ///
+    /// `json` may either be a JSON-LD document with an `@context` entry,
+    /// or a context itself: an object, an array of contexts or `null`.
+    /// Remote contexts, given by their URL, are not supported.
+    ///
+    /// See the [module documentation](crate::jsonld) for the rules used.
+    ///
+    /// This requires the `jsonld` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`JsonLdError`] if the JSON is not valid or the context
+    /// is not valid. Definitions processed before the error was found
+    /// remain in the mapping.
+    pub fn extend_from_jsonld_context(&mut self, json: &str) -> Result<(), JsonLdError> {
+        let value: Value = serde_json::from_str(json)
+            .map_err(|err| JsonLdError::Json(syntax_error(json, &err)))?;
+        self.extend_from_jsonld_value(&value)
+    }
+
+    /// Add the prefixes defined in a parsed JSON-LD document or context.
+    pub(crate) fn extend_from_jsonld_value(&mut self, value: &Value) -> Result<(), JsonLdError> {
+        let context = match value {
+            Value::Object(document) if document.contains_key("@context") => &document["@context"],
+            context => context,
+        };
+        ContextProcessor {
+            mapping: self,
+            protected: HashMap::new(),
+        }
+        .process(context)
+    }
+
+    /// Write the default prefix, the base IRI and the prefixes of this
+    /// mapping as a JSON-LD document with an `@context` entry.
+    ///
+    /// The default prefix is written as `@vocab` and the base IRI as
+    /// `@base`. Prefixes are written so that importing the context
+    /// gives the same mapping: a namespace that JSON-LD wouldn't use as
+    /// a prefix, because it doesn't end with a gen-delim character, is
+    /// written with `"@prefix": true`, and protected prefixes with
+    /// `"@protected": true`. Terms are written after the prefixes,
+    /// with an expanded definition where JSON-LD would otherwise take
+    /// them to be prefixes, unless a prefix has the same name. URI
+    /// templates are not written, and neither is the empty prefix or an
+    /// empty term, since JSON-LD can't define an empty term.
+    ///
+    /// This requires the `jsonld` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::PrefixMapping;
+    ///
+    /// let mut mapping = PrefixMapping::default();
+    /// mapping.set_default("http://schema.org/");
+    /// mapping.add_prefix("foaf", "http://xmlns.com/foaf/0.1/").unwrap();
+    /// mapping.add_prefix("item", "http://example.com/item").unwrap();
+    ///
+    /// assert_eq!(mapping.to_jsonld_context(), r#"{
+    ///   "@context": {
+    ///     "@vocab": "http://schema.org/",
+    ///     "foaf": "http://xmlns.com/foaf/0.1/",
+    ///     "item": {
+    ///       "@id": "http://example.com/item",
+    ///       "@prefix": true
+    ///     }
+    ///   }
+    /// }"#);
+    /// assert_eq!(PrefixMapping::from_jsonld_context(&mapping.to_jsonld_context()).unwrap(),
+    ///            mapping);
+    /// ```
+    #[must_use]
+    pub fn to_jsonld_context(&self) -> String {
+        serde_json::to_string_pretty(&self.jsonld_context_document())
+            .expect("JSON values can be written")
+    }
+
+    /// Build the document written by [`PrefixMapping::to_jsonld_context()`].
+    pub(crate) fn jsonld_context_document(&self) -> Value {
+        let mut context = Map::new();
+        if let Some(ref base) = self.base {
+            context.insert(String::from("@base"), Value::String(base.clone()));
+        }
+        if let Some(ref vocab) = self.default {
+            context.insert(String::from("@vocab"), Value::String(vocab.clone()));
+        }
+        for (prefix, namespace) in &self.mapping {
+            if prefix.is_empty() {
+                continue;
+            }
+            let protected = self.is_protected(prefix);
+            let definition = if namespace.ends_with(GEN_DELIMS) && !protected {
+                Value::String(namespace.clone())
+            } else {
+                let mut definition = Map::new();
+                definition.insert(String::from("@id"), Value::String(namespace.clone()));
+                definition.insert(String::from("@prefix"), Value::Bool(true));
+                if protected {
+                    definition.insert(String::from("@protected"), Value::Bool(true));
+                }
+                Value::Object(definition)
+            };
+            context.insert(prefix.clone(), definition);
+        }
+        for (term, iri) in &self.terms {
+            if term.is_empty() || self.mapping.contains_key(term) {
+                continue;
+            }
+            let simple_term = !term.contains([':', '/']);
+            let definition = if simple_term && (iri.ends_with(GEN_DELIMS) || iri.starts_with("_:"))
+            {
+                let mut definition = Map::new();
+                definition.insert(String::from("@id"), Value::String(iri.clone()));
+                Value::Object(definition)
+            } else {
+                Value::String(iri.clone())
+            };
+            context.insert(term.clone(), definition);
+        }
+        let mut document = Map::new();
+        document.insert(String::from("@context"), Value::Object(context));
+        Value::Object(document)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Curie;
+
+    #[test]
+    fn prefix_flag() {
+        let mapping = PrefixMapping::from_jsonld_context(
+            r#"{
+                "ex": "http://example.com/",
+                "frag": "http://example.com/ns#",
+                "name": "http://example.com/name",
+                "ex:other": "http://example.com/other/",
+                "def": {"@id": "http://example.com/def/"},
+                "forced": {"@id": "http://example.com/forced", "@prefix": true}
+            }"#,
+        )
+        .unwrap();
+
+        let prefixes: Vec<&str> = mapping.mappings().map(|(p, _)| p).collect();
+        assert_eq!(prefixes, ["ex", "frag", "forced"]);
+        let terms: Vec<&str> = mapping.terms().map(|(t, _)| t).collect();
+        assert_eq!(terms, ["name", "ex:other", "def"]);
+    }
+
+    #[test]
+    fn term_definitions() {
+        let mut mapping = PrefixMapping::from_jsonld_context(
+            r#"{
+                "foaf": "http://xmlns.com/foaf/0.1/",
+                "name": "foaf:name",
+                "knows": {"@id": "foaf:knows", "@type": "@id"},
+                "type": "@type",
+                "home": {"@id": "http://example.com/home/"}
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            mapping.expand_curie_string("name"),
+            Ok(String::from("http://xmlns.com/foaf/0.1/name"))
+        );
+        assert_eq!(
+            mapping.shrink_iri("http://xmlns.com/foaf/0.1/knows"),
+            Ok(Curie::new(None, "knows"))
+        );
+        assert_eq!(mapping.term("type"), None);
+        assert_eq!(
+            PrefixMapping::from_jsonld_context(&mapping.to_jsonld_context()).unwrap(),
+            mapping
+        );
+
+        mapping
+            .extend_from_jsonld_context(r#"{"name": null}"#)
+            .unwrap();
+        assert_eq!(mapping.term("name"), None);
+        assert_eq!(
+            mapping.term("knows"),
+            Some("http://xmlns.com/foaf/0.1/knows")
+        );
+    }
+
+    #[test]
+    fn arrays_and_null() {
+        let mut mapping = PrefixMapping::default();
+        mapping
+            .extend_from_jsonld_context(
+                r#"[
+                    {"a": "http://example.com/a/", "b": "http://example.com/b/"},
+                    {"a": null, "@vocab": "http://example.com/", "@base": "http://example.com/doc"}
+                ]"#,
+            )
+            .unwrap();
+        assert_eq!(mapping.base(), Some("http://example.com/doc"));
+        mapping
+            .extend_from_jsonld_context(r#"{"@base": "other/doc"}"#)
+            .unwrap();
+        assert_eq!(mapping.base(), Some("http://example.com/other/doc"));
+        assert_eq!(mapping.expand_curie_string("a:x").ok(), None);
+        assert_eq!(
+            mapping.expand_curie_string("b:x"),
+            Ok(String::from("http://example.com/b/x"))
+        );
+        assert_eq!(
+            mapping.expand_curie_string("x"),
+            Ok(String::from("http://example.com/x"))
+        );
+
+        mapping
+            .add_prefix_template("doi", "https://doi.org/{id}")
+            .unwrap();
+        mapping.extend_from_jsonld_context("null").unwrap();
+        assert_eq!(mapping, PrefixMapping::default());
+        assert!(!mapping.contains_prefix("doi"));
+    }
+
+    #[test]
+    fn protected_terms() {
+        let mut mapping = PrefixMapping::default();
+
+        // Identical redefinitions are allowed.
+        mapping
+            .extend_from_jsonld_context(
+                r#"[
+                    {"@protected": true, "ex": "http://example.com/"},
+                    {"ex": "http://example.com/"}
+                ]"#,
+            )
+            .unwrap();
+
+        let err = mapping
+            .extend_from_jsonld_context(
+                r#"[
+                    {"ex": {"@id": "http://example.com/", "@protected": true}},
+                    {"ex": "http://example.org/"}
+                ]"#,
+            )
+            .unwrap_err();
+        assert!(matches!(err, JsonLdError::ProtectedTermRedefinition(ref t) if t == "ex"));
+
+        let err = mapping
+            .extend_from_jsonld_context(
+                r#"[{"@protected": true, "ex": "http://example.com/"}, null]"#,
+            )
+            .unwrap_err();
+        assert!(matches!(err, JsonLdError::InvalidContextNullification));
+    }
+
+    #[test]
+    fn protected_in_mapping() {
+        let mut mapping = PrefixMapping::default();
+        mapping
+            .add_prefix("rdf", "http://www.w3.org/1999/02/22-rdf-syntax-ns#")
+            .unwrap();
+        mapping.protect_prefix("rdf");
+
+        let err = mapping
+            .extend_from_jsonld_context(r#"{"rdf": "http://example.com/"}"#)
+            .unwrap_err();
+        assert!(matches!(err, JsonLdError::ProtectedTermRedefinition(ref t) if t == "rdf"));
+        let err = mapping
+            .extend_from_jsonld_context(r#"{"rdf": null}"#)
+            .unwrap_err();
+        assert!(matches!(err, JsonLdError::ProtectedTermRedefinition(_)));
+        let err = mapping.extend_from_jsonld_context("null").unwrap_err();
+        assert!(matches!(err, JsonLdError::InvalidContextNullification));
+
+        // A failed redefinition leaves a term of the same name in place.
+        mapping.add_term("rdf", "http://example.com/rdf");
+        let before = mapping.clone();
+        assert!(mapping
+            .extend_from_jsonld_context(r#"{"rdf": "http://example.com/other"}"#)
+            .is_err());
+        assert_eq!(mapping, before);
+
+        // Protection in a context carries over to later imports.
+        mapping
+            .extend_from_jsonld_context(r#"{"@protected": true, "ex": "http://example.com/"}"#)
+            .unwrap();
+        assert!(mapping.is_protected("ex"));
+        assert!(mapping
+            .extend_from_jsonld_context(r#"{"ex": "http://example.org/"}"#)
+            .is_err());
+    }
+
+    #[test]
+    fn relative_definitions() {
+        let mapping = PrefixMapping::from_jsonld_context(
+            r#"{
+                "@vocab": "http://example.com/vocab/",
+                "sub": "base:sub/",
+                "base": "http://example.com/",
+                "rel": "terms/",
+                "isbn": "urn:isbn:"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            mapping.expand_curie_string("sub:a"),
+            Ok(String::from("http://example.com/sub/a"))
+        );
+        assert_eq!(
+            mapping.expand_curie_string("rel:a"),
+            Ok(String::from("http://example.com/vocab/terms/a"))
+        );
+        assert_eq!(
+            mapping.expand_curie_string("isbn:1"),
+            Ok(String::from("urn:isbn:1"))
+        );
+
+        let err = PrefixMapping::from_jsonld_context(r#"{"a": "b:x/", "b": "a:y/"}"#).unwrap_err();
+        assert!(matches!(err, JsonLdError::CyclicIriMapping(ref t) if t == "a"));
+        let err = PrefixMapping::from_jsonld_context(r#"{"a": "path/"}"#).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`a` is defined as `path/`, which is relative and there is no @vocab"
+        );
+    }
+
+    #[test]
+    fn export() {
+        let mut mapping = PrefixMapping::default();
+        assert_eq!(mapping.to_jsonld_context(), "{\n  \"@context\": {}\n}");
+
+        mapping.set_base("http://example.com/doc");
+        mapping
+            .add_prefix("rdf", "http://www.w3.org/1999/02/22-rdf-syntax-ns#")
+            .unwrap();
+        mapping.protect_prefix("rdf");
+        let json = mapping.to_jsonld_context();
+        assert!(json.contains("\"@base\": \"http://example.com/doc\""));
+        assert!(json.contains("\"@protected\": true"));
+        let imported = PrefixMapping::from_jsonld_context(&json).unwrap();
+        assert_eq!(imported, mapping);
+        assert!(imported.is_protected("rdf"));
+
+        mapping.add_prefix("", "http://example.com/empty/").unwrap();
+        mapping.add_term("", "http://example.com/empty");
+        assert_eq!(mapping.to_jsonld_context(), json);
+    }
+
+    #[test]
+    fn invalid_contexts() {
+        let check = |json: &str| PrefixMapping::from_jsonld_context(json).unwrap_err();
+
+        assert!(matches!(check("{"), JsonLdError::Json(_)));
+        match check("{\n  \"ex\": \"http://example.com/\",\n  \"a\" 1\n}") {
+            JsonLdError::Json(err) => {
+                assert_eq!(err.kind(), ParseErrorKind::InvalidJson);
+                assert_eq!((err.line(), err.column()), (3, 7));
+                assert_eq!(err.slice(), "1");
+            }
+            err => panic!("unexpected error {err:?}"),
+        }
+        assert!(matches!(check("1"), JsonLdError::InvalidLocalContext));
+        assert!(matches!(
+            check(r#""http://example.com/context.jsonld""#),
+            JsonLdError::RemoteContext(_)
+        ));
+        assert!(matches!(
+            check(r#"{"@version": 1.0}"#),
+            JsonLdError::InvalidVersion
+        ));
+        assert!(matches!(
+            check(r#"{"@vocab": true}"#),
+            JsonLdError::InvalidVocabMapping
+        ));
+        assert!(matches!(
+            check(r#"{"@base": 1}"#),
+            JsonLdError::InvalidBaseIri
+        ));
+        assert!(matches!(
+            check(r#"{"ex": {"@id": "http://example.com/", "@prefix": "yes"}}"#),
+            JsonLdError::InvalidPrefixValue(_)
+        ));
+        assert!(matches!(
+            check(r#"{"ex:a": {"@id": "http://example.com/", "@prefix": true}}"#),
+            JsonLdError::InvalidTermDefinition(_)
+        ));
+        assert!(matches!(
+            check(r#"{"ex": 1}"#),
+            JsonLdError::InvalidTermDefinition(_)
+        ));
+    }
+
+    #[test]
+    fn directives() {
+        let read = |json: &str| super::directives(json).collect::<Vec<_>>();
+        let prefix = |prefix: &str, iri: &str| {
+            Ok(Directive::Prefix {
+                prefix: prefix.to_owned(),
+                iri: iri.to_owned(),
+            })
+        };
+
+        assert_eq!(
+            read(
+                r#"[
+                    {"ex": "http://example.com/", "@base": "http://example.org/"},
+                    null,
+                    {
+                        "a": {"@id": "ex:a"},
+                        "b": {"@id": "b/", "@prefix": true},
+                        "c": null,
+                        "id": "@id"
+                    }
+                ]"#
+            ),
+            [
+                Ok(Directive::Base(String::from("http://example.org/"))),
+                prefix("ex", "http://example.com/"),
+                Ok(Directive::Term {
+                    term: String::from("a"),
+                    iri: String::from("ex:a"),
+                }),
+                prefix("b", "b/"),
+            ]
+        );
+        assert_eq!(
+            read(r#"[{"ex": "http://example.com/"}, {"ex": 1}, {"other": "http://example.org/"}]"#),
+            [
+                prefix("ex", "http://example.com/"),
+                Err(JsonLdError::InvalidTermDefinition(String::from("ex"))),
+            ]
+        );
+        assert!(matches!(&read("{")[..], [Err(JsonLdError::Json(_))]));
+        assert_eq!(read("null"), []);
+    }
+}
@@ -115,6 +115,38 @@
 //!            mapper.shrink_iri("http://xmlns.com/foaf/0.1/Agent"));
 //! ```
 //!
+//! ## References with several parts
+//!
+//! A reference may contain several path segments, a query and a
+//! fragment, as in `schema:Person/extra?x=1#top`. The prefix ends at
+//! the first colon, so the reference may also contain colons.
+//!
+//! When expanding, the reference is appended to the namespace as it
+//! is. The one exception is a reference containing a `#` when the
+//! namespace already has a fragment, as an IRI can only have one
+//! fragment: this is [`ExpansionError::DuplicateFragment`].
+//!
+//! When shrinking, a namespace is only used when it ends at a boundary
+//! of the IRI: a namespace that ends within the authority, like
+//! `http://example.com`, doesn't match `http://example.com.au/`.
+//!
+//! ## Metrics
+//!
+//! With the `metrics` feature, these are reported through the
+//! [`metrics`](https://docs.rs/metrics) facade:
+//!
+//! * `curie_expansions_total`: a counter of expansions, labelled with
+//!   their `result`: `ok`, `unmapped_prefix`, `missing_default` or
+//!   `invalid_reference`.
+//! * `curie_expanded_iri_length`: a histogram of the lengths of
+//!   expanded IRIs, in bytes.
+//! * `curie_shrinks_total`: a counter of attempts to shrink an IRI,
+//!   labelled with their `result`: `ok` or `no_match`.
+//! * `curie_shrink_ambiguities_total`: a counter of IRIs that more than
+//!   one namespace could shrink.
+//! * `curie_interner_lookups_total`: a counter of lookups in a
+//!   `SharedInterner`, labelled with their `result`: `hit` or `miss`.
+//!
 //! [defined by the W3C]: https://www.w3.org/TR/curie/
 //! [specification]: https://www.w3.org/TR/curie/
 
@@ -129,6 +161,122 @@
 
 use std::fmt;
 
+#[cfg(feature = "bioregistry-common")]
+mod bioregistry;
+mod builder;
+#[cfg(feature = "bumpalo")]
+mod bump;
+mod compose;
+mod concurrent;
+#[cfg(test)]
+mod conformance;
+mod coverage;
+mod curie_buf;
+mod curie_enum;
+mod dataset;
+mod diff;
+mod expanded_term;
+mod extra;
+mod format;
+#[cfg(feature = "dashmap")]
+mod interner;
+mod iri;
+mod iter;
+#[cfg(feature = "jsonld")]
+pub mod jsonld;
+mod merge;
+mod namespace;
+mod normalize;
+mod packed;
+mod parent;
+mod parse_error;
+#[cfg(feature = "regex")]
+mod pattern;
+mod prefix_block;
+#[cfg(feature = "prefix-cc")]
+pub mod prefix_cc;
+mod prefix_id;
+pub mod presets;
+mod protect;
+pub mod rdfa;
+mod registry;
+mod rename;
+mod resolve;
+mod resolved;
+mod reuse;
+mod round_trip;
+#[cfg(feature = "schemars")]
+mod schema;
+mod scoped;
+mod select;
+#[cfg(feature = "serde_with")]
+pub mod serde_as;
+#[cfg(feature = "serde")]
+mod serde_impl;
+#[cfg(feature = "arc-swap")]
+mod snapshot;
+pub mod sort;
+mod sparql;
+#[cfg(feature = "heapless")]
+mod static_mapping;
+mod suggest;
+mod syntax;
+mod telemetry;
+mod template;
+mod terms;
+pub mod testing;
+pub mod turtle;
+pub mod vocab;
+mod vocabulary;
+mod void;
+mod write;
+mod xmlns;
+#[cfg(feature = "yaml")]
+pub mod yamlld;
+
+pub use builder::{is_valid_curie, CurieBuildError, CurieBuilder};
+pub use compose::{ComposeError, ContextLayer, PrefixChange};
+pub use concurrent::ConcurrentPrefixMapping;
+pub use coverage::CoverageReport;
+pub use curie_buf::CurieBuf;
+pub use dataset::DatasetPrefixes;
+pub use expanded_term::ExpandedTerm;
+pub use format::{DeclarationStyle, PrefixFormatter, PrefixOrder};
+#[cfg(feature = "dashmap")]
+pub use interner::SharedInterner;
+pub use iri::IriBuf;
+pub use iter::Mappings;
+pub use merge::{MergeConflict, MergeError, MergePolicy};
+pub use namespace::Namespace;
+pub use packed::{CurieArena, PackError, PackedCurie};
+pub use parse_error::{ParseError, ParseErrorKind};
+pub use prefix_block::PrefixBlock;
+pub use prefix_id::PrefixId;
+pub use registry::{PrefixSuggestion, Registry};
+pub use resolve::Unprefixed;
+pub use resolved::ResolvedCurie;
+pub use scoped::ScopedPrefixMapping;
+#[cfg(feature = "arc-swap")]
+pub use snapshot::SnapshotPrefixMapping;
+pub use sparql::{sparql_directives, SparqlDirectives};
+#[cfg(feature = "heapless")]
+pub use static_mapping::{StaticPrefixError, StaticPrefixMapping};
+pub use suggest::UnknownPrefix;
+pub use template::TemplateError;
+pub use vocabulary::{Term, Vocabulary};
+pub use write::WriteError;
+pub use xmlns::{xmlns_directives, XmlnsDirectives, XmlnsError};
+
+/// Items used by the macros exported from this crate. Not public API.
+#[doc(hidden)]
+pub mod __private {
+    use crate::Curie;
+
+    pub fn split_curie(curie_str: &str) -> Curie<'_> {
+        Curie::split(curie_str)
+    }
+}
+
 /// Errors that might occur when adding a prefix to a [`PrefixMapping`].
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum InvalidPrefixError {
@@ -136,21 +284,159 @@ pub enum InvalidPrefixError {
     ///
     /// The prefix `"_"` is reserved.
     ReservedPrefix,
+    /// The prefix is protected and can't be rebound or removed.
+    ///
+    /// See [`PrefixMapping::protect_prefix()`].
+    ProtectedPrefix,
+    /// The prefix is not an `NCName`, as a CURIE prefix must be.
+    ///
+    /// This is only checked in strict mode. See
+    /// [`PrefixMapping::set_strict_prefixes()`].
+    InvalidName,
+    /// The namespace is not an absolute IRI.
+    ///
+    /// This is only checked in strict mode. See
+    /// [`PrefixMapping::set_strict_namespaces()`].
+    InvalidNamespace,
+    /// The prefix is already bound to a different namespace.
+    ///
+    /// This is checked when rebinding is strict, see
+    /// [`PrefixMapping::set_strict_rebinding()`], and by
+    /// [`PrefixMapping::rename_prefix()`].
+    AlreadyBound,
 }
 
+impl fmt::Display for InvalidPrefixError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            InvalidPrefixError::ReservedPrefix => "the prefix is reserved",
+            InvalidPrefixError::ProtectedPrefix => "the prefix is protected",
+            InvalidPrefixError::InvalidName => "the prefix is not an NCName",
+            InvalidPrefixError::InvalidNamespace => "the namespace is not an absolute IRI",
+            InvalidPrefixError::AlreadyBound => {
+                "the prefix is already bound to a different namespace"
+            }
+        })
+    }
+}
+
+impl std::error::Error for InvalidPrefixError {}
+
 /// Errors that might occur during CURIE expansion.
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum ExpansionError {
     /// The prefix on the CURIE has no valid mapping.
+    ///
+    /// This is returned when the prefix isn't known by name, such as
+    /// for a [`PrefixId`] whose prefix has been removed, or when the
+    /// error can't allocate, as with a `StaticPrefixMapping`.
+    /// Otherwise, [`ExpansionError::UnknownPrefix`] is returned.
     Invalid,
+    /// The prefix on the CURIE is not bound in the mapping.
+    ///
+    /// The unknown prefix is included, so it can be reported. See also
+    /// [`PrefixMapping::unknown_prefix()`] for suggesting a prefix that
+    /// may have been meant.
+    UnknownPrefix(String),
     /// The CURIE uses a default prefix, but one has not
     /// been set.
     MissingDefault,
+    /// The reference contains a fragment, but so does the namespace
+    /// that it would be appended to.
+    DuplicateFragment,
+    /// The reference contains a character that can never appear in an
+    /// IRI.
+    ///
+    /// This is only checked in strict mode. See
+    /// [`PrefixMapping::set_strict_references()`].
+    InvalidCharacter {
+        /// The byte offset of the character in the reference.
+        offset: usize,
+        /// The character.
+        character: char,
+    },
+    /// The expanded IRI couldn't be written out, for example because
+    /// a fixed-size buffer is full.
+    WriteFailed,
+    /// The CURIE has no prefix and is resolved against the base IRI,
+    /// but no absolute base IRI has been set.
+    ///
+    /// See [`PrefixMapping::set_unprefixed()`].
+    MissingBase,
+    /// The reference doesn't match the pattern attached to its prefix.
+    ///
+    /// This is only checked by `PrefixMapping::validate_curie()`, which
+    /// requires the `regex` feature.
+    PatternMismatch {
+        /// The prefix of the CURIE.
+        prefix: String,
+        /// The pattern that the reference doesn't match.
+        pattern: String,
+    },
 }
 
+impl fmt::Display for ExpansionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExpansionError::Invalid => f.write_str("the prefix has no mapping"),
+            ExpansionError::UnknownPrefix(prefix) => {
+                write!(f, "the prefix {prefix:?} has no mapping")
+            }
+            ExpansionError::MissingDefault => f.write_str("no default prefix has been set"),
+            ExpansionError::DuplicateFragment => {
+                f.write_str("the reference would add a second fragment to the namespace")
+            }
+            ExpansionError::InvalidCharacter { offset, character } => write!(
+                f,
+                "the reference contains {character:?} at offset {offset}, \
+                 which can't appear in an IRI"
+            ),
+            ExpansionError::WriteFailed => f.write_str("the expanded IRI couldn't be written"),
+            ExpansionError::MissingBase => f.write_str("no absolute base IRI has been set"),
+            ExpansionError::PatternMismatch { prefix, pattern } => write!(
+                f,
+                "the reference doesn't match the pattern {pattern:?} of the prefix {prefix:?}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ExpansionError {}
+
+/// Errors that might occur when shrinking an IRI to a CURIE.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ShrinkError {
+    /// The IRI is empty.
+    EmptyIri,
+    /// No namespace or URI template matches the IRI.
+    NoMatch,
+    /// The CURIE that the IRI shrinks to doesn't expand back to the
+    /// IRI.
+    ///
+    /// This is only checked by [`PrefixMapping::shrink_iri_verified()`].
+    RoundTripMismatch,
+}
+
+impl fmt::Display for ShrinkError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            ShrinkError::EmptyIri => "the IRI is empty",
+            ShrinkError::NoMatch => "no namespace matches the IRI",
+            ShrinkError::RoundTripMismatch => "the CURIE doesn't expand back to the IRI",
+        })
+    }
+}
+
+impl std::error::Error for ShrinkError {}
+
 /// Maps prefixes to base URIs and allows for the expansion of
 /// CURIEs (Compact URIs).
 ///
+/// Prefixes are kept in the order they were added, not in hash order,
+/// so [`PrefixMapping::mappings()`], the output of the exporters and
+/// the results of [`PrefixMapping::shrink_iri()`] are the same on
+/// every run and platform.
+///
 /// # Examples
 ///
 /// ```
@@ -159,10 +445,81 @@ pub enum ExpansionError {
 /// // Create using the `Default` trait:
 /// let mut mapping = PrefixMapping::default();
 /// ```
-#[derive(Debug, Default, PartialEq)]
+#[derive(Clone, Default)]
 pub struct PrefixMapping {
     default: Option<String>,
+    /// The base IRI, which relative IRIs are resolved against.
+    base: Option<String>,
     mapping: indexmap::IndexMap<String, String>,
+    /// Every prefix that has ever been added, indexed by [`PrefixId`].
+    ///
+    /// Entries are never removed so that ids remain stable.
+    prefix_ids: indexmap::IndexSet<String>,
+    /// Prefixes that can't be rebound or removed without being forced.
+    protected: indexmap::IndexSet<String>,
+    /// Prefixes bound to URI templates rather than namespaces.
+    templates: indexmap::IndexMap<String, template::UriTemplate>,
+    /// Whole terms and the IRIs they map to.
+    terms: indexmap::IndexMap<String, String>,
+    /// Whether references are checked for characters that can't
+    /// appear in an IRI.
+    strict_references: bool,
+    /// Whether prefixes must be `NCName`s.
+    strict_prefixes: bool,
+    /// Whether namespaces must be absolute IRIs.
+    strict_namespaces: bool,
+    /// Whether bound prefixes can only be rebound by force.
+    strict_rebinding: bool,
+    /// How references without a prefix are expanded.
+    unprefixed: Unprefixed,
+    /// The mapping that namespaces are looked up in when they aren't
+    /// bound in this one.
+    parent: Option<std::sync::Arc<PrefixMapping>>,
+    /// Patterns that the references of each prefix must match.
+    #[cfg(feature = "regex")]
+    patterns: indexmap::IndexMap<String, regex::Regex>,
+}
+
+impl PartialEq for PrefixMapping {
+    fn eq(&self, other: &Self) -> bool {
+        // Prefix ids depend on the history of the mapping rather
+        // than on what it currently maps, so they are not compared.
+        // Neither is protection, which restricts future changes,
+        // nor strictness or reference patterns, which only affect
+        // what is accepted, nor the handling of unprefixed references,
+        // which is a choice of the caller rather than part of the
+        // mapping's contents.
+        self.default == other.default
+            && self.base == other.base
+            && self.mapping == other.mapping
+            && self.templates == other.templates
+            && self.terms == other.terms
+            && self.parent == other.parent
+    }
+}
+
+/// Shows the default prefix, the base IRI, the parent and the
+/// prefixes, URI templates and terms sorted by name, so that the
+/// output doesn't depend on the order they were added in. Settings
+/// are left out, as they are by [`PartialEq`].
+impl fmt::Debug for PrefixMapping {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mappings: std::collections::BTreeMap<&str, &str> = self.mappings().collect();
+        let terms: std::collections::BTreeMap<&str, &str> = self.terms().collect();
+        let templates: std::collections::BTreeMap<&str, String> = self
+            .templates
+            .keys()
+            .filter_map(|prefix| Some((prefix.as_str(), self.prefix_template(prefix)?)))
+            .collect();
+        f.debug_struct("PrefixMapping")
+            .field("default", &self.default)
+            .field("base", &self.base)
+            .field("mappings", &mappings)
+            .field("templates", &templates)
+            .field("terms", &terms)
+            .field("parent", &self.parent)
+            .finish_non_exhaustive()
+    }
 }
 
 impl PrefixMapping {
@@ -192,10 +549,78 @@ impl PrefixMapping {
     /// # See also
     ///
     /// * [`PrefixMapping::add_prefix()`]
+    /// * [`PrefixMapping::default_namespace()`]
+    /// * [`PrefixMapping::unset_default()`]
     pub fn set_default(&mut self, default: &str) {
         self.default = Some(String::from(default));
     }
 
+    /// The namespace of the default prefix, if one has been set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::PrefixMapping;
+    ///
+    /// let mut mapping = PrefixMapping::default();
+    /// assert_eq!(mapping.default_namespace(), None);
+    ///
+    /// mapping.set_default("http://example.com/");
+    /// assert_eq!(mapping.default_namespace(), Some("http://example.com/"));
+    ///
+    /// mapping.unset_default();
+    /// assert_eq!(mapping.default_namespace(), None);
+    /// ```
+    #[must_use]
+    pub fn default_namespace(&self) -> Option<&str> {
+        self.default.as_deref()
+    }
+
+    /// Remove the default prefix.
+    ///
+    /// CURIEs without a prefix can't be expanded afterwards, unless
+    /// the mapping has a [parent](PrefixMapping::with_parent()) with a
+    /// default prefix.
+    pub fn unset_default(&mut self) {
+        self.default = None;
+    }
+
+    /// Set a default prefix, checking that it is an absolute IRI.
+    ///
+    /// Unlike [`PrefixMapping::set_default()`], this rejects values
+    /// that can't be the start of an IRI, such as `"not a uri"`,
+    /// whether or not the mapping is in strict mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidPrefixError::InvalidNamespace`] if `default`
+    /// is not an absolute IRI. The default is left unchanged.
+    pub fn try_set_default(&mut self, default: &str) -> Result<(), InvalidPrefixError> {
+        if !syntax::is_valid_absolute_iri(default) {
+            return Err(InvalidPrefixError::InvalidNamespace);
+        }
+        self.set_default(default);
+        Ok(())
+    }
+
+    /// Set the base IRI.
+    ///
+    /// Unlike the default prefix, which is prepended to the reference
+    /// of a CURIE without a prefix, the base IRI is what relative IRIs,
+    /// such as those in a document's `@base` or Turtle's `<#x>`, are
+    /// relative to. It is not used when expanding CURIEs.
+    pub fn set_base(&mut self, base: &str) {
+        self.base = Some(String::from(base));
+    }
+
+    /// The base IRI, if one has been set.
+    ///
+    /// See [`PrefixMapping::set_base()`].
+    #[must_use]
+    pub fn base(&self) -> Option<&str> {
+        self.base.as_deref()
+    }
+
     /// Add a prefix to the mapping.
     ///
     /// This allows this prefix to be resolved when a CURIE is expanded.
@@ -203,32 +628,67 @@ impl PrefixMapping {
     /// # Errors
     ///
     /// Returns [`InvalidPrefixError`] when the `prefix` is invalid. Typically, this is
-    /// when `prefix` is `_`, which is a reserved prefix.
+    /// when `prefix` is `_`, which is a reserved prefix. Rebinding a protected
+    /// prefix to a different value is also an error.
     ///
     /// # See also
     ///
     /// * [`PrefixMapping::remove_prefix()`]
     /// * [`PrefixMapping::set_default()`]
+    /// * [`PrefixMapping::protect_prefix()`]
     pub fn add_prefix(&mut self, prefix: &str, value: &str) -> Result<(), InvalidPrefixError> {
-        if prefix == "_" {
-            Err(InvalidPrefixError::ReservedPrefix)
+        self.check_rebinding(prefix, self.get_expansion(prefix), value)?;
+        self.force_add_prefix(prefix, value)
+    }
+
+    /// Check that `prefix`, bound to the namespace `bound`, can be
+    /// rebound to `value` without being forced.
+    pub(crate) fn check_rebinding(
+        &self,
+        prefix: &str,
+        bound: Option<&str>,
+        value: &str,
+    ) -> Result<(), InvalidPrefixError> {
+        let rebinding = match bound {
+            Some(namespace) => namespace != value,
+            None => self.templates.contains_key(prefix),
+        };
+        if self.protected.contains(prefix) && rebinding {
+            Err(InvalidPrefixError::ProtectedPrefix)
+        } else if self.strict_rebinding && rebinding {
+            Err(InvalidPrefixError::AlreadyBound)
         } else {
-            self.mapping
-                .insert(String::from(prefix), String::from(value));
             Ok(())
         }
     }
 
-    /// Remove a prefix from the mapping.
+    /// Remove a prefix from the mapping, returning the namespace it was
+    /// bound to.
     ///
     /// Future calls to [`PrefixMapping::expand_curie_string()`] or [`PrefixMapping::expand_curie()`]
-    /// that use this `prefix` will result in a [`ExpansionError::Invalid`] error.
+    /// that use this `prefix` will result in an [`ExpansionError::UnknownPrefix`] error,
+    /// unless the [parent](PrefixMapping::with_parent()) binds it, in which case the
+    /// parent's namespace is used.
+    ///
+    /// Returns `None` if the prefix wasn't bound, or was bound to a URI
+    /// template, which is removed too.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidPrefixError::ProtectedPrefix`] when the `prefix` is
+    /// protected.
     ///
     /// # See also
     ///
     /// * [`PrefixMapping::add_prefix()`]
-    pub fn remove_prefix(&mut self, prefix: &str) {
-        self.mapping.shift_remove(prefix);
+    /// * [`PrefixMapping::force_remove_prefix()`]
+    pub fn remove_prefix(&mut self, prefix: &str) -> Result<Option<String>, InvalidPrefixError> {
+        if self.protected.contains(prefix) {
+            Err(InvalidPrefixError::ProtectedPrefix)
+        } else {
+            self.templates.shift_remove(prefix);
+            Ok(self.mapping.shift_remove(prefix))
+        }
     }
 
     /// Expand a CURIE, returning a complete IRI.
@@ -241,16 +701,11 @@ impl PrefixMapping {
     ///
     /// * [`PrefixMapping::expand_curie()`]
     pub fn expand_curie_string(&self, curie_str: &str) -> Result<String, ExpansionError> {
-        if let Some(separator_idx) = curie_str.chars().position(|c| c == ':') {
-            // If we have a separator, try to expand.
-            let prefix = Some(&curie_str[..separator_idx]);
-            let reference = &curie_str[separator_idx + 1..];
-            let curie = Curie::new(prefix, reference);
-            self.expand_curie(&curie)
-        } else {
-            let curie = Curie::new(None, curie_str);
-            self.expand_curie(&curie)
+        if let Some(iri) = self.terms.get(curie_str) {
+            telemetry::record_expansion(Ok(iri.len()));
+            return Ok(iri.clone());
         }
+        self.expand_curie(&Curie::split(curie_str))
     }
 
     /// Expand a parsed [`Curie`], returning a complete IRI.
@@ -266,19 +721,285 @@ impl PrefixMapping {
         self.expand_exploded_curie(curie.prefix, curie.reference)
     }
 
+    /// Expand a [safe CURIE], a CURIE in square brackets, returning a
+    /// complete IRI.
+    ///
+    /// Safe CURIEs are used where either a CURIE or an IRI may appear,
+    /// so that a CURIE can't be mistaken for an IRI. A CURIE without
+    /// the brackets is expanded as by
+    /// [`PrefixMapping::expand_curie_string()`].
+    ///
+    /// # Errors
+    ///
+    /// This will return [`ExpansionError`] if the expansion fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::{Curie, PrefixMapping};
+    ///
+    /// let mut mapping = PrefixMapping::default();
+    /// mapping.add_prefix("foaf", "http://xmlns.com/foaf/0.1/").unwrap();
+    ///
+    /// assert_eq!(mapping.expand_safe_curie("[foaf:Agent]"),
+    ///            Ok(String::from("http://xmlns.com/foaf/0.1/Agent")));
+    /// assert_eq!(mapping.expand_safe_curie("foaf:Agent"),
+    ///            Ok(String::from("http://xmlns.com/foaf/0.1/Agent")));
+    ///
+    /// assert_eq!(Curie::new(Some("foaf"), "Agent").to_safe_string(), "[foaf:Agent]");
+    /// ```
+    ///
+    /// [safe CURIE]: https://www.w3.org/TR/curie/#s_syntax
+    pub fn expand_safe_curie(&self, safe_curie: &str) -> Result<String, ExpansionError> {
+        let curie_str = safe_curie
+            .strip_prefix('[')
+            .and_then(|inner| inner.strip_suffix(']'))
+            .unwrap_or(safe_curie);
+        self.expand_curie_string(curie_str)
+    }
+
     fn expand_exploded_curie(
         &self,
         prefix: Option<&str>,
         reference: &str,
     ) -> Result<String, ExpansionError> {
+        let result = self.expand_unrecorded(prefix, reference);
+        telemetry::record_expansion(result.as_ref().map(String::len));
+        result
+    }
+
+    fn expand_unrecorded(
+        &self,
+        prefix: Option<&str>,
+        reference: &str,
+    ) -> Result<String, ExpansionError> {
+        self.resolve_exploded(prefix, reference)
+            .map(|resolved| resolved.to_iri())
+    }
+
+    /// Check that `reference` can be appended to `namespace`.
+    pub(crate) fn check_reference(
+        &self,
+        namespace: &str,
+        reference: &str,
+    ) -> Result<(), ExpansionError> {
+        self.check_reference_chars(reference)?;
+        syntax::check_append(namespace, reference)
+    }
+
+    pub(crate) fn check_reference_chars(&self, reference: &str) -> Result<(), ExpansionError> {
+        match syntax::find_invalid_iri_char(reference) {
+            Some((offset, character)) if self.strict_references => {
+                Err(ExpansionError::InvalidCharacter { offset, character })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Turn on or off all of the checks that the [CURIE syntax] calls
+    /// for.
+    ///
+    /// This sets [`PrefixMapping::set_strict_prefixes()`],
+    /// [`PrefixMapping::set_strict_namespaces()`] and
+    /// [`PrefixMapping::set_strict_references()`] together. Safe CURIEs
+    /// are expanded with [`PrefixMapping::expand_safe_curie()`], and
+    /// the syntax of a CURIE can be checked without a mapping with
+    /// [`is_valid_curie()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::{ExpansionError, InvalidPrefixError, PrefixMapping};
+    ///
+    /// let mut mapping = PrefixMapping::default();
+    /// mapping.set_strict(true);
+    ///
+    /// assert_eq!(mapping.add_prefix("my ex", "http://example.com/"),
+    ///            Err(InvalidPrefixError::InvalidName));
+    /// assert_eq!(mapping.add_prefix("ex", "example.com"),
+    ///            Err(InvalidPrefixError::InvalidNamespace));
+    /// mapping.add_prefix("ex", "http://example.com/").unwrap();
+    /// assert!(matches!(mapping.expand_safe_curie("[ex:a b]"),
+    ///                  Err(ExpansionError::InvalidCharacter { .. })));
+    /// ```
+    ///
+    /// [CURIE syntax]: https://www.w3.org/TR/curie/
+    pub fn set_strict(&mut self, strict: bool) {
+        self.set_strict_prefixes(strict);
+        self.set_strict_namespaces(strict);
+        self.set_strict_references(strict);
+    }
+
+    /// Reject references containing characters that can never appear
+    /// in an IRI when expanding.
+    ///
+    /// These are spaces and other control characters and the
+    /// characters `<>"{}|\^` and `` ` ``, which must be percent-encoded
+    /// in an IRI. This is off by default, in which case references are
+    /// used as they are.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::{ExpansionError, PrefixMapping};
+    ///
+    /// let mut mapping = PrefixMapping::default();
+    /// mapping.add_prefix("ex", "http://example.com/").unwrap();
+    /// mapping.set_strict_references(true);
+    ///
+    /// assert_eq!(mapping.expand_curie_string("ex:two words"),
+    ///            Err(ExpansionError::InvalidCharacter { offset: 3, character: ' ' }));
+    /// ```
+    pub fn set_strict_references(&mut self, strict: bool) {
+        self.strict_references = strict;
+    }
+
+    /// Whether references are checked when expanding.
+    ///
+    /// See [`PrefixMapping::set_strict_references()`].
+    #[must_use]
+    pub fn strict_references(&self) -> bool {
+        self.strict_references
+    }
+
+    /// Reject prefixes that aren't [`NCName`s] when adding them.
+    ///
+    /// The CURIE syntax requires a prefix to be an `NCName`, so it can't
+    /// contain spaces or colons or start with a digit. The empty prefix
+    /// used by Turtle and SPARQL is still allowed. This is off by
+    /// default, in which case any prefix but `_` is accepted. Prefixes
+    /// that are already in the mapping are not checked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::{InvalidPrefixError, PrefixMapping};
+    ///
+    /// let mut mapping = PrefixMapping::default();
+    /// mapping.set_strict_prefixes(true);
+    /// assert_eq!(mapping.add_prefix("my prefix", "http://example.com/"),
+    ///            Err(InvalidPrefixError::InvalidName));
+    /// assert_eq!(mapping.add_prefix("ex", "http://example.com/"), Ok(()));
+    /// ```
+    ///
+    /// [`NCName`s]: https://www.w3.org/TR/xml-names/#NT-NCName
+    pub fn set_strict_prefixes(&mut self, strict: bool) {
+        self.strict_prefixes = strict;
+    }
+
+    /// Whether prefixes are checked when adding them.
+    ///
+    /// See [`PrefixMapping::set_strict_prefixes()`].
+    #[must_use]
+    pub fn strict_prefixes(&self) -> bool {
+        self.strict_prefixes
+    }
+
+    /// Reject namespaces that aren't absolute IRIs when adding
+    /// prefixes.
+    ///
+    /// A namespace must start with a scheme, such as `http:`, and can't
+    /// contain spaces or other characters that can never appear in an
+    /// IRI. This is off by default, in which case namespaces are used
+    /// as they are. The parts of a URI template on either side of its
+    /// placeholder are checked in the same way. Prefixes that are
+    /// already in the mapping are not checked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::{InvalidPrefixError, PrefixMapping};
+    ///
+    /// let mut mapping = PrefixMapping::default();
+    /// mapping.set_strict_namespaces(true);
+    /// assert_eq!(mapping.add_prefix("ex", "not a uri"),
+    ///            Err(InvalidPrefixError::InvalidNamespace));
+    /// assert_eq!(mapping.add_prefix("ex", "http://example.com/"), Ok(()));
+    /// ```
+    pub fn set_strict_namespaces(&mut self, strict: bool) {
+        self.strict_namespaces = strict;
+    }
+
+    /// Whether namespaces are checked when adding prefixes.
+    ///
+    /// See [`PrefixMapping::set_strict_namespaces()`].
+    #[must_use]
+    pub fn strict_namespaces(&self) -> bool {
+        self.strict_namespaces
+    }
+
+    /// Reject adding a prefix that is already bound to something else.
+    ///
+    /// This is off by default, in which case adding a prefix that is
+    /// already bound replaces its namespace, which can hide two
+    /// vocabularies claiming the same prefix. When it is on,
+    /// [`PrefixMapping::add_prefix()`] and
+    /// [`PrefixMapping::add_prefix_template()`] return
+    /// [`InvalidPrefixError::AlreadyBound`] instead, and the importers
+    /// report declarations that rebind a prefix. Adding a prefix again
+    /// with the same value is allowed, and
+    /// [`PrefixMapping::force_add_prefix()`] rebinds regardless.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::{InvalidPrefixError, PrefixMapping};
+    ///
+    /// let mut mapping = PrefixMapping::default();
+    /// mapping.set_strict_rebinding(true);
+    /// mapping.add_prefix("dc", "http://purl.org/dc/elements/1.1/").unwrap();
+    /// assert_eq!(mapping.add_prefix("dc", "http://purl.org/dc/elements/1.1/"), Ok(()));
+    /// assert_eq!(mapping.add_prefix("dc", "http://purl.org/dc/terms/"),
+    ///            Err(InvalidPrefixError::AlreadyBound));
+    /// assert_eq!(mapping.get_expansion("dc"), Some("http://purl.org/dc/elements/1.1/"));
+    /// ```
+    pub fn set_strict_rebinding(&mut self, strict: bool) {
+        self.strict_rebinding = strict;
+    }
+
+    /// Whether bound prefixes are protected from being rebound.
+    ///
+    /// See [`PrefixMapping::set_strict_rebinding()`].
+    #[must_use]
+    pub fn strict_rebinding(&self) -> bool {
+        self.strict_rebinding
+    }
+
+    /// Check that `namespace` can be bound to a prefix.
+    pub(crate) fn check_namespace(&self, namespace: &str) -> Result<(), InvalidPrefixError> {
+        if self.strict_namespaces && !syntax::is_valid_absolute_iri(namespace) {
+            Err(InvalidPrefixError::InvalidNamespace)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Check that `prefix` can be bound.
+    pub(crate) fn check_prefix(&self, prefix: &str) -> Result<(), InvalidPrefixError> {
+        if prefix == "_" {
+            Err(InvalidPrefixError::ReservedPrefix)
+        } else if self.strict_prefixes && !prefix.is_empty() && !syntax::is_ncname(prefix) {
+            Err(InvalidPrefixError::InvalidName)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Look up the namespace that a prefix, or the default when there
+    /// is no prefix, is mapped to.
+    pub(crate) fn namespace_for(&self, prefix: Option<&str>) -> Result<&str, ExpansionError> {
         if let Some(prefix) = prefix {
             if let Some(mapped_prefix) = self.mapping.get(prefix) {
-                Ok((*mapped_prefix).clone() + reference)
+                Ok(mapped_prefix)
+            } else if let Some(ref parent) = self.parent {
+                parent.namespace_for(Some(prefix))
             } else {
-                Err(ExpansionError::Invalid)
+                Err(ExpansionError::UnknownPrefix(String::from(prefix)))
             }
         } else if let Some(ref default) = self.default {
-            Ok((default).clone() + reference)
+            Ok(default)
+        } else if let Some(ref parent) = self.parent {
+            parent.namespace_for(None)
         } else {
             Err(ExpansionError::MissingDefault)
         }
@@ -287,8 +1008,7 @@ impl PrefixMapping {
     /// Shrink an IRI, returning a [`Curie`].
     ///
     /// If several base IRIs match the expanded IRI passed as argument, the
-    /// one that was inserted first is used, even though another base IRI is
-    /// a better match:
+    /// longest, and so most specific, is used:
     ///
     /// ```rust
     /// use curie::{PrefixMapping, Curie};
@@ -298,27 +1018,74 @@ impl PrefixMapping {
     /// mapping.add_prefix("egdoc", "http://example.com/document/").unwrap();
     ///
     /// assert_eq!(mapping.shrink_iri("http://example.com/document/thing"),
-    ///            Ok(Curie::new(Some("eg"), "document/thing")));
+    ///            Ok(Curie::new(Some("egdoc"), "thing")));
+    /// assert_eq!(mapping.shrink_iri("http://example.com/thing"),
+    ///            Ok(Curie::new(Some("eg"), "thing")));
     /// ```
     ///
+    /// The default namespace takes part in this like any other. When
+    /// the same namespace is bound more than once, the default is used
+    /// in preference to a prefix, and otherwise the prefix that was
+    /// added first.
+    ///
+    /// An IRI that a [term](PrefixMapping::add_term()) maps to is
+    /// shrunk to the term. Otherwise, prefixes bound to URI templates
+    /// take part in choosing the shortest reference too, and are used in
+    /// preference to a namespace that gives a reference as short.
+    ///
     /// # Errors
     ///
-    /// An error is returned if there is no valid mapping (default or otherwise)
-    /// that would allow the IRI to be shortened.
-    pub fn shrink_iri<'a>(&'a self, iri: &'a str) -> Result<Curie<'a>, &'static str> {
-        if let Some(ref def) = self.default {
-            if iri.starts_with(def) {
-                return Ok(Curie::new(None, iri.trim_start_matches(def)));
-            }
+    /// Returns [`ShrinkError::EmptyIri`] if `iri` is empty, and
+    /// [`ShrinkError::NoMatch`] if there is no valid mapping (default or
+    /// otherwise) that would allow the IRI to be shortened.
+    pub fn shrink_iri<'a>(&'a self, iri: &'a str) -> Result<Curie<'a>, ShrinkError> {
+        let result = self.shrink_unrecorded(iri);
+        telemetry::record_shrink(self, iri, result.is_ok());
+        result
+    }
+
+    fn shrink_unrecorded<'a>(&'a self, iri: &'a str) -> Result<Curie<'a>, ShrinkError> {
+        if iri.is_empty() {
+            return Err(ShrinkError::EmptyIri);
+        }
+        if let Some(term) = self.term_for_iri(iri) {
+            return Ok(Curie::new(None, term));
         }
 
-        for mp in &self.mapping {
-            if iri.starts_with(mp.1) {
-                return Ok(Curie::new(Some(mp.0), iri.trim_start_matches(mp.1)));
+        // The longest namespace gives the shortest reference. Keep the
+        // first candidate on ties, so URI templates win, then the default
+        // namespace and then the prefix that was added first.
+        // The bindings of the parents come after the mapping's own, and
+        // are skipped when they are shadowed.
+        let mut best = self.shrink_with_templates(iri);
+        let mut layer = Some(self);
+        while let Some(mapping) = layer {
+            let candidates = mapping.default.iter().map(|def| (None, def)).chain(
+                mapping
+                    .mapping
+                    .iter()
+                    .map(|(prefix, ns)| (Some(prefix), ns)),
+            );
+            for (prefix, namespace) in candidates {
+                let prefix = prefix.map(String::as_str);
+                if !std::ptr::eq(mapping, self)
+                    && self.namespace_for(prefix) != Ok(namespace.as_str())
+                {
+                    continue;
+                }
+                if let Some(reference) = syntax::strip_namespace(iri, namespace) {
+                    if best
+                        .as_ref()
+                        .is_none_or(|best| reference.len() < best.reference.len())
+                    {
+                        best = Some(Curie::new(prefix, reference));
+                    }
+                }
             }
+            layer = mapping.parent.as_deref();
         }
 
-        Err("Unable to shorten")
+        best.ok_or(ShrinkError::NoMatch)
     }
 
     /// Return an iterator over the prefix mappings.
@@ -326,8 +1093,94 @@ impl PrefixMapping {
     /// The iterator yields IRI mappings in the same order they were inserted.
     /// This is useful when testing code that uses this crate.
     #[must_use]
-    pub fn mappings(&self) -> indexmap::map::Iter<String, String> {
-        self.mapping.iter()
+    pub fn mappings(&self) -> Mappings<'_> {
+        Mappings::new(&self.mapping)
+    }
+
+    /// The number of prefixes bound to namespaces.
+    ///
+    /// The default prefix, prefixes bound to URI templates and the
+    /// prefixes of a [parent](PrefixMapping::with_parent()) are not
+    /// counted.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.mapping.len()
+    }
+
+    /// Whether no prefix is bound to a namespace.
+    ///
+    /// This is `true` even if there is a default prefix. See
+    /// [`PrefixMapping::len()`].
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.mapping.is_empty()
+    }
+
+    /// Whether `prefix` is bound, to a namespace or a URI template.
+    ///
+    /// Only the mapping's own prefixes are checked, not those of a
+    /// [parent](PrefixMapping::with_parent()), even though a prefix
+    /// bound by the parent still expands.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::PrefixMapping;
+    ///
+    /// let mut mapping = PrefixMapping::default();
+    /// assert!(mapping.is_empty());
+    ///
+    /// mapping.add_prefix("foaf", "http://xmlns.com/foaf/0.1/").unwrap();
+    /// assert_eq!(mapping.len(), 1);
+    /// assert!(mapping.contains_prefix("foaf"));
+    /// assert!(!mapping.contains_prefix("dc"));
+    /// assert_eq!(mapping.get_expansion("foaf"), Some("http://xmlns.com/foaf/0.1/"));
+    /// ```
+    #[must_use]
+    pub fn contains_prefix(&self, prefix: &str) -> bool {
+        self.mapping.contains_key(prefix) || self.templates.contains_key(prefix)
+    }
+
+    /// The namespace that `prefix` is bound to, if any.
+    ///
+    /// Prefixes bound to URI templates have no namespace; see
+    /// [`PrefixMapping::prefix_template()`]. As with
+    /// [`PrefixMapping::contains_prefix()`], the prefixes of a
+    /// [parent](PrefixMapping::with_parent()) are not looked up.
+    #[must_use]
+    pub fn get_expansion(&self, prefix: &str) -> Option<&str> {
+        self.mapping.get(prefix).map(String::as_str)
+    }
+
+    /// Keep only the prefixes for which `keep` returns `true`, given
+    /// the prefix and its namespace.
+    ///
+    /// Protected prefixes are always kept, as with
+    /// [`PrefixMapping::remove_prefix()`]. Prefixes bound to URI
+    /// templates and the default prefix are not affected. The order of
+    /// the prefixes that are kept is preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::PrefixMapping;
+    ///
+    /// let mut mapping = PrefixMapping::default();
+    /// mapping.add_prefix("foaf", "http://xmlns.com/foaf/0.1/").unwrap();
+    /// mapping.add_prefix("test1", "http://test.example/1/").unwrap();
+    /// mapping.add_prefix("test2", "http://test.example/2/").unwrap();
+    ///
+    /// mapping.retain(|_, namespace| !namespace.starts_with("http://test.example/"));
+    /// assert_eq!(mapping.mappings().collect::<Vec<_>>(),
+    ///            [("foaf", "http://xmlns.com/foaf/0.1/")]);
+    /// ```
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&str, &str) -> bool,
+    {
+        let protected = &self.protected;
+        self.mapping
+            .retain(|prefix, namespace| protected.contains(prefix) || keep(prefix, namespace));
     }
 }
 
@@ -376,7 +1229,24 @@ impl PrefixMapping {
 /// let curie = Curie::new(Some("foaf"), "Agent");
 /// assert_eq!("foaf:Agent", format!("{}", curie));
 /// ```
-#[derive(Debug, Eq, PartialEq)]
+///
+/// ## Collections:
+///
+/// `Curie` is `Copy`, `Hash` and `Ord`, so it can be used in sets and
+/// maps. CURIEs are ordered by prefix, with no prefix first, and then
+/// by reference. For an order that compares numbers within them, see
+/// [`sort::compare()`].
+///
+/// ```
+/// # use curie::Curie;
+/// use std::collections::BTreeSet;
+///
+/// let set: BTreeSet<_> = [Curie::new(Some("foaf"), "name"), Curie::new(None, "name")]
+///     .into_iter()
+///     .collect();
+/// assert_eq!(set.first(), Some(&Curie::new(None, "name")));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Curie<'c> {
     prefix: Option<&'c str>,
     reference: &'c str,
@@ -388,6 +1258,61 @@ impl<'c> Curie<'c> {
     pub fn new(prefix: Option<&'c str>, reference: &'c str) -> Self {
         Curie { prefix, reference }
     }
+
+    /// The prefix, or `None` if this uses the default namespace.
+    ///
+    /// ```
+    /// # use curie::Curie;
+    /// assert_eq!(Curie::new(Some("foaf"), "Person").prefix(), Some("foaf"));
+    /// assert_eq!(Curie::new(None, "Person").prefix(), None);
+    /// ```
+    #[must_use]
+    pub fn prefix(&self) -> Option<&'c str> {
+        self.prefix
+    }
+
+    /// The reference, which follows the prefix.
+    ///
+    /// ```
+    /// # use curie::Curie;
+    /// assert_eq!(Curie::new(Some("foaf"), "Person").reference(), "Person");
+    /// ```
+    #[must_use]
+    pub fn reference(&self) -> &'c str {
+        self.reference
+    }
+
+    /// Split this into its prefix and reference.
+    ///
+    /// ```
+    /// # use curie::Curie;
+    /// let (prefix, reference) = Curie::new(Some("foaf"), "Person").into_parts();
+    /// assert_eq!((prefix, reference), (Some("foaf"), "Person"));
+    /// ```
+    #[must_use]
+    pub fn into_parts(self) -> (Option<&'c str>, &'c str) {
+        (self.prefix, self.reference)
+    }
+
+    /// Format this as a [safe CURIE], wrapped in square brackets.
+    ///
+    /// See [`PrefixMapping::expand_safe_curie()`].
+    ///
+    /// [safe CURIE]: https://www.w3.org/TR/curie/#s_syntax
+    #[must_use]
+    pub fn to_safe_string(&self) -> String {
+        format!("[{self}]")
+    }
+
+    /// Split a string into a `Curie` at the first separator.
+    ///
+    /// If there is no separator, the whole string is the reference.
+    pub(crate) fn split(curie_str: &'c str) -> Self {
+        match curie_str.split_once(':') {
+            Some((prefix, reference)) => Curie::new(Some(prefix), reference),
+            None => Curie::new(None, curie_str),
+        }
+    }
 }
 
 impl<'c> From<&'c Curie<'c>> for String {
@@ -438,12 +1363,63 @@ mod tests {
         );
 
         // Keys can be removed.
-        pm.remove_prefix("foaf");
+        assert_eq!(pm.remove_prefix("foaf"), Ok(Some(String::from(FOAF_VOCAB))));
+        assert_eq!(pm.remove_prefix("foaf"), Ok(None));
 
         // The "foaf" key should not be found.
         assert_eq!(pm.mapping.get("foaf"), None);
     }
 
+    #[test]
+    fn accessors() {
+        let mut pm = PrefixMapping::default();
+        pm.set_default(FOAF_VOCAB);
+        assert!(pm.is_empty());
+
+        pm.add_prefix("foaf", FOAF_VOCAB).unwrap();
+        pm.add_prefix_template("doi", "https://doi.org/{id}")
+            .unwrap();
+        assert_eq!(pm.len(), 1);
+        assert!(!pm.is_empty());
+        assert!(pm.contains_prefix("doi"));
+        assert_eq!(pm.get_expansion("doi"), None);
+        assert_eq!(pm.get_expansion("foaf"), Some(FOAF_VOCAB));
+        assert_eq!(pm.get_expansion("rdfs"), None);
+    }
+
+    #[test]
+    fn debug_is_sorted() {
+        let mut mapping = PrefixMapping::default();
+        mapping.set_default("http://example.com/");
+        mapping.add_prefix("b", "http://example.com/b/").unwrap();
+        mapping.add_prefix("a", "http://example.com/a/").unwrap();
+        assert_eq!(
+            format!("{mapping:?}"),
+            "PrefixMapping { default: Some(\"http://example.com/\"), base: None, \
+             mappings: {\"a\": \"http://example.com/a/\", \"b\": \"http://example.com/b/\"}, \
+             templates: {}, terms: {}, parent: None, .. }"
+        );
+    }
+
+    #[test]
+    fn retain_keeps_protected() {
+        let mut pm = PrefixMapping::default();
+        pm.add_prefix("a", "http://example.com/a/").unwrap();
+        pm.add_prefix("b", "http://example.com/b/").unwrap();
+        pm.add_prefix("c", "http://example.com/c/").unwrap();
+        pm.protect_prefix("a");
+        let mut seen = Vec::new();
+        pm.retain(|prefix, _| {
+            seen.push(String::from(prefix));
+            prefix == "c"
+        });
+        assert_eq!(seen, ["b", "c"]);
+        assert_eq!(
+            pm.mappings().map(|(p, _)| p).collect::<Vec<_>>(),
+            ["a", "c"]
+        );
+    }
+
     #[test]
     fn display_curie() {
         let curie = Curie::new(Some("foaf"), "Agent");
@@ -462,6 +1438,149 @@ mod tests {
         assert_eq!("foaf:Agent", String::from(&curie));
     }
 
+    #[test]
+    fn strict_prefixes() {
+        let mut mapping = PrefixMapping::default();
+        mapping.add_prefix("1st", FOAF_VOCAB).unwrap();
+        mapping.set_strict_prefixes(true);
+        assert!(mapping.strict_prefixes());
+        assert_eq!(
+            mapping.add_prefix("a:b", FOAF_VOCAB),
+            Err(InvalidPrefixError::InvalidName)
+        );
+        assert_eq!(
+            mapping.add_prefix("_", FOAF_VOCAB),
+            Err(InvalidPrefixError::ReservedPrefix)
+        );
+        assert_eq!(
+            mapping.add_prefix_template("2nd", "http://example.com/{id}"),
+            Err(TemplateError::InvalidPrefix(
+                InvalidPrefixError::InvalidName
+            ))
+        );
+        assert_eq!(mapping.add_prefix("", FOAF_VOCAB), Ok(()));
+        // Prefixes added before are kept.
+        assert_eq!(
+            mapping.expand_curie_string("1st:name"),
+            Ok(format!("{FOAF_VOCAB}name"))
+        );
+    }
+
+    #[test]
+    fn strict_namespaces() {
+        let mut mapping = PrefixMapping::default();
+        assert_eq!(
+            mapping.try_set_default("example"),
+            Err(InvalidPrefixError::InvalidNamespace)
+        );
+        assert_eq!(mapping.try_set_default("http://example.com/"), Ok(()));
+        mapping.add_prefix("rel", "../vocab/").unwrap();
+
+        mapping.set_strict_namespaces(true);
+        assert!(mapping.strict_namespaces());
+        assert_eq!(
+            mapping.add_prefix("ex", "http://example.com/a b/"),
+            Err(InvalidPrefixError::InvalidNamespace)
+        );
+        assert_eq!(
+            mapping.add_prefix_template("ex", "example.com/{id}"),
+            Err(TemplateError::InvalidPrefix(
+                InvalidPrefixError::InvalidNamespace
+            ))
+        );
+        assert_eq!(
+            mapping.add_prefix_template("ex", "https://example.com/?id={id}&v=2"),
+            Ok(())
+        );
+        assert_eq!(
+            mapping.expand_curie_string("rel:x"),
+            Ok(String::from("../vocab/x"))
+        );
+    }
+
+    #[test]
+    fn strict_rebinding() {
+        let mut mapping = PrefixMapping::default();
+        mapping.set_strict_rebinding(true);
+        assert!(mapping.strict_rebinding());
+        mapping.add_prefix("ex", "http://example.com/").unwrap();
+        mapping
+            .add_prefix_template("doi", "https://doi.org/{id}")
+            .unwrap();
+
+        assert_eq!(
+            mapping.add_prefix("doi", "https://doi.org/"),
+            Err(InvalidPrefixError::AlreadyBound)
+        );
+        assert_eq!(
+            mapping.add_prefix_template("ex", "http://example.com/{id}"),
+            Err(TemplateError::InvalidPrefix(
+                InvalidPrefixError::AlreadyBound
+            ))
+        );
+        assert_eq!(
+            mapping.add_prefix_template("doi", "https://doi.org/{id}"),
+            Ok(())
+        );
+        mapping
+            .force_add_prefix("ex", "http://example.org/")
+            .unwrap();
+        assert_eq!(
+            mapping.remove_prefix("ex"),
+            Ok(Some(String::from("http://example.org/")))
+        );
+        assert_eq!(mapping.add_prefix("ex", "http://example.com/"), Ok(()));
+    }
+
+    #[test]
+    fn non_ascii_curies() {
+        let mut mapping = PrefixMapping::default();
+        mapping
+            .add_prefix("été", "http://example.com/été/")
+            .unwrap();
+        mapping
+            .add_prefix("名前", "http://example.com/名前#")
+            .unwrap();
+
+        assert_eq!(
+            mapping.expand_curie_string("été:café"),
+            Ok(String::from("http://example.com/été/café"))
+        );
+        assert_eq!(
+            mapping.expand_curie_string("名前:東京"),
+            Ok(String::from("http://example.com/名前#東京"))
+        );
+        assert_eq!(
+            mapping.shrink_iri("http://example.com/名前#東京"),
+            Ok(Curie::new(Some("名前"), "東京"))
+        );
+        assert_eq!(
+            mapping.expand_curie_string("ß:x"),
+            Err(ExpansionError::UnknownPrefix(String::from("ß")))
+        );
+    }
+
+    #[test]
+    fn safe_curies() {
+        let mut mapping = PrefixMapping::default();
+        mapping.add_prefix("foaf", FOAF_VOCAB).unwrap();
+
+        let curie = Curie::new(Some("foaf"), "Person");
+        assert_eq!(
+            mapping.expand_safe_curie(&curie.to_safe_string()),
+            mapping.expand_curie(&curie)
+        );
+        // Unbalanced brackets are left as they are.
+        assert_eq!(
+            mapping.expand_safe_curie("[foaf:Person"),
+            Err(ExpansionError::UnknownPrefix(String::from("[foaf")))
+        );
+        assert_eq!(
+            mapping.expand_safe_curie("[Person]"),
+            Err(ExpansionError::MissingDefault)
+        );
+    }
+
     #[test]
     fn expand_curie_string() {
         let mut mapping = PrefixMapping::default();
@@ -471,7 +1590,7 @@ mod tests {
         // A CURIE with an unmapped prefix isn't expanded.
         assert_eq!(
             mapping.expand_curie_string(curie),
-            Err(ExpansionError::Invalid)
+            Err(ExpansionError::UnknownPrefix(String::from("foaf")))
         );
 
         // A CURIE without a separator doesn't cause problems. It still
@@ -492,7 +1611,7 @@ mod tests {
         // for an empty string.
         assert_eq!(
             mapping.expand_curie_string(":Person"),
-            Err(ExpansionError::Invalid)
+            Err(ExpansionError::UnknownPrefix(String::new()))
         );
         mapping
             .add_prefix("", "http://example.com/ExampleDocument#")
@@ -506,7 +1625,7 @@ mod tests {
         // be expanded with the default.
         assert_eq!(
             mapping.expand_curie_string(curie),
-            Err(ExpansionError::Invalid)
+            Err(ExpansionError::UnknownPrefix(String::from("foaf")))
         );
 
         mapping.add_prefix("foaf", FOAF_VOCAB).unwrap();
@@ -569,7 +1688,18 @@ mod tests {
         );
         assert_eq!(
             mapping.shrink_iri("http://example.com/other/thing"),
-            Ok(Curie::new(Some("a"), "other/thing")),
+            Ok(Curie::new(Some("b"), "thing")),
+        );
+
+        // The default namespace is only used if it is as specific.
+        mapping.set_default("http://example.com/");
+        assert_eq!(
+            mapping.shrink_iri("http://example.com/thing"),
+            Ok(Curie::new(None, "thing")),
+        );
+        assert_eq!(
+            mapping.shrink_iri("http://example.com/other/thing"),
+            Ok(Curie::new(Some("b"), "thing")),
         );
 
         let mut mapping2 = PrefixMapping::default();
@@ -588,6 +1718,88 @@ mod tests {
         );
     }
 
+    #[test]
+    fn multi_segment_references() {
+        let mut mapping = PrefixMapping::default();
+        mapping.add_prefix("schema", "http://schema.org/").unwrap();
+        mapping.add_prefix("v", "http://example.com/v#").unwrap();
+        mapping.add_prefix("host", "http://example.com").unwrap();
+        mapping.add_prefix("ab", "ab").unwrap();
+
+        let iri = "http://schema.org/Person/extra?x=1#top";
+        assert_eq!(
+            mapping.expand_curie_string("schema:Person/extra?x=1#top"),
+            Ok(String::from(iri))
+        );
+        assert_eq!(
+            mapping.shrink_iri(iri),
+            Ok(Curie::new(Some("schema"), "Person/extra?x=1#top"))
+        );
+        assert_eq!(
+            mapping.expand_curie_string("v:a/b?c:d"),
+            Ok(String::from("http://example.com/v#a/b?c:d"))
+        );
+        assert_eq!(
+            mapping.expand_curie_string("v:a#b"),
+            Err(ExpansionError::DuplicateFragment)
+        );
+
+        // Namespaces ending within the authority match at a boundary only.
+        assert_eq!(
+            mapping.shrink_iri("http://example.com/x"),
+            Ok(Curie::new(Some("host"), "/x"))
+        );
+        assert_eq!(
+            mapping.shrink_iri("http://example.com.au/x"),
+            Err(ShrinkError::NoMatch)
+        );
+        assert_eq!(mapping.shrink_iri(""), Err(ShrinkError::EmptyIri));
+
+        // The namespace is only stripped once.
+        assert_eq!(
+            mapping.shrink_iri("ababc"),
+            Ok(Curie::new(Some("ab"), "abc"))
+        );
+    }
+
+    #[test]
+    fn strict_references() {
+        let mut mapping = PrefixMapping::default();
+        mapping.add_prefix("ex", "http://example.com/").unwrap();
+        mapping.set_default("http://example.com/default/");
+        assert_eq!(
+            mapping.expand_curie_string("ex:a{b}"),
+            Ok(String::from("http://example.com/a{b}"))
+        );
+
+        mapping.set_strict_references(true);
+        assert!(mapping.strict_references());
+        let err = mapping.expand_curie_string("ex:a{b}").unwrap_err();
+        assert_eq!(
+            err,
+            ExpansionError::InvalidCharacter {
+                offset: 1,
+                character: '{'
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "the reference contains '{' at offset 1, which can't appear in an IRI"
+        );
+        assert_eq!(
+            mapping.expand_curie_string("é\"x\""),
+            Err(ExpansionError::InvalidCharacter {
+                offset: 2,
+                character: '"'
+            })
+        );
+        assert!(mapping.resolve(&Curie::new(Some("ex"), "<a>")).is_err());
+        assert_eq!(
+            mapping.expand_curie_string("ex:a/b?c=d#e"),
+            Ok(String::from("http://example.com/a/b?c=d#e"))
+        );
+    }
+
     #[test]
     fn split_iri_default() {
         let mut mapping = PrefixMapping::default();
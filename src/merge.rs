@@ -0,0 +1,268 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{InvalidPrefixError, PrefixMapping};
+use std::fmt;
+
+/// How [`PrefixMapping::merge()`] handles a prefix that both mappings
+/// bind to different values.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum MergePolicy {
+    /// Keep the existing binding.
+    #[default]
+    KeepExisting,
+    /// Replace the existing binding with the incoming one.
+    Overwrite,
+    /// Fail without changing the mapping.
+    ErrorOnConflict,
+}
+
+/// A prefix that both mappings bind to different values in
+/// [`PrefixMapping::merge()`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MergeConflict {
+    /// The prefix, or `None` for the default prefix.
+    pub prefix: Option<String>,
+    /// The namespace, or URI template, that the prefix was bound to.
+    pub existing: String,
+    /// The namespace that the other mapping binds the prefix to.
+    pub incoming: String,
+}
+
+/// The error returned by [`PrefixMapping::merge()`].
+///
+/// The mapping is left unchanged.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MergeError {
+    /// The mappings conflict, with [`MergePolicy::ErrorOnConflict`].
+    Conflicts(Vec<MergeConflict>),
+    /// A prefix can't be bound as the other mapping binds it.
+    InvalidPrefix {
+        /// The prefix.
+        prefix: String,
+        /// Why it can't be bound.
+        error: InvalidPrefixError,
+    },
+}
+
+impl fmt::Display for MergeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MergeError::Conflicts(conflicts) => {
+                write!(f, "{} conflicting prefixes", conflicts.len())
+            }
+            MergeError::InvalidPrefix { prefix, error } => {
+                write!(f, "can't bind `{prefix}`: {error}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for MergeError {}
+
+impl PrefixMapping {
+    /// Add the prefixes and default prefix of `other` to this mapping.
+    ///
+    /// A prefix that both mappings bind to different values is a
+    /// conflict, which is resolved according to `policy`. New prefixes
+    /// are added in the order of `other`, after the existing ones. Only
+    /// namespaces are merged: the URI templates, terms and settings of
+    /// `other` are not.
+    ///
+    /// Returns the conflicts, whether the existing bindings were kept
+    /// or overwritten.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MergeError::Conflicts`] if there are conflicts and
+    /// `policy` is [`MergePolicy::ErrorOnConflict`], and
+    /// [`MergeError::InvalidPrefix`] if a prefix can't be bound, because
+    /// it is protected or doesn't pass the checks of this mapping. With
+    /// [`PrefixMapping::set_strict_rebinding()`], overwriting a prefix
+    /// is an [`InvalidPrefixError::AlreadyBound`] error. The mapping is
+    /// only changed if there is no error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::{MergeConflict, MergePolicy, PrefixMapping};
+    ///
+    /// let mut mapping = PrefixMapping::default();
+    /// mapping.add_prefix("ex", "http://example.com/").unwrap();
+    ///
+    /// let mut other = PrefixMapping::default();
+    /// other.add_prefix("ex", "http://example.org/").unwrap();
+    /// other.add_prefix("foaf", "http://xmlns.com/foaf/0.1/").unwrap();
+    ///
+    /// let conflicts = mapping.merge(&other, MergePolicy::KeepExisting).unwrap();
+    /// assert_eq!(conflicts, [MergeConflict {
+    ///     prefix: Some(String::from("ex")),
+    ///     existing: String::from("http://example.com/"),
+    ///     incoming: String::from("http://example.org/"),
+    /// }]);
+    /// assert_eq!(mapping.expand_curie_string("ex:a"),
+    ///            Ok(String::from("http://example.com/a")));
+    /// assert_eq!(mapping.expand_curie_string("foaf:name"),
+    ///            Ok(String::from("http://xmlns.com/foaf/0.1/name")));
+    /// ```
+    pub fn merge(
+        &mut self,
+        other: &PrefixMapping,
+        policy: MergePolicy,
+    ) -> Result<Vec<MergeConflict>, MergeError> {
+        let mut conflicts = Vec::new();
+        let mut default = None;
+        if let Some(ref incoming) = other.default {
+            match self.default {
+                None => default = Some(incoming),
+                Some(ref existing) if existing != incoming => {
+                    conflicts.push(MergeConflict {
+                        prefix: None,
+                        existing: existing.clone(),
+                        incoming: incoming.clone(),
+                    });
+                    if policy == MergePolicy::Overwrite {
+                        default = Some(incoming);
+                    }
+                }
+                Some(_) => {}
+            }
+        }
+
+        let mut additions = Vec::new();
+        for (prefix, incoming) in &other.mapping {
+            let existing = self
+                .mapping
+                .get(prefix)
+                .cloned()
+                .or_else(|| self.prefix_template(prefix));
+            match existing {
+                None => additions.push((prefix, incoming)),
+                Some(existing) if existing != *incoming => {
+                    conflicts.push(MergeConflict {
+                        prefix: Some(prefix.clone()),
+                        existing,
+                        incoming: incoming.clone(),
+                    });
+                    if policy == MergePolicy::Overwrite {
+                        additions.push((prefix, incoming));
+                    }
+                }
+                Some(_) => {}
+            }
+        }
+        if policy == MergePolicy::ErrorOnConflict && !conflicts.is_empty() {
+            return Err(MergeError::Conflicts(conflicts));
+        }
+
+        for &(prefix, namespace) in &additions {
+            let checked = if self.protected.contains(prefix) {
+                Err(InvalidPrefixError::ProtectedPrefix)
+            } else {
+                let bound = self.mapping.get(prefix).map(String::as_str);
+                self.check_rebinding(prefix, bound, namespace)
+                    .and_then(|()| self.check_prefix(prefix))
+                    .and_then(|()| self.check_namespace(namespace))
+            };
+            checked.map_err(|error| MergeError::InvalidPrefix {
+                prefix: prefix.clone(),
+                error,
+            })?;
+        }
+        if let Some(default) = default {
+            self.set_default(default);
+        }
+        for (prefix, namespace) in additions {
+            self.force_add_prefix(prefix, namespace)
+                .expect("prefixes are checked before merging");
+        }
+        Ok(conflicts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mappings() -> (PrefixMapping, PrefixMapping) {
+        let mut mapping = PrefixMapping::default();
+        mapping.set_default("http://example.com/");
+        mapping.add_prefix("a", "http://example.com/a/").unwrap();
+        mapping.add_prefix("b", "http://example.com/b/").unwrap();
+        let mut other = PrefixMapping::default();
+        other.set_default("http://example.org/");
+        other.add_prefix("b", "http://example.org/b/").unwrap();
+        other.add_prefix("a", "http://example.com/a/").unwrap();
+        other.add_prefix("c", "http://example.org/c/").unwrap();
+        (mapping, other)
+    }
+
+    #[test]
+    fn policies() {
+        let (mut mapping, other) = mappings();
+        let conflicts = mapping.merge(&other, MergePolicy::Overwrite).unwrap();
+        let prefixes: Vec<_> = conflicts.iter().map(|c| c.prefix.as_deref()).collect();
+        assert_eq!(prefixes, [None, Some("b")]);
+        assert_eq!(
+            mapping.expand_curie_string("b:x"),
+            Ok(String::from("http://example.org/b/x"))
+        );
+        assert_eq!(
+            mapping.expand_curie_string("x"),
+            Ok(String::from("http://example.org/x"))
+        );
+        let order: Vec<&str> = mapping.mappings().map(|(p, _)| p).collect();
+        assert_eq!(order, ["a", "b", "c"]);
+
+        let (mut mapping, other) = mappings();
+        let before = mapping.clone();
+        let err = mapping
+            .merge(&other, MergePolicy::ErrorOnConflict)
+            .unwrap_err();
+        assert!(matches!(err, MergeError::Conflicts(ref c) if c.len() == 2));
+        assert_eq!(err.to_string(), "2 conflicting prefixes");
+        assert_eq!(mapping, before);
+    }
+
+    #[test]
+    fn invalid_prefixes() {
+        let (mut mapping, other) = mappings();
+        mapping.protect_prefix("b");
+        let before = mapping.clone();
+        assert_eq!(
+            mapping.merge(&other, MergePolicy::Overwrite),
+            Err(MergeError::InvalidPrefix {
+                prefix: String::from("b"),
+                error: InvalidPrefixError::ProtectedPrefix,
+            })
+        );
+        assert_eq!(mapping, before);
+        assert_eq!(
+            mapping
+                .merge(&other, MergePolicy::KeepExisting)
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn strict_rebinding() {
+        let (mut mapping, other) = mappings();
+        mapping.set_strict_rebinding(true);
+        let before = mapping.clone();
+        assert_eq!(
+            mapping.merge(&other, MergePolicy::Overwrite),
+            Err(MergeError::InvalidPrefix {
+                prefix: String::from("b"),
+                error: InvalidPrefixError::AlreadyBound,
+            })
+        );
+        assert_eq!(mapping, before);
+        assert!(mapping.merge(&other, MergePolicy::KeepExisting).is_ok());
+        assert_eq!(mapping.get_expansion("c"), Some("http://example.org/c/"));
+    }
+}
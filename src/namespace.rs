@@ -0,0 +1,128 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{InvalidPrefixError, IriBuf, PrefixMapping};
+use std::fmt;
+
+/// A namespace IRI, from which the IRIs of terms are built.
+///
+/// This is useful for building IRIs directly, without going through a
+/// CURIE and a [`PrefixMapping`].
+///
+/// # Examples
+///
+/// ```
+/// use curie::{Curie, Namespace, PrefixMapping};
+///
+/// let foaf = Namespace::new("http://xmlns.com/foaf/0.1/");
+/// assert_eq!(foaf.term("Agent"), "http://xmlns.com/foaf/0.1/Agent");
+/// assert_eq!(foaf.local_name("http://xmlns.com/foaf/0.1/name"), Some("name"));
+///
+/// let mut mapping = PrefixMapping::default();
+/// mapping.add_namespace("foaf", &foaf).unwrap();
+/// assert_eq!(mapping.expand_curie(&Curie::new(Some("foaf"), "Agent")),
+///            Ok(foaf.term("Agent").into_string()));
+/// ```
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Namespace(String);
+
+impl Namespace {
+    /// Construct a `Namespace` from its IRI.
+    #[must_use]
+    pub fn new(iri: impl Into<String>) -> Self {
+        Namespace(iri.into())
+    }
+
+    /// Borrow the namespace IRI as a string slice.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The IRI of the term `name` within this namespace.
+    #[must_use]
+    pub fn term(&self, name: &str) -> IriBuf {
+        let mut iri = String::with_capacity(self.0.len() + name.len());
+        iri.push_str(&self.0);
+        iri.push_str(name);
+        IriBuf::from(iri)
+    }
+
+    /// The name of a term within this namespace, or `None` when `iri`
+    /// isn't within this namespace.
+    #[must_use]
+    pub fn local_name<'a>(&self, iri: &'a str) -> Option<&'a str> {
+        iri.strip_prefix(self.0.as_str())
+    }
+}
+
+impl From<&str> for Namespace {
+    fn from(iri: &str) -> Namespace {
+        Namespace::new(iri)
+    }
+}
+
+impl From<String> for Namespace {
+    fn from(iri: String) -> Namespace {
+        Namespace(iri)
+    }
+}
+
+impl AsRef<str> for Namespace {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Namespace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl PrefixMapping {
+    /// Add a prefix for a [`Namespace`] to the mapping.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidPrefixError`] when the prefix is invalid.
+    ///
+    /// # See also
+    ///
+    /// * [`PrefixMapping::add_prefix()`]
+    pub fn add_namespace(
+        &mut self,
+        prefix: &str,
+        namespace: &Namespace,
+    ) -> Result<(), InvalidPrefixError> {
+        self.add_prefix(prefix, namespace.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Curie;
+
+    #[test]
+    fn terms() {
+        let ex = Namespace::from("http://example.com/ns#");
+        assert_eq!(ex.term("Thing"), "http://example.com/ns#Thing");
+        assert_eq!(ex.local_name("http://example.com/other#Thing"), None);
+        assert_eq!(ex.to_string(), "http://example.com/ns#");
+
+        let mut mapping = PrefixMapping::default();
+        assert_eq!(
+            mapping.add_namespace("_", &ex),
+            Err(InvalidPrefixError::ReservedPrefix)
+        );
+        mapping.add_namespace("ex", &ex).unwrap();
+        assert_eq!(
+            mapping.shrink_iri(ex.term("Thing").as_str()),
+            Ok(Curie::new(Some("ex"), "Thing"))
+        );
+    }
+}
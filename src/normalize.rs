@@ -0,0 +1,150 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{syntax, CurieBuf, ParseError, ParseErrorKind};
+
+impl CurieBuf {
+    /// Parse a CURIE from a token taken from a file, normalizing it on
+    /// the way.
+    ///
+    /// The token is normalized by:
+    ///
+    /// * trimming the whitespace around it,
+    /// * decoding `\uXXXX` and `\UXXXXXXXX` escapes, as found in Turtle
+    ///   and SPARQL, and the backslash escapes of reserved characters
+    ///   allowed in Turtle local names, like `\.` and `\/`,
+    /// * rejecting control characters, whether escaped or not.
+    ///
+    /// The prefix ends at the first unescaped colon, so an escaped
+    /// colon is part of the prefix or reference.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] of kind [`ParseErrorKind::InvalidEscape`]
+    /// or [`ParseErrorKind::ControlCharacter`], with the position in
+    /// `input`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::{CurieBuf, ParseErrorKind};
+    ///
+    /// let curie = CurieBuf::parse_normalized("  ex:caf\\u00E9\\.menu\n").unwrap();
+    /// assert_eq!(curie.to_string(), "ex:café.menu");
+    ///
+    /// let err = CurieBuf::parse_normalized("ex:a\\u0007").unwrap_err();
+    /// assert_eq!(err.kind(), ParseErrorKind::ControlCharacter);
+    /// assert_eq!((err.column(), err.slice()), (5, "\\u0007"));
+    /// ```
+    pub fn parse_normalized(input: &str) -> Result<CurieBuf, ParseError> {
+        let start = input.len() - input.trim_start().len();
+        let end = input.trim_end().len().max(start);
+        let token = &input[start..end];
+        let decode = |from: usize, to: usize| unescape(input, start + from, start + to);
+        match token.find(':') {
+            Some(idx) => Ok(CurieBuf::new(
+                Some(&decode(0, idx)?),
+                &decode(idx + 1, token.len())?,
+            )),
+            None => Ok(CurieBuf::new(None, &decode(0, token.len())?)),
+        }
+    }
+}
+
+/// Decode the escapes in `input[start..end]`, rejecting control
+/// characters.
+fn unescape(input: &str, start: usize, end: usize) -> Result<String, ParseError> {
+    let mut unescaped = String::with_capacity(end - start);
+    let mut chars = input[start..end].char_indices();
+    while let Some((idx, c)) = chars.next() {
+        let pos = start + idx;
+        let decoded = if c == '\\' {
+            let rest = &input[pos + 1..end];
+            match rest.chars().next() {
+                Some(escaped) if is_reserved(escaped) => {
+                    chars.next();
+                    escaped
+                }
+                _ => {
+                    let Some((decoded, len)) = syntax::decode_uchar(rest) else {
+                        let len = rest.chars().next().map_or(0, char::len_utf8);
+                        return Err(ParseError::at(
+                            ParseErrorKind::InvalidEscape,
+                            input,
+                            pos,
+                            pos + 1 + len,
+                        ));
+                    };
+                    if decoded.is_control() {
+                        return Err(ParseError::at(
+                            ParseErrorKind::ControlCharacter,
+                            input,
+                            pos,
+                            pos + 1 + len,
+                        ));
+                    }
+                    // The escape is ASCII, so this skips it.
+                    for _ in 0..len {
+                        chars.next();
+                    }
+                    decoded
+                }
+            }
+        } else if c.is_control() {
+            return Err(ParseError::at(
+                ParseErrorKind::ControlCharacter,
+                input,
+                pos,
+                pos + c.len_utf8(),
+            ));
+        } else {
+            c
+        };
+        unescaped.push(decoded);
+    }
+    Ok(unescaped)
+}
+
+/// Can `c` be escaped with a backslash in a Turtle local name?
+fn is_reserved(c: char) -> bool {
+    "_~.-!$&'()*+,;=/?#@%".contains(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalization() {
+        assert_eq!(
+            CurieBuf::parse_normalized("\t\\u0066oaf:\\U0001F600 "),
+            Ok(CurieBuf::new(Some("foaf"), "😀"))
+        );
+        assert_eq!(
+            CurieBuf::parse_normalized("ex\\u003Aa:b"),
+            Ok(CurieBuf::new(Some("ex:a"), "b"))
+        );
+        assert_eq!(
+            CurieBuf::parse_normalized("  Entity  "),
+            Ok(CurieBuf::new(None, "Entity"))
+        );
+        assert_eq!(
+            CurieBuf::parse_normalized("   "),
+            Ok(CurieBuf::new(None, ""))
+        );
+
+        let err = CurieBuf::parse_normalized(" ex:é\\q").unwrap_err();
+        assert_eq!(err.kind(), ParseErrorKind::InvalidEscape);
+        assert_eq!((err.offset(), err.column(), err.slice()), (6, 6, "\\q"));
+
+        let err = CurieBuf::parse_normalized("ex:a\\u12").unwrap_err();
+        assert_eq!(err.kind(), ParseErrorKind::InvalidEscape);
+
+        let err = CurieBuf::parse_normalized("ex:a\u{0}b").unwrap_err();
+        assert_eq!(err.kind(), ParseErrorKind::ControlCharacter);
+        assert_eq!(err.offset(), 4);
+    }
+}
@@ -0,0 +1,252 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{Curie, ExpansionError, PrefixId, PrefixMapping};
+use std::fmt;
+
+/// The raw prefix value used for CURIEs without a prefix.
+const NO_PREFIX: u32 = u32::MAX;
+
+/// Errors that might occur when packing a CURIE into a [`CurieArena`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PackError {
+    /// The prefix on the CURIE is not mapped.
+    UnknownPrefix,
+    /// The arena has no room left for the reference.
+    ///
+    /// An arena holds at most 4 GiB of reference text.
+    ArenaFull,
+}
+
+impl fmt::Display for PackError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PackError::UnknownPrefix => f.write_str("the prefix is not mapped"),
+            PackError::ArenaFull => f.write_str("the arena is full"),
+        }
+    }
+}
+
+impl std::error::Error for PackError {}
+
+/// A CURIE packed into 12 bytes: a [`PrefixId`] and the location of
+/// the reference within a [`CurieArena`].
+///
+/// A `PackedCurie` is only meaningful together with the arena and the
+/// [`PrefixMapping`] that were used to create it.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct PackedCurie {
+    prefix: u32,
+    start: u32,
+    len: u32,
+}
+
+impl PackedCurie {
+    /// The id of the prefix, or `None` if the CURIE has no prefix.
+    #[must_use]
+    pub fn prefix_id(&self) -> Option<PrefixId> {
+        (self.prefix != NO_PREFIX).then(|| PrefixId::from_u32(self.prefix))
+    }
+
+    /// Encode as little-endian bytes.
+    #[must_use]
+    pub fn to_bytes(&self) -> [u8; 12] {
+        let mut bytes = [0; 12];
+        bytes[0..4].copy_from_slice(&self.prefix.to_le_bytes());
+        bytes[4..8].copy_from_slice(&self.start.to_le_bytes());
+        bytes[8..12].copy_from_slice(&self.len.to_le_bytes());
+        bytes
+    }
+
+    /// Decode from bytes produced by [`PackedCurie::to_bytes()`].
+    #[must_use]
+    pub fn from_bytes(bytes: [u8; 12]) -> Self {
+        let word = |idx: usize| {
+            u32::from_le_bytes([bytes[idx], bytes[idx + 1], bytes[idx + 2], bytes[idx + 3]])
+        };
+        PackedCurie {
+            prefix: word(0),
+            start: word(4),
+            len: word(8),
+        }
+    }
+}
+
+/// Shared storage for the references of [`PackedCurie`]s.
+///
+/// The references of all packed CURIEs are appended to a single
+/// string, so that storing a large number of CURIEs costs little more
+/// than the text of their references.
+///
+/// # Examples
+///
+/// ```
+/// use curie::{Curie, CurieArena, PrefixMapping};
+///
+/// let mut mapping = PrefixMapping::default();
+/// mapping.add_prefix("foaf", "http://xmlns.com/foaf/0.1/").unwrap();
+///
+/// let mut arena = CurieArena::default();
+/// let packed = arena.pack(&mapping, &Curie::new(Some("foaf"), "Agent")).unwrap();
+///
+/// assert_eq!(arena.unpack(&mapping, packed),
+///            Some(Curie::new(Some("foaf"), "Agent")));
+/// assert_eq!(arena.expand(&mapping, packed),
+///            Ok(String::from("http://xmlns.com/foaf/0.1/Agent")));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CurieArena {
+    text: String,
+}
+
+impl CurieArena {
+    /// Create an arena with room for `capacity` bytes of references.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        CurieArena {
+            text: String::with_capacity(capacity),
+        }
+    }
+
+    /// Pack a [`Curie`], storing its reference in this arena.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PackError::UnknownPrefix`] if the prefix of the CURIE
+    /// is not mapped by `mapping`, or [`PackError::ArenaFull`] if the
+    /// reference doesn't fit.
+    pub fn pack(
+        &mut self,
+        mapping: &PrefixMapping,
+        curie: &Curie,
+    ) -> Result<PackedCurie, PackError> {
+        let prefix = match curie.prefix {
+            Some(prefix) => mapping
+                .prefix_id(prefix)
+                .ok_or(PackError::UnknownPrefix)?
+                .as_u32(),
+            None => NO_PREFIX,
+        };
+        let start = u32::try_from(self.text.len()).map_err(|_| PackError::ArenaFull)?;
+        let len = u32::try_from(curie.reference.len()).map_err(|_| PackError::ArenaFull)?;
+        start.checked_add(len).ok_or(PackError::ArenaFull)?;
+        self.text.push_str(curie.reference);
+        Ok(PackedCurie { prefix, start, len })
+    }
+
+    /// Get the reference of a packed CURIE.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `packed` was not created by this arena.
+    #[must_use]
+    pub fn reference(&self, packed: PackedCurie) -> &str {
+        let start = packed.start as usize;
+        &self.text[start..start + packed.len as usize]
+    }
+
+    /// Unpack a packed CURIE.
+    ///
+    /// Returns `None` if the prefix of the CURIE is no longer mapped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `packed` was not created by this arena.
+    #[must_use]
+    pub fn unpack<'a>(
+        &'a self,
+        mapping: &'a PrefixMapping,
+        packed: PackedCurie,
+    ) -> Option<Curie<'a>> {
+        let prefix = match packed.prefix_id() {
+            Some(id) => Some(mapping.prefix_for_id(id)?),
+            None => None,
+        };
+        Some(Curie::new(prefix, self.reference(packed)))
+    }
+
+    /// Expand a packed CURIE, returning a complete IRI.
+    ///
+    /// # Errors
+    ///
+    /// This will return [`ExpansionError`] if the expansion fails.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `packed` was not created by this arena.
+    pub fn expand(
+        &self,
+        mapping: &PrefixMapping,
+        packed: PackedCurie,
+    ) -> Result<String, ExpansionError> {
+        let reference = self.reference(packed);
+        match packed.prefix_id() {
+            Some(id) => mapping.expand_prefix_id(id, reference),
+            None => mapping.expand_exploded_curie(None, reference),
+        }
+    }
+
+    /// The number of bytes of reference text stored in this arena.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.text.len()
+    }
+
+    /// Does this arena hold no reference text?
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pack_round_trip() {
+        let mut mapping = PrefixMapping::default();
+        mapping
+            .add_prefix("foaf", "http://xmlns.com/foaf/0.1/")
+            .unwrap();
+        mapping.set_default("http://example.com/");
+
+        let mut arena = CurieArena::default();
+        let agent = arena
+            .pack(&mapping, &Curie::new(Some("foaf"), "Agent"))
+            .unwrap();
+        let entity = arena.pack(&mapping, &Curie::new(None, "Entity")).unwrap();
+        assert_eq!(arena.len(), "AgentEntity".len());
+
+        let agent = PackedCurie::from_bytes(agent.to_bytes());
+        assert_eq!(arena.reference(agent), "Agent");
+        assert_eq!(arena.reference(entity), "Entity");
+        assert_eq!(entity.prefix_id(), None);
+        assert_eq!(
+            arena.unpack(&mapping, entity),
+            Some(Curie::new(None, "Entity"))
+        );
+        assert_eq!(
+            arena.expand(&mapping, entity),
+            Ok(String::from("http://example.com/Entity"))
+        );
+
+        mapping.remove_prefix("foaf").unwrap();
+        assert_eq!(arena.unpack(&mapping, agent), None);
+        assert_eq!(arena.expand(&mapping, agent), Err(ExpansionError::Invalid));
+    }
+
+    #[test]
+    fn pack_unknown_prefix() {
+        let mapping = PrefixMapping::default();
+        let mut arena = CurieArena::default();
+        assert_eq!(
+            arena.pack(&mapping, &Curie::new(Some("foaf"), "Agent")),
+            Err(PackError::UnknownPrefix)
+        );
+        assert!(arena.is_empty());
+    }
+}
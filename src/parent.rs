@@ -0,0 +1,135 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::PrefixMapping;
+use std::sync::Arc;
+
+impl PrefixMapping {
+    /// Construct an empty mapping that falls back to `parent`.
+    ///
+    /// A prefix that isn't bound in the mapping, or the default prefix
+    /// when it isn't set, is looked up in the parent, and shrinking
+    /// uses the namespaces of the parent that the mapping doesn't
+    /// shadow. Prefixes added to the mapping are kept separate from the
+    /// parent, which is never changed, so one large set of prefixes can
+    /// be shared by many mappings without being copied.
+    ///
+    /// Only namespaces are inherited: URI templates, terms and settings
+    /// such as strictness are those of the mapping itself. Removing a
+    /// prefix only removes the mapping's own binding, so the parent's
+    /// binding is used again. [`PrefixMapping::mappings()`] only yields
+    /// the mapping's own prefixes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::{Curie, PrefixMapping};
+    /// use std::sync::Arc;
+    ///
+    /// let mut common = PrefixMapping::default();
+    /// common.add_prefix("foaf", "http://xmlns.com/foaf/0.1/").unwrap();
+    /// common.add_prefix("ex", "http://example.com/").unwrap();
+    /// let common = Arc::new(common);
+    ///
+    /// let mut mapping = PrefixMapping::with_parent(Arc::clone(&common));
+    /// mapping.add_prefix("ex", "http://example.org/").unwrap();
+    ///
+    /// assert_eq!(mapping.expand_curie_string("foaf:name"),
+    ///            Ok(String::from("http://xmlns.com/foaf/0.1/name")));
+    /// assert_eq!(mapping.expand_curie_string("ex:a"),
+    ///            Ok(String::from("http://example.org/a")));
+    /// assert_eq!(mapping.shrink_iri("http://xmlns.com/foaf/0.1/name"),
+    ///            Ok(Curie::new(Some("foaf"), "name")));
+    /// assert_eq!(mapping.mappings().count(), 1);
+    /// ```
+    #[must_use]
+    pub fn with_parent(parent: Arc<PrefixMapping>) -> Self {
+        PrefixMapping {
+            parent: Some(parent),
+            ..PrefixMapping::default()
+        }
+    }
+
+    /// The mapping that this one falls back to, if any.
+    #[must_use]
+    pub fn parent(&self) -> Option<&Arc<PrefixMapping>> {
+        self.parent.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Curie, ExpansionError};
+
+    #[test]
+    fn fallback() {
+        let mut grandparent = PrefixMapping::default();
+        grandparent.set_default("http://example.com/vocab/");
+        grandparent
+            .add_prefix("a", "http://example.com/a/")
+            .unwrap();
+        let mut parent = PrefixMapping::with_parent(Arc::new(grandparent));
+        parent.add_prefix("b", "http://example.com/b/").unwrap();
+        parent.add_prefix("a", "http://example.com/").unwrap();
+        let parent = Arc::new(parent);
+        let mut mapping = PrefixMapping::with_parent(Arc::clone(&parent));
+        mapping.add_prefix("c", "http://example.com/c/").unwrap();
+
+        assert_eq!(
+            mapping.expand_curie_string("x"),
+            Ok(String::from("http://example.com/vocab/x"))
+        );
+        assert_eq!(
+            mapping.expand_curie_string("a:x"),
+            Ok(String::from("http://example.com/x"))
+        );
+        assert_eq!(
+            mapping.expand_curie_string("d:x"),
+            Err(ExpansionError::UnknownPrefix(String::from("d")))
+        );
+
+        // The grandparent's "a" is shadowed by the parent's.
+        assert_eq!(
+            mapping.shrink_iri("http://example.com/a/x"),
+            Ok(Curie::new(Some("a"), "a/x"))
+        );
+        assert_eq!(
+            mapping.shrink_iri("http://example.com/vocab/x"),
+            Ok(Curie::new(None, "x"))
+        );
+        assert_eq!(
+            mapping.shrink_iri("http://example.com/c/x"),
+            Ok(Curie::new(Some("c"), "x"))
+        );
+
+        mapping.add_prefix("b", "http://example.org/b/").unwrap();
+        mapping.remove_prefix("b").unwrap();
+        assert_eq!(
+            mapping.expand_curie_string("b:x"),
+            Ok(String::from("http://example.com/b/x"))
+        );
+        assert_eq!(mapping.parent(), Some(&parent));
+        assert_ne!(mapping, PrefixMapping::default());
+    }
+
+    #[test]
+    fn accessors_exclude_parent() {
+        let mut parent = PrefixMapping::default();
+        parent
+            .add_prefix("foaf", "http://xmlns.com/foaf/0.1/")
+            .unwrap();
+        let mut mapping = PrefixMapping::with_parent(Arc::new(parent));
+        mapping.add_prefix("ex", "http://example.com/").unwrap();
+
+        assert!(mapping.expand_curie_string("foaf:name").is_ok());
+        assert!(!mapping.contains_prefix("foaf"));
+        assert_eq!(mapping.get_expansion("foaf"), None);
+        assert!(mapping.contains_prefix("ex"));
+        assert_eq!(mapping.get_expansion("ex"), Some("http://example.com/"));
+        assert_eq!(mapping.len(), 1);
+    }
+}
@@ -0,0 +1,208 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt;
+
+/// What went wrong when reading prefix declarations from text.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ParseErrorKind {
+    /// A declaration is malformed.
+    InvalidDirective,
+    /// The prefix being declared is not a valid prefix name.
+    InvalidPrefix,
+    /// The IRI being declared is not valid.
+    InvalidIri,
+    /// The prefix being declared is protected in the mapping.
+    ProtectedPrefix,
+    /// The prefix being declared is already bound to a different IRI,
+    /// and the mapping doesn't allow rebinding it.
+    RedefinedPrefix,
+    /// The input is not valid JSON.
+    InvalidJson,
+    /// The input is not valid YAML.
+    InvalidYaml,
+    /// The input ended in the middle of a declaration or statement.
+    UnexpectedEnd,
+    /// A backslash escape is malformed or not allowed.
+    InvalidEscape,
+    /// The input contains a control character, possibly escaped.
+    ControlCharacter,
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            ParseErrorKind::InvalidDirective => "invalid directive",
+            ParseErrorKind::InvalidPrefix => "invalid prefix",
+            ParseErrorKind::InvalidIri => "invalid IRI",
+            ParseErrorKind::ProtectedPrefix => "protected prefix",
+            ParseErrorKind::RedefinedPrefix => "redefined prefix",
+            ParseErrorKind::InvalidJson => "invalid JSON",
+            ParseErrorKind::InvalidYaml => "invalid YAML",
+            ParseErrorKind::UnexpectedEnd => "unexpected end of input",
+            ParseErrorKind::InvalidEscape => "invalid escape",
+            ParseErrorKind::ControlCharacter => "control character",
+        })
+    }
+}
+
+/// An error found when reading prefix declarations from text, with
+/// where it was found.
+///
+/// This is shared by all of the importers that read text.
+///
+/// # Examples
+///
+/// ```
+/// use curie::ParseErrorKind;
+/// use curie::turtle::IncrementalImporter;
+///
+/// let mut importer = IncrementalImporter::new();
+/// let err = importer
+///     .feed_chunk("@prefix ex: <http://example.com/> .\n@prefix _: <http://example.com/> .\n")
+///     .unwrap_err();
+///
+/// assert_eq!(err.kind(), ParseErrorKind::InvalidPrefix);
+/// assert_eq!((err.offset(), err.line(), err.column()), (44, 2, 9));
+/// assert_eq!(err.slice(), "_");
+/// assert_eq!(err.to_string(), "invalid prefix at line 2, column 9: `_`");
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    kind: ParseErrorKind,
+    position: TextPosition,
+    slice: String,
+}
+
+impl ParseError {
+    pub(crate) fn new(kind: ParseErrorKind, position: TextPosition, slice: &str) -> Self {
+        ParseError {
+            kind,
+            position,
+            slice: String::from(slice),
+        }
+    }
+
+    /// Construct an error for the span `start..end` of `input`.
+    pub(crate) fn at(kind: ParseErrorKind, input: &str, start: usize, end: usize) -> Self {
+        let mut position = TextPosition::default();
+        position.advance(&input[..start]);
+        ParseError::new(kind, position, &input[start..end])
+    }
+
+    /// What went wrong.
+    #[must_use]
+    pub fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+
+    /// The offset in bytes from the start of the input.
+    #[must_use]
+    pub fn offset(&self) -> usize {
+        self.position.offset
+    }
+
+    /// The line, counting from 1.
+    #[must_use]
+    pub fn line(&self) -> usize {
+        self.position.line
+    }
+
+    /// The column, in characters and counting from 1.
+    #[must_use]
+    pub fn column(&self) -> usize {
+        self.position.column
+    }
+
+    /// The text that the error is about.
+    ///
+    /// This is empty for [`ParseErrorKind::UnexpectedEnd`].
+    #[must_use]
+    pub fn slice(&self) -> &str {
+        &self.slice
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} at line {}, column {}",
+            self.kind, self.position.line, self.position.column
+        )?;
+        if !self.slice.is_empty() {
+            write!(f, ": `{}`", self.slice)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A position in some text.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct TextPosition {
+    pub(crate) offset: usize,
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+}
+
+impl Default for TextPosition {
+    fn default() -> Self {
+        TextPosition {
+            offset: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+}
+
+impl TextPosition {
+    /// Move past `text`.
+    pub(crate) fn advance(&mut self, text: &str) {
+        self.offset += text.len();
+        match text.rfind('\n') {
+            Some(idx) => {
+                self.line += text.matches('\n').count();
+                self.column = 1 + text[idx + 1..].chars().count();
+            }
+            None => self.column += text.chars().count(),
+        }
+    }
+
+    /// The position after moving past `text`.
+    pub(crate) fn advanced(mut self, text: &str) -> Self {
+        self.advance(text);
+        self
+    }
+}
+
+/// Find the end of the token starting at `start`: the next whitespace
+/// or the end of `text`.
+pub(crate) fn token_end(text: &str, start: usize) -> usize {
+    text[start..]
+        .find(char::is_whitespace)
+        .map_or(text.len(), |len| start + len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn positions() {
+        let err = ParseError::at(ParseErrorKind::InvalidIri, "ab\nçd <x y>", 7, 12);
+        assert_eq!((err.offset(), err.line(), err.column()), (7, 2, 4));
+        assert_eq!(err.slice(), "<x y>");
+
+        let err = ParseError::at(ParseErrorKind::UnexpectedEnd, "ab\n", 3, 3);
+        assert_eq!((err.line(), err.column()), (2, 1));
+        assert_eq!(
+            err.to_string(),
+            "unexpected end of input at line 2, column 1"
+        );
+    }
+}
@@ -0,0 +1,139 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{Curie, ExpansionError, PrefixMapping};
+use regex::Regex;
+
+impl PrefixMapping {
+    /// Attach a regular expression that the references of CURIEs using
+    /// `prefix` must match, as identifier registries do for local
+    /// identifiers.
+    ///
+    /// The pattern is checked by [`PrefixMapping::validate_curie()`],
+    /// not by the other ways of expanding a CURIE. It isn't implicitly
+    /// anchored, so it should usually start with `^` and end with `$`.
+    /// The pattern stays attached to the prefix if the prefix is
+    /// rebound or removed.
+    ///
+    /// This requires the `regex` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from the `regex` crate if `pattern` is not a
+    /// valid regular expression.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::{Curie, ExpansionError, PrefixMapping};
+    ///
+    /// let mut mapping = PrefixMapping::default();
+    /// mapping.add_prefix("pubmed", "https://www.ncbi.nlm.nih.gov/pubmed/").unwrap();
+    /// mapping.set_reference_pattern("pubmed", r"^\d+$").unwrap();
+    ///
+    /// assert_eq!(mapping.validate_curie(&Curie::new(Some("pubmed"), "22140235")),
+    ///            Ok(String::from("https://www.ncbi.nlm.nih.gov/pubmed/22140235")));
+    /// assert_eq!(mapping.validate_curie(&Curie::new(Some("pubmed"), "PMC3531190")),
+    ///            Err(ExpansionError::PatternMismatch {
+    ///                prefix: String::from("pubmed"),
+    ///                pattern: String::from(r"^\d+$"),
+    ///            }));
+    /// ```
+    pub fn set_reference_pattern(
+        &mut self,
+        prefix: &str,
+        pattern: &str,
+    ) -> Result<(), regex::Error> {
+        let pattern = Regex::new(pattern)?;
+        self.patterns.insert(String::from(prefix), pattern);
+        Ok(())
+    }
+
+    /// Remove the pattern attached to `prefix`, returning whether there
+    /// was one.
+    pub fn remove_reference_pattern(&mut self, prefix: &str) -> bool {
+        self.patterns.shift_remove(prefix).is_some()
+    }
+
+    /// The pattern attached to `prefix`, if any.
+    ///
+    /// See [`PrefixMapping::set_reference_pattern()`].
+    #[must_use]
+    pub fn reference_pattern(&self, prefix: &str) -> Option<&str> {
+        self.patterns.get(prefix).map(Regex::as_str)
+    }
+
+    /// Expand a CURIE, checking its reference against the pattern
+    /// attached to its prefix.
+    ///
+    /// CURIEs whose prefix has no pattern, including those without a
+    /// prefix, are expanded as by [`PrefixMapping::expand_curie()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExpansionError::PatternMismatch`] if the reference
+    /// doesn't match the pattern, or any error from expanding the
+    /// CURIE.
+    pub fn validate_curie(&self, curie: &Curie) -> Result<String, ExpansionError> {
+        let iri = self.expand_curie(curie)?;
+        if let Some((prefix, pattern)) = curie
+            .prefix()
+            .and_then(|prefix| self.patterns.get_key_value(prefix))
+        {
+            if !pattern.is_match(curie.reference()) {
+                return Err(ExpansionError::PatternMismatch {
+                    prefix: prefix.clone(),
+                    pattern: String::from(pattern.as_str()),
+                });
+            }
+        }
+        Ok(iri)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn patterns() {
+        let mut mapping = PrefixMapping::default();
+        mapping
+            .add_prefix("go", "http://purl.obolibrary.org/obo/GO_")
+            .unwrap();
+        mapping.set_default("http://example.com/");
+        assert!(mapping.set_reference_pattern("go", "^(").is_err());
+        mapping.set_reference_pattern("go", r"^\d{7}$").unwrap();
+        assert_eq!(mapping.reference_pattern("go"), Some(r"^\d{7}$"));
+
+        assert!(mapping
+            .validate_curie(&Curie::new(Some("go"), "0008150"))
+            .is_ok());
+        let err = mapping
+            .validate_curie(&Curie::new(Some("go"), "8150"))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"the reference doesn't match the pattern "^\\d{7}$" of the prefix "go""#
+        );
+        // Expansion errors come first.
+        assert_eq!(
+            mapping.validate_curie(&Curie::new(Some("hp"), "x")),
+            Err(ExpansionError::UnknownPrefix(String::from("hp")))
+        );
+        assert!(mapping.validate_curie(&Curie::new(None, "x")).is_ok());
+        // Other ways of expanding don't check the pattern.
+        assert!(mapping
+            .expand_curie(&Curie::new(Some("go"), "8150"))
+            .is_ok());
+
+        assert!(mapping.remove_reference_pattern("go"));
+        assert!(!mapping.remove_reference_pattern("go"));
+        assert!(mapping
+            .validate_curie(&Curie::new(Some("go"), "8150"))
+            .is_ok());
+    }
+}
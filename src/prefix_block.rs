@@ -0,0 +1,488 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::turtle::{parse_directive, skip_space, Directive, ParsedDirective, Stop};
+use crate::{syntax, ParseError, ParseErrorKind};
+use std::fmt;
+use std::ops::Range;
+
+/// A prefix declaration within a [`PrefixBlock`].
+#[derive(Clone, Debug, PartialEq)]
+struct Declaration {
+    /// The declaration as written.
+    text: String,
+    /// Where the prefix is in `text`.
+    prefix: Range<usize>,
+    /// Where the IRI, with its angle brackets, is in `text`.
+    iri: Range<usize>,
+    /// The IRI with its escapes decoded.
+    namespace: String,
+}
+
+impl Declaration {
+    fn prefix(&self) -> &str {
+        &self.text[self.prefix.clone()]
+    }
+
+    /// Replace the prefix or the IRI, adjusting the spans.
+    fn splice(&mut self, range: Range<usize>, with: &str) {
+        let adjust = |span: &mut Range<usize>| {
+            if *span == range {
+                span.end = span.start + with.len();
+            } else if span.start >= range.end {
+                span.start = span.start - range.len() + with.len();
+                span.end = span.end - range.len() + with.len();
+            }
+        };
+        adjust(&mut self.prefix);
+        adjust(&mut self.iri);
+        self.text.replace_range(range, with);
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Segment {
+    /// Whitespace, comments, base directives and the rest of the
+    /// document, kept as they are.
+    Text(String),
+    Declaration(Declaration),
+}
+
+/// A block of Turtle or SPARQL prefix declarations that can be edited
+/// without disturbing its formatting.
+///
+/// The block is read up to the first statement that is not a directive;
+/// that statement and everything after it is kept as it is. Comments,
+/// whitespace, the order of the declarations and the way each one is
+/// written are preserved, so that writing the block back out only
+/// changes what was edited.
+///
+/// # Examples
+///
+/// ```
+/// use curie::PrefixBlock;
+///
+/// let mut block = PrefixBlock::parse("\
+/// ## Vocabularies
+/// @prefix foaf:  <http://xmlns.com/foaf/0.1/> .   # people
+/// @prefix dc:    <http://purl.org/dc/elements/1.1/> .
+/// @prefix old:   <http://example.com/old#> .
+///
+/// <#me> a foaf:Person .
+/// ").unwrap();
+///
+/// block.set_prefix("dc", "http://purl.org/dc/terms/").unwrap();
+/// block.rename_prefix("foaf", "f").unwrap();
+/// block.remove_prefix("old");
+/// block.set_prefix("ex", "http://example.com/").unwrap();
+///
+/// assert_eq!(block.to_string(), "\
+/// ## Vocabularies
+/// @prefix f:  <http://xmlns.com/foaf/0.1/> .   # people
+/// @prefix dc:    <http://purl.org/dc/terms/> .
+/// @prefix ex: <http://example.com/> .
+///
+/// <#me> a foaf:Person .
+/// ");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct PrefixBlock {
+    segments: Vec<Segment>,
+}
+
+impl PrefixBlock {
+    /// Read the prefix declarations at the start of a Turtle, TriG or
+    /// SPARQL document.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] if a directive is malformed.
+    pub fn parse(text: &str) -> Result<PrefixBlock, ParseError> {
+        let mut block = PrefixBlock {
+            segments: Vec::new(),
+        };
+        let mut pos = 0;
+        while pos < text.len() {
+            let space = skip_space(&text[pos..], true).unwrap_or(0);
+            block.push_text(&text[pos..pos + space]);
+            pos += space;
+            if pos == text.len() {
+                break;
+            }
+            match parse_directive(&text[pos..], true) {
+                Ok(Some(ParsedDirective {
+                    directive: Directive::Prefix { iri, .. },
+                    len,
+                    prefix,
+                    iri: iri_span,
+                })) => {
+                    block.segments.push(Segment::Declaration(Declaration {
+                        text: String::from(&text[pos..pos + len]),
+                        prefix,
+                        iri: iri_span,
+                        namespace: iri,
+                    }));
+                    pos += len;
+                }
+                Ok(Some(ParsedDirective { len, .. })) => {
+                    block.push_text(&text[pos..pos + len]);
+                    pos += len;
+                }
+                Ok(None) => {
+                    block.push_text(&text[pos..]);
+                    break;
+                }
+                Err(Stop::Incomplete) => {
+                    let end = text.len();
+                    return Err(ParseError::at(
+                        ParseErrorKind::UnexpectedEnd,
+                        text,
+                        end,
+                        end,
+                    ));
+                }
+                Err(Stop::Error(kind, start, end)) => {
+                    return Err(ParseError::at(kind, text, pos + start, pos + end));
+                }
+            }
+        }
+        Ok(block)
+    }
+
+    /// Iterate over the declared prefixes and their namespaces, in the
+    /// order they are declared.
+    pub fn declarations(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.declarations_iter()
+            .map(|declaration| (declaration.prefix(), declaration.namespace.as_str()))
+    }
+
+    /// The namespace that a prefix is declared as, if it is declared.
+    ///
+    /// When a prefix is declared more than once, the last declaration
+    /// is the one that applies.
+    #[must_use]
+    pub fn namespace(&self, prefix: &str) -> Option<&str> {
+        self.declarations()
+            .filter(|&(declared, _)| declared == prefix)
+            .last()
+            .map(|(_, namespace)| namespace)
+    }
+
+    /// Declare a prefix.
+    ///
+    /// If the prefix is already declared, only the IRI of its last
+    /// declaration is rewritten. Otherwise, a declaration is added on
+    /// a new line after the last one, written in the same style.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseErrorKind::InvalidPrefix`] or
+    /// [`ParseErrorKind::InvalidIri`] if the prefix or namespace can't
+    /// be written in a declaration.
+    pub fn set_prefix(&mut self, prefix: &str, namespace: &str) -> Result<(), ParseErrorKind> {
+        if !syntax::is_pn_prefix(prefix) {
+            return Err(ParseErrorKind::InvalidPrefix);
+        }
+        if syntax::find_invalid_iri_char(namespace).is_some() {
+            return Err(ParseErrorKind::InvalidIri);
+        }
+        let iri = format!("<{namespace}>");
+        if let Some(index) = self.last_declaration(prefix) {
+            if let Segment::Declaration(ref mut declaration) = self.segments[index] {
+                declaration.splice(declaration.iri.clone(), &iri);
+                declaration.namespace = String::from(namespace);
+            }
+            return Ok(());
+        }
+
+        let last = self
+            .segments
+            .iter()
+            .rposition(|segment| matches!(segment, Segment::Declaration(_)));
+        let (keyword, terminator, index) = match last {
+            Some(index) => {
+                let Segment::Declaration(ref declaration) = self.segments[index] else {
+                    unreachable!()
+                };
+                (
+                    &declaration.text[..declaration.prefix.start],
+                    &declaration.text[declaration.iri.end..],
+                    index,
+                )
+            }
+            None => ("@prefix ", " .", 0),
+        };
+        let text = format!("{keyword}{prefix}: {iri}{terminator}");
+        let declaration = Declaration {
+            prefix: keyword.len()..keyword.len() + prefix.len(),
+            iri: keyword.len() + prefix.len() + 2..keyword.len() + prefix.len() + 2 + iri.len(),
+            text,
+            namespace: String::from(namespace),
+        };
+        match last {
+            Some(_) => {
+                let indent = self.indent_before(index);
+                self.segments
+                    .insert(index + 1, Segment::Text(format!("\n{indent}")));
+                self.segments
+                    .insert(index + 2, Segment::Declaration(declaration));
+            }
+            None => {
+                self.segments.insert(0, Segment::Declaration(declaration));
+                self.segments.insert(1, Segment::Text(String::from("\n")));
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove every declaration of a prefix.
+    ///
+    /// When a declaration is alone on its line, the whole line is
+    /// removed, including any comment following it.
+    ///
+    /// Returns `false` if the prefix was not declared.
+    pub fn remove_prefix(&mut self, prefix: &str) -> bool {
+        let mut removed = false;
+        while let Some(index) = self.last_declaration(prefix) {
+            self.remove_declaration(index);
+            removed = true;
+        }
+        removed
+    }
+
+    /// Rename a prefix in every declaration of it.
+    ///
+    /// Only the prefix is rewritten; uses of the prefix in the rest of
+    /// the document are not changed.
+    ///
+    /// Returns `Ok(false)` if `old` is not declared or `new` is already
+    /// declared, in which case nothing is changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseErrorKind::InvalidPrefix`] if `new` can't be
+    /// written in a declaration.
+    pub fn rename_prefix(&mut self, old: &str, new: &str) -> Result<bool, ParseErrorKind> {
+        if !syntax::is_pn_prefix(new) {
+            return Err(ParseErrorKind::InvalidPrefix);
+        }
+        if self.namespace(old).is_none() || self.namespace(new).is_some() {
+            return Ok(false);
+        }
+        for segment in &mut self.segments {
+            if let Segment::Declaration(declaration) = segment {
+                if declaration.prefix() == old {
+                    declaration.splice(declaration.prefix.clone(), new);
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    fn declarations_iter(&self) -> impl Iterator<Item = &Declaration> {
+        self.segments.iter().filter_map(|segment| match segment {
+            Segment::Declaration(declaration) => Some(declaration),
+            Segment::Text(_) => None,
+        })
+    }
+
+    fn last_declaration(&self, prefix: &str) -> Option<usize> {
+        self.segments.iter().rposition(
+            |segment| matches!(segment, Segment::Declaration(declaration) if declaration.prefix() == prefix),
+        )
+    }
+
+    fn push_text(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        match self.segments.last_mut() {
+            Some(Segment::Text(last)) => last.push_str(text),
+            _ => self.segments.push(Segment::Text(String::from(text))),
+        }
+    }
+
+    /// The text before a segment on its line, if it is only whitespace.
+    fn indent_before(&self, index: usize) -> String {
+        match index.checked_sub(1).map(|before| &self.segments[before]) {
+            Some(Segment::Text(text)) => {
+                let line = text.rsplit('\n').next().unwrap_or("");
+                if line.trim().is_empty() && (text.contains('\n') || index == 1) {
+                    String::from(line)
+                } else {
+                    String::new()
+                }
+            }
+            _ => String::new(),
+        }
+    }
+
+    fn remove_declaration(&mut self, index: usize) {
+        self.segments.remove(index);
+
+        // The text on either side of the declaration, now adjacent.
+        let before = index
+            .checked_sub(1)
+            .filter(|&before| matches!(self.segments[before], Segment::Text(_)));
+        let after = (index < self.segments.len()
+            && matches!(self.segments[index], Segment::Text(_)))
+        .then_some(index);
+
+        let starts_line = match before {
+            Some(before) => {
+                let Segment::Text(ref text) = self.segments[before] else {
+                    unreachable!()
+                };
+                let line = text.rsplit('\n').next().unwrap_or("");
+                line.trim().is_empty() && (text.contains('\n') || before == 0)
+            }
+            None => index == 0,
+        };
+        let line_rest = after.map(|after| {
+            let Segment::Text(ref text) = self.segments[after] else {
+                unreachable!()
+            };
+            let end = text.find('\n').map_or(text.len(), |idx| idx + 1);
+            let rest = &text[..end];
+            let only_comment = rest.trim().is_empty() || rest.trim_start().starts_with('#');
+            (end, only_comment)
+        });
+        let ends_line = match line_rest {
+            Some((_, only_comment)) => only_comment,
+            None => true,
+        };
+
+        if starts_line && ends_line {
+            // Remove the whole line.
+            if let Some(before) = before {
+                if let Segment::Text(ref mut text) = self.segments[before] {
+                    let keep = text.rfind('\n').map_or(0, |idx| idx + 1);
+                    text.truncate(keep);
+                }
+            }
+            if let (Some(after), Some((end, _))) = (after, line_rest) {
+                if let Segment::Text(ref mut text) = self.segments[after] {
+                    text.replace_range(..end, "");
+                }
+            }
+        } else if let Some(after) = after {
+            // Remove the space separating it from what follows.
+            if let Segment::Text(ref mut text) = self.segments[after] {
+                let space = text.len() - text.trim_start_matches([' ', '\t']).len();
+                text.replace_range(..space, "");
+            }
+        }
+
+        // Merge the text on either side.
+        if let (Some(before), Some(after)) = (before, after) {
+            if let Segment::Text(text) = self.segments.remove(after) {
+                if let Segment::Text(ref mut before) = self.segments[before] {
+                    before.push_str(&text);
+                }
+            }
+        }
+        self.segments
+            .retain(|segment| !matches!(segment, Segment::Text(text) if text.is_empty()));
+    }
+}
+
+impl fmt::Display for PrefixBlock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for segment in &self.segments {
+            match segment {
+                Segment::Text(text) => f.write_str(text)?,
+                Segment::Declaration(declaration) => f.write_str(&declaration.text)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPARQL: &str = "PREFIX a: <http://example.com/a/>\n\
+                          \x20 prefix b: <http://example.com/b/> # b\n\
+                          BASE <http://example.com/>\n\
+                          SELECT * WHERE { ?s a:p ?o }\n";
+
+    #[test]
+    fn round_trip() {
+        let block = PrefixBlock::parse(SPARQL).unwrap();
+        assert_eq!(block.to_string(), SPARQL);
+        assert_eq!(
+            block.declarations().collect::<Vec<_>>(),
+            [
+                ("a", "http://example.com/a/"),
+                ("b", "http://example.com/b/")
+            ]
+        );
+    }
+
+    #[test]
+    fn add_in_same_style() {
+        let mut block = PrefixBlock::parse(SPARQL).unwrap();
+        block.set_prefix("c", "http://example.com/c/").unwrap();
+        assert_eq!(
+            block.to_string(),
+            "PREFIX a: <http://example.com/a/>\n\
+             \x20 prefix b: <http://example.com/b/>\n\
+             \x20 prefix c: <http://example.com/c/> # b\n\
+             BASE <http://example.com/>\n\
+             SELECT * WHERE { ?s a:p ?o }\n"
+        );
+
+        let mut block = PrefixBlock::parse("<a> <b> <c> .\n").unwrap();
+        block.set_prefix("", "http://example.com/").unwrap();
+        assert_eq!(
+            block.to_string(),
+            "@prefix : <http://example.com/> .\n<a> <b> <c> .\n"
+        );
+    }
+
+    #[test]
+    fn remove() {
+        let mut block = PrefixBlock::parse(SPARQL).unwrap();
+        assert!(block.remove_prefix("b"));
+        assert!(!block.remove_prefix("b"));
+        assert_eq!(
+            block.to_string(),
+            "PREFIX a: <http://example.com/a/>\n\
+             BASE <http://example.com/>\n\
+             SELECT * WHERE { ?s a:p ?o }\n"
+        );
+
+        let mut block =
+            PrefixBlock::parse("@prefix a: <x:a> . @prefix b: <x:b> .\n@prefix a: <x:c> .")
+                .unwrap();
+        assert!(block.remove_prefix("a"));
+        assert_eq!(block.to_string(), "@prefix b: <x:b> .\n");
+    }
+
+    #[test]
+    fn rename_and_errors() {
+        let mut block = PrefixBlock::parse(SPARQL).unwrap();
+        assert_eq!(block.rename_prefix("a", "b"), Ok(false));
+        assert_eq!(
+            block.rename_prefix("a", "_a"),
+            Err(ParseErrorKind::InvalidPrefix)
+        );
+        assert_eq!(block.rename_prefix("a", "alpha"), Ok(true));
+        block.set_prefix("alpha", "http://example.com/α/").unwrap();
+        assert_eq!(block.namespace("alpha"), Some("http://example.com/α/"));
+        assert!(block
+            .to_string()
+            .starts_with("PREFIX alpha: <http://example.com/α/>\n"));
+        assert_eq!(
+            block.set_prefix("c", "not an iri"),
+            Err(ParseErrorKind::InvalidIri)
+        );
+
+        let err = PrefixBlock::parse("@prefix a: <x:a>").unwrap_err();
+        assert_eq!(err.kind(), ParseErrorKind::UnexpectedEnd);
+    }
+}
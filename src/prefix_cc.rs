@@ -0,0 +1,303 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Looking up prefixes on [prefix.cc](https://prefix.cc/).
+//!
+//! [`PrefixCcResolver`] builds the prefix.cc lookup URLs and reads the
+//! responses, caching what it learns. It doesn't make the requests
+//! itself: it is given a function that fetches a URL, so that any HTTP
+//! client, blocking or driven by an async runtime, can be used and so
+//! that no network access happens unless a program asks for it.
+//!
+//! This requires the `prefix-cc` feature.
+
+use crate::{PrefixMapping, Registry};
+use std::collections::HashSet;
+use std::fmt;
+use std::fmt::Write;
+
+/// An error when looking up a prefix on prefix.cc.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PrefixCcError<E> {
+    /// Fetching a URL failed.
+    Fetch(E),
+    /// The response is not a JSON object of prefixes and namespaces.
+    InvalidResponse,
+}
+
+impl<E: fmt::Display> fmt::Display for PrefixCcError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PrefixCcError::Fetch(err) => write!(f, "can't fetch from prefix.cc: {err}"),
+            PrefixCcError::InvalidResponse => f.write_str("invalid response from prefix.cc"),
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> std::error::Error for PrefixCcError<E> {}
+
+/// Looks up prefixes and namespaces on prefix.cc, caching the results.
+///
+/// The `fetch` function is called with a URL and returns the body of
+/// the response, or `None` when prefix.cc has no entry (a 404
+/// response). Lookups that found nothing are cached too, so each
+/// prefix or namespace is fetched at most once.
+///
+/// # Examples
+///
+/// ```
+/// use curie::prefix_cc::PrefixCcResolver;
+/// use curie::PrefixMapping;
+///
+/// // A real program would make an HTTP request here.
+/// let fetch = |url: &str| -> Result<Option<String>, std::io::Error> {
+///     assert_eq!(url, "https://prefix.cc/foaf.file.json");
+///     Ok(Some(String::from(r#"{"foaf": "http://xmlns.com/foaf/0.1/"}"#)))
+/// };
+///
+/// let mut resolver = PrefixCcResolver::new(fetch);
+/// let mut mapping = PrefixMapping::default();
+/// assert_eq!(resolver.add_unknown_prefixes(&mut mapping, ["foaf"]).unwrap(), 1);
+/// assert_eq!(mapping.expand_curie_string("foaf:Agent"),
+///            Ok(String::from("http://xmlns.com/foaf/0.1/Agent")));
+/// ```
+pub struct PrefixCcResolver<F> {
+    fetch: F,
+    cache: Registry,
+    missing: HashSet<String>,
+}
+
+impl<F, E> PrefixCcResolver<F>
+where
+    F: FnMut(&str) -> Result<Option<String>, E>,
+{
+    /// Construct a resolver that fetches URLs with `fetch`.
+    pub fn new(fetch: F) -> Self {
+        PrefixCcResolver::with_cache(fetch, Registry::new())
+    }
+
+    /// Construct a resolver with a cache of known prefixes, such as
+    /// one saved from an earlier run.
+    pub fn with_cache(fetch: F, cache: Registry) -> Self {
+        PrefixCcResolver {
+            fetch,
+            cache,
+            missing: HashSet::new(),
+        }
+    }
+
+    /// The prefixes that have been found so far.
+    #[must_use]
+    pub fn cache(&self) -> &Registry {
+        &self.cache
+    }
+
+    /// Look up the namespace for `prefix`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when fetching fails or the response can't be
+    /// read.
+    pub fn namespace(&mut self, prefix: &str) -> Result<Option<&str>, PrefixCcError<E>> {
+        if self.cache.namespace(prefix).is_none() && !self.missing.contains(prefix) {
+            let url = format!("https://prefix.cc/{}.file.json", encode(prefix));
+            match self.lookup(&url)? {
+                Some(found) => {
+                    for (found_prefix, namespace) in &found {
+                        self.cache.insert(found_prefix, namespace);
+                    }
+                    if !found.iter().any(|(found_prefix, _)| found_prefix == prefix) {
+                        self.missing.insert(String::from(prefix));
+                    }
+                }
+                None => {
+                    self.missing.insert(String::from(prefix));
+                }
+            }
+        }
+        Ok(self.cache.namespace(prefix))
+    }
+
+    /// Look up the preferred prefix for `namespace`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when fetching fails or the response can't be
+    /// read.
+    pub fn suggest_prefix(&mut self, namespace: &str) -> Result<Option<&str>, PrefixCcError<E>> {
+        if self.cached_prefix(namespace).is_none() && !self.missing.contains(namespace) {
+            let url = format!(
+                "https://prefix.cc/reverse?uri={}&format=json",
+                encode(namespace)
+            );
+            match self
+                .lookup(&url)?
+                .and_then(|found| found.into_iter().next())
+            {
+                Some((prefix, found_namespace)) if found_namespace == namespace => {
+                    self.cache.insert(&prefix, &found_namespace);
+                }
+                _ => {
+                    self.missing.insert(String::from(namespace));
+                }
+            }
+        }
+        Ok(self.cached_prefix(namespace))
+    }
+
+    /// Add the prefixes that `mapping` doesn't have, when prefix.cc
+    /// knows them.
+    ///
+    /// Prefixes that are already bound, to a namespace or a URI
+    /// template, are never looked up or changed.
+    /// Returns the number of prefixes added.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when fetching fails or a response can't be
+    /// read. Prefixes looked up before the error are still added.
+    pub fn add_unknown_prefixes<'a, I>(
+        &mut self,
+        mapping: &mut PrefixMapping,
+        prefixes: I,
+    ) -> Result<usize, PrefixCcError<E>>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut added = 0;
+        for prefix in prefixes {
+            if mapping.contains_prefix(prefix) {
+                continue;
+            }
+            if let Some(namespace) = self.namespace(prefix)? {
+                if mapping.add_prefix(prefix, namespace).is_ok() {
+                    added += 1;
+                }
+            }
+        }
+        Ok(added)
+    }
+
+    fn cached_prefix(&self, namespace: &str) -> Option<&str> {
+        self.cache
+            .iter()
+            .find(|&(_, cached)| cached == namespace)
+            .map(|(prefix, _)| prefix)
+    }
+
+    fn lookup(&mut self, url: &str) -> Result<Option<Vec<(String, String)>>, PrefixCcError<E>> {
+        match (self.fetch)(url).map_err(PrefixCcError::Fetch)? {
+            Some(body) => parse_response(&body).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Read a prefix.cc JSON response, an object of prefixes and namespaces.
+fn parse_response<E>(body: &str) -> Result<Vec<(String, String)>, PrefixCcError<E>> {
+    let value: serde_json::Value =
+        serde_json::from_str(body).map_err(|_| PrefixCcError::InvalidResponse)?;
+    let serde_json::Value::Object(object) = value else {
+        return Err(PrefixCcError::InvalidResponse);
+    };
+    object
+        .into_iter()
+        .map(|(prefix, namespace)| match namespace {
+            serde_json::Value::String(namespace) => Ok((prefix, namespace)),
+            _ => Err(PrefixCcError::InvalidResponse),
+        })
+        .collect()
+}
+
+/// Percent-encode everything but unreserved characters for use in a URL.
+fn encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            encoded.push(char::from(byte));
+        } else {
+            let _ = write!(encoded, "%{byte:02X}");
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[test]
+    fn lookups_are_cached() {
+        let requests = RefCell::new(Vec::new());
+        let fetch = |url: &str| -> Result<Option<String>, String> {
+            requests.borrow_mut().push(String::from(url));
+            Ok(match url {
+                "https://prefix.cc/foaf.file.json" => {
+                    Some(String::from(r#"{"foaf": "http://xmlns.com/foaf/0.1/"}"#))
+                }
+                "https://prefix.cc/reverse?uri=http%3A%2F%2Fschema.org%2F&format=json" => {
+                    Some(String::from(r#"{"schema": "http://schema.org/"}"#))
+                }
+                "https://prefix.cc/bad.file.json" => Some(String::from("[]")),
+                _ => None,
+            })
+        };
+        let mut resolver = PrefixCcResolver::new(fetch);
+
+        assert_eq!(
+            resolver.namespace("foaf"),
+            Ok(Some("http://xmlns.com/foaf/0.1/"))
+        );
+        assert_eq!(
+            resolver.namespace("foaf"),
+            Ok(Some("http://xmlns.com/foaf/0.1/"))
+        );
+        assert_eq!(resolver.namespace("nope"), Ok(None));
+        assert_eq!(resolver.namespace("nope"), Ok(None));
+        assert_eq!(
+            resolver.suggest_prefix("http://schema.org/"),
+            Ok(Some("schema"))
+        );
+        assert_eq!(resolver.namespace("schema"), Ok(Some("http://schema.org/")));
+        assert_eq!(
+            resolver.suggest_prefix("http://xmlns.com/foaf/0.1/"),
+            Ok(Some("foaf"))
+        );
+        assert_eq!(
+            resolver.namespace("bad"),
+            Err(PrefixCcError::InvalidResponse)
+        );
+        assert_eq!(requests.borrow().len(), 4);
+
+        let mut mapping = PrefixMapping::default();
+        mapping.add_prefix("schema", "https://schema.org/").unwrap();
+        assert_eq!(
+            resolver.add_unknown_prefixes(&mut mapping, ["foaf", "schema", "nope"]),
+            Ok(1)
+        );
+        assert_eq!(
+            mapping.expand_curie_string("schema:Person"),
+            Ok(String::from("https://schema.org/Person"))
+        );
+    }
+
+    #[test]
+    fn fetch_errors() {
+        let mut resolver = PrefixCcResolver::new(|_: &str| Err("offline"));
+        let err = resolver.namespace("foaf").unwrap_err();
+        assert_eq!(err, PrefixCcError::Fetch("offline"));
+        assert_eq!(err.to_string(), "can't fetch from prefix.cc: offline");
+
+        // Bound prefixes, including templates, aren't fetched.
+        let mut mapping = PrefixMapping::default();
+        mapping
+            .add_prefix_template("doi", "https://doi.org/{id}")
+            .unwrap();
+        assert_eq!(resolver.add_unknown_prefixes(&mut mapping, ["doi"]), Ok(0));
+        assert!(mapping.prefix_template("doi").is_some());
+    }
+}
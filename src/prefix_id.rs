@@ -0,0 +1,167 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{ExpansionError, PrefixMapping};
+
+/// A small, stable handle for a prefix registered in a [`PrefixMapping`].
+///
+/// Storing a `PrefixId` rather than the prefix itself lets syntax
+/// trees and other large structures refer to a prefix in 4 bytes.
+///
+/// A prefix keeps the same id for the lifetime of the mapping, even
+/// if it is removed and added again. Ids are only meaningful for the
+/// mapping that issued them.
+///
+/// # Examples
+///
+/// ```
+/// use curie::PrefixMapping;
+///
+/// let mut mapping = PrefixMapping::default();
+/// mapping.add_prefix("foaf", "http://xmlns.com/foaf/0.1/").unwrap();
+///
+/// let foaf = mapping.prefix_id("foaf").unwrap();
+/// assert_eq!(mapping.prefix_for_id(foaf), Some("foaf"));
+/// assert_eq!(mapping.expand_prefix_id(foaf, "Agent"),
+///            Ok(String::from("http://xmlns.com/foaf/0.1/Agent")));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct PrefixId(u32);
+
+impl PrefixId {
+    /// Construct a `PrefixId` from its raw value.
+    ///
+    /// This is intended for restoring ids that were previously
+    /// obtained with [`PrefixId::as_u32()`].
+    #[must_use]
+    pub fn from_u32(id: u32) -> Self {
+        PrefixId(id)
+    }
+
+    /// The raw value of this id.
+    #[must_use]
+    pub fn as_u32(self) -> u32 {
+        self.0
+    }
+}
+
+impl PrefixMapping {
+    /// Get the [`PrefixId`] for a prefix.
+    ///
+    /// Returns `None` if the prefix is not currently bound, to a
+    /// namespace or a URI template.
+    ///
+    /// # See also
+    ///
+    /// * [`PrefixMapping::prefix_for_id()`]
+    /// * [`PrefixMapping::expand_prefix_id()`]
+    #[must_use]
+    pub fn prefix_id(&self, prefix: &str) -> Option<PrefixId> {
+        if !self.contains_prefix(prefix) {
+            return None;
+        }
+        self.prefix_ids
+            .get_index_of(prefix)
+            .and_then(|index| u32::try_from(index).ok())
+            .map(PrefixId)
+    }
+
+    /// Get the prefix that a [`PrefixId`] stands for.
+    ///
+    /// Returns `None` if the prefix is not currently bound.
+    ///
+    /// # See also
+    ///
+    /// * [`PrefixMapping::prefix_id()`]
+    #[must_use]
+    pub fn prefix_for_id(&self, id: PrefixId) -> Option<&str> {
+        self.prefix_ids
+            .get_index(id.0 as usize)
+            .filter(|prefix| self.contains_prefix(prefix))
+            .map(String::as_str)
+    }
+
+    /// Expand the prefix identified by a [`PrefixId`] and a reference,
+    /// returning a complete IRI.
+    ///
+    /// # Errors
+    ///
+    /// This will return [`ExpansionError::Invalid`] if the prefix is
+    /// not currently bound.
+    ///
+    /// # See also
+    ///
+    /// * [`PrefixMapping::expand_curie()`]
+    pub fn expand_prefix_id(
+        &self,
+        id: PrefixId,
+        reference: &str,
+    ) -> Result<String, ExpansionError> {
+        let prefix = self.prefix_for_id(id).ok_or(ExpansionError::Invalid)?;
+        self.expand_exploded_curie(Some(prefix), reference)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_are_stable() {
+        let mut mapping = PrefixMapping::default();
+        mapping.add_prefix("a", "http://example.com/a/").unwrap();
+        mapping.add_prefix("b", "http://example.com/b/").unwrap();
+
+        let a = mapping.prefix_id("a").unwrap();
+        let b = mapping.prefix_id("b").unwrap();
+        assert_ne!(a, b);
+
+        // Removing a prefix invalidates its id, but not others.
+        mapping.remove_prefix("a").unwrap();
+        assert_eq!(mapping.prefix_id("a"), None);
+        assert_eq!(mapping.prefix_for_id(a), None);
+        assert_eq!(
+            mapping.expand_prefix_id(a, "thing"),
+            Err(ExpansionError::Invalid)
+        );
+        assert_eq!(mapping.prefix_id("b"), Some(b));
+
+        // Adding it back, even with a new namespace, restores its id.
+        mapping.add_prefix("a", "http://example.org/a/").unwrap();
+        assert_eq!(mapping.prefix_id("a"), Some(a));
+        assert_eq!(
+            mapping.expand_prefix_id(a, "thing"),
+            Ok(String::from("http://example.org/a/thing"))
+        );
+    }
+
+    #[test]
+    fn template_prefixes() {
+        let mut mapping = PrefixMapping::default();
+        mapping
+            .add_prefix_template("doi", "https://doi.org/{id}")
+            .unwrap();
+        let doi = mapping.prefix_id("doi").unwrap();
+        assert_eq!(mapping.prefix_for_id(doi), Some("doi"));
+        assert_eq!(
+            mapping.expand_prefix_id(doi, "10.1000/182"),
+            Ok(String::from("https://doi.org/10.1000/182"))
+        );
+
+        // Binding the prefix to a namespace instead keeps its id.
+        mapping.add_prefix("doi", "https://doi.org/").unwrap();
+        assert_eq!(mapping.prefix_id("doi"), Some(doi));
+    }
+
+    #[test]
+    fn ids_do_not_affect_equality() {
+        let mut m1 = PrefixMapping::default();
+        m1.add_prefix("a", "http://example.com/a/").unwrap();
+        m1.remove_prefix("a").unwrap();
+
+        assert_eq!(m1, PrefixMapping::default());
+    }
+}
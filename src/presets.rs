@@ -0,0 +1,186 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Ready-made mappings of the prefixes used in particular domains.
+//!
+//! The core vocabularies that nearly every RDF document binds are
+//! always available from [`common()`]. Each domain is behind its own
+//! feature, so that only the tables that are needed are built into a
+//! program:
+//!
+//! * `preset-bio`: [`bio()`], for OBO ontologies and `UniProt`.
+//! * `preset-geo`: [`geo()`], for `GeoSPARQL`, WGS84 and `GeoNames`.
+//! * `preset-library`: [`library()`], for BIBFRAME, MARC relators and
+//!   other bibliographic vocabularies.
+//! * `preset-gov`: [`gov()`], for DCAT, ADMS and other vocabularies used
+//!   by open government data portals.
+//!
+//! ```
+//! # #[cfg(feature = "preset-geo")] {
+//! use curie::presets;
+//!
+//! let mapping = presets::geo();
+//! assert_eq!(mapping.expand_curie_string("geo:asWKT"),
+//!            Ok(String::from("http://www.opengis.net/ont/geosparql#asWKT")));
+//! # }
+//! ```
+
+use crate::PrefixMapping;
+
+/// Build a mapping from a table of valid prefixes.
+fn from_table(table: &[(&str, &str)]) -> PrefixMapping {
+    let mut mapping = PrefixMapping::default();
+    for &(prefix, namespace) in table {
+        mapping
+            .add_prefix(prefix, namespace)
+            .expect("preset prefixes are valid");
+    }
+    mapping
+}
+
+/// Prefixes for the core vocabularies: `rdf`, `rdfs`, `owl`, `xsd`,
+/// `skos`, `foaf` and `schema`.
+///
+/// ```
+/// use curie::presets;
+///
+/// let mapping = presets::common();
+/// assert_eq!(mapping.expand_curie_string("xsd:dateTime"),
+///            Ok(String::from("http://www.w3.org/2001/XMLSchema#dateTime")));
+/// ```
+#[must_use]
+pub fn common() -> PrefixMapping {
+    from_table(&[
+        ("rdf", "http://www.w3.org/1999/02/22-rdf-syntax-ns#"),
+        ("rdfs", "http://www.w3.org/2000/01/rdf-schema#"),
+        ("owl", "http://www.w3.org/2002/07/owl#"),
+        ("xsd", "http://www.w3.org/2001/XMLSchema#"),
+        ("skos", "http://www.w3.org/2004/02/skos/core#"),
+        ("foaf", "http://xmlns.com/foaf/0.1/"),
+        ("schema", "https://schema.org/"),
+    ])
+}
+
+/// Prefixes for the life sciences: the OBO Foundry ontologies, using
+/// their conventional upper-case prefixes, and `UniProt`.
+///
+/// This requires the `preset-bio` feature.
+#[cfg(feature = "preset-bio")]
+#[must_use]
+pub fn bio() -> PrefixMapping {
+    from_table(&[
+        ("obo", "http://purl.obolibrary.org/obo/"),
+        ("oboInOwl", "http://www.geneontology.org/formats/oboInOwl#"),
+        ("BFO", "http://purl.obolibrary.org/obo/BFO_"),
+        ("CHEBI", "http://purl.obolibrary.org/obo/CHEBI_"),
+        ("CL", "http://purl.obolibrary.org/obo/CL_"),
+        ("GO", "http://purl.obolibrary.org/obo/GO_"),
+        ("HP", "http://purl.obolibrary.org/obo/HP_"),
+        ("MONDO", "http://purl.obolibrary.org/obo/MONDO_"),
+        ("NCBITaxon", "http://purl.obolibrary.org/obo/NCBITaxon_"),
+        ("PATO", "http://purl.obolibrary.org/obo/PATO_"),
+        ("PR", "http://purl.obolibrary.org/obo/PR_"),
+        ("RO", "http://purl.obolibrary.org/obo/RO_"),
+        ("SO", "http://purl.obolibrary.org/obo/SO_"),
+        ("UBERON", "http://purl.obolibrary.org/obo/UBERON_"),
+        ("uniprot", "http://purl.uniprot.org/uniprot/"),
+        ("up", "http://purl.uniprot.org/core/"),
+    ])
+}
+
+/// Prefixes for geospatial data: `GeoSPARQL` and its functions and
+/// Simple Features, the W3C WGS84 vocabulary and `GeoNames`.
+///
+/// This requires the `preset-geo` feature.
+#[cfg(feature = "preset-geo")]
+#[must_use]
+pub fn geo() -> PrefixMapping {
+    from_table(&[
+        ("geo", "http://www.opengis.net/ont/geosparql#"),
+        ("geof", "http://www.opengis.net/def/function/geosparql/"),
+        ("sf", "http://www.opengis.net/ont/sf#"),
+        ("uom", "http://www.opengis.net/def/uom/OGC/1.0/"),
+        ("wgs84", "http://www.w3.org/2003/01/geo/wgs84_pos#"),
+        ("gn", "http://www.geonames.org/ontology#"),
+    ])
+}
+
+/// Prefixes for library and bibliographic data: BIBFRAME, the MARC
+/// relators, MADS, BIBO, FRBR and DCMI terms.
+///
+/// This requires the `preset-library` feature.
+#[cfg(feature = "preset-library")]
+#[must_use]
+pub fn library() -> PrefixMapping {
+    from_table(&[
+        ("bf", "http://id.loc.gov/ontologies/bibframe/"),
+        ("bflc", "http://id.loc.gov/ontologies/bflc/"),
+        ("relators", "http://id.loc.gov/vocabulary/relators/"),
+        ("madsrdf", "http://www.loc.gov/mads/rdf/v1#"),
+        ("bibo", "http://purl.org/ontology/bibo/"),
+        ("frbr", "http://purl.org/vocab/frbr/core#"),
+        ("dcterms", "http://purl.org/dc/terms/"),
+    ])
+}
+
+/// Prefixes for open government data: DCAT and DCAT-AP, ADMS, and the
+/// vocabularies they build upon.
+///
+/// This requires the `preset-gov` feature.
+#[cfg(feature = "preset-gov")]
+#[must_use]
+pub fn gov() -> PrefixMapping {
+    from_table(&[
+        ("dcat", "http://www.w3.org/ns/dcat#"),
+        ("dcatap", "http://data.europa.eu/r5r/"),
+        ("adms", "http://www.w3.org/ns/adms#"),
+        ("dcterms", "http://purl.org/dc/terms/"),
+        ("foaf", "http://xmlns.com/foaf/0.1/"),
+        ("locn", "http://www.w3.org/ns/locn#"),
+        ("org", "http://www.w3.org/ns/org#"),
+        ("skos", "http://www.w3.org/2004/02/skos/core#"),
+        ("spdx", "http://spdx.org/rdf/terms#"),
+        ("vcard", "http://www.w3.org/2006/vcard/ns#"),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn common_prefixes() {
+        let mapping = common();
+        assert_eq!(mapping.mappings().count(), 7);
+        assert_eq!(
+            mapping.shrink_iri("http://www.w3.org/1999/02/22-rdf-syntax-ns#type"),
+            Ok(crate::Curie::new(Some("rdf"), "type"))
+        );
+    }
+
+    #[test]
+    #[cfg(all(
+        feature = "preset-bio",
+        feature = "preset-geo",
+        feature = "preset-library",
+        feature = "preset-gov"
+    ))]
+    fn presets() {
+        assert_eq!(
+            bio().expand_curie_string("GO:0008150"),
+            Ok(String::from("http://purl.obolibrary.org/obo/GO_0008150"))
+        );
+        assert_eq!(
+            geo().shrink_iri("http://www.w3.org/2003/01/geo/wgs84_pos#lat"),
+            Ok(crate::Curie::new(Some("wgs84"), "lat"))
+        );
+        assert_eq!(
+            library().expand_curie_string("relators:aut"),
+            Ok(String::from("http://id.loc.gov/vocabulary/relators/aut"))
+        );
+        assert_eq!(gov().mappings().count(), 10);
+    }
+}
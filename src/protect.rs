@@ -0,0 +1,163 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{InvalidPrefixError, PrefixMapping};
+
+impl PrefixMapping {
+    /// Protect the binding of a prefix.
+    ///
+    /// A protected prefix can't be rebound to a different value with
+    /// [`PrefixMapping::add_prefix()`] or removed with
+    /// [`PrefixMapping::remove_prefix()`]; both return
+    /// [`InvalidPrefixError::ProtectedPrefix`]. Adding the prefix again
+    /// with the same value is allowed. This keeps bindings that the
+    /// application relies upon intact when merging user-supplied prefixes.
+    ///
+    /// Returns `false` if the prefix is not mapped, in which case nothing
+    /// is protected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::{InvalidPrefixError, PrefixMapping};
+    ///
+    /// let mut mapping = PrefixMapping::default();
+    /// mapping.add_prefix("rdf", "http://www.w3.org/1999/02/22-rdf-syntax-ns#").unwrap();
+    /// assert!(mapping.protect_prefix("rdf"));
+    ///
+    /// assert_eq!(mapping.add_prefix("rdf", "http://example.com/"),
+    ///            Err(InvalidPrefixError::ProtectedPrefix));
+    /// assert_eq!(mapping.remove_prefix("rdf"),
+    ///            Err(InvalidPrefixError::ProtectedPrefix));
+    ///
+    /// // Forcing the change is still possible.
+    /// mapping.force_add_prefix("rdf", "http://example.com/").unwrap();
+    /// assert_eq!(mapping.expand_curie_string("rdf:type"),
+    ///            Ok(String::from("http://example.com/type")));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`PrefixMapping::unprotect_prefix()`]
+    /// * [`PrefixMapping::force_add_prefix()`]
+    /// * [`PrefixMapping::force_remove_prefix()`]
+    pub fn protect_prefix(&mut self, prefix: &str) -> bool {
+        if self.mapping.contains_key(prefix) {
+            if !self.protected.contains(prefix) {
+                self.protected.insert(String::from(prefix));
+            }
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Remove the protection from a prefix.
+    ///
+    /// Returns `false` if the prefix was not protected.
+    pub fn unprotect_prefix(&mut self, prefix: &str) -> bool {
+        self.protected.shift_remove(prefix)
+    }
+
+    /// Whether the binding of a prefix is protected.
+    #[must_use]
+    pub fn is_protected(&self, prefix: &str) -> bool {
+        self.protected.contains(prefix)
+    }
+
+    /// Iterate over the protected prefixes, in the order they were
+    /// protected.
+    pub fn protected_prefixes(&self) -> impl Iterator<Item = &str> {
+        self.protected.iter().map(String::as_str)
+    }
+
+    /// Add a prefix to the mapping, even if it is protected.
+    ///
+    /// The prefix stays protected, with its new value.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidPrefixError::ReservedPrefix`] when `prefix`
+    /// is `_`. In strict mode, it returns [`InvalidPrefixError::InvalidName`]
+    /// when `prefix` isn't an `NCName` and
+    /// [`InvalidPrefixError::InvalidNamespace`] when `value` isn't an
+    /// absolute IRI.
+    ///
+    /// # See also
+    ///
+    /// * [`PrefixMapping::add_prefix()`]
+    pub fn force_add_prefix(
+        &mut self,
+        prefix: &str,
+        value: &str,
+    ) -> Result<(), InvalidPrefixError> {
+        self.check_prefix(prefix)?;
+        self.check_namespace(value)?;
+        self.templates.shift_remove(prefix);
+        self.mapping
+            .insert(String::from(prefix), String::from(value));
+        if !self.prefix_ids.contains(prefix) {
+            self.prefix_ids.insert(String::from(prefix));
+        }
+        Ok(())
+    }
+
+    /// Remove a prefix from the mapping, even if it is protected,
+    /// returning the namespace it was bound to.
+    ///
+    /// Any protection of the prefix is removed along with it.
+    ///
+    /// # See also
+    ///
+    /// * [`PrefixMapping::remove_prefix()`]
+    pub fn force_remove_prefix(&mut self, prefix: &str) -> Option<String> {
+        self.protected.shift_remove(prefix);
+        self.templates.shift_remove(prefix);
+        self.mapping.shift_remove(prefix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn protected_prefixes() {
+        let mut mapping = PrefixMapping::default();
+        mapping.add_prefix("ex", "http://example.com/").unwrap();
+        assert!(!mapping.protect_prefix("missing"));
+        assert!(mapping.protect_prefix("ex"));
+        assert!(mapping.is_protected("ex"));
+
+        // Rebinding to the same value is not a change.
+        assert_eq!(mapping.add_prefix("ex", "http://example.com/"), Ok(()));
+        assert_eq!(
+            mapping.add_prefix("ex", "http://example.org/"),
+            Err(InvalidPrefixError::ProtectedPrefix)
+        );
+        assert_eq!(mapping.protected_prefixes().collect::<Vec<_>>(), ["ex"]);
+
+        assert_eq!(
+            mapping.force_remove_prefix("ex"),
+            Some(String::from("http://example.com/"))
+        );
+        assert!(!mapping.is_protected("ex"));
+        assert_eq!(mapping.add_prefix("ex", "http://example.org/"), Ok(()));
+    }
+
+    #[test]
+    fn unprotect() {
+        let mut mapping = PrefixMapping::default();
+        mapping.add_prefix("ex", "http://example.com/").unwrap();
+        mapping.protect_prefix("ex");
+        assert!(mapping.unprotect_prefix("ex"));
+        assert!(!mapping.unprotect_prefix("ex"));
+        assert_eq!(
+            mapping.remove_prefix("ex"),
+            Ok(Some(String::from("http://example.com/")))
+        );
+    }
+}
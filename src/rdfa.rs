@@ -0,0 +1,655 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Support for resolving values as an [RDFa] processor does.
+//!
+//! RDFa attributes such as `@property` and `@rel` accept a
+//! `TERMorCURIEorAbsIRI`: a bare term, a CURIE or an absolute IRI.
+//! Terms are resolved against a [`TermMapping`], while CURIEs are
+//! resolved against a [`PrefixMapping`].
+//!
+//! ```
+//! use curie::PrefixMapping;
+//! use curie::rdfa::TermMapping;
+//!
+//! let mut prefixes = PrefixMapping::default();
+//! prefixes.add_prefix("foaf", "http://xmlns.com/foaf/0.1/").unwrap();
+//!
+//! let mut terms = TermMapping::default();
+//! terms.add_term("license", "http://www.w3.org/1999/xhtml/vocab#license");
+//!
+//! assert_eq!(prefixes.resolve_term_or_curie_or_abs_iri("license", &terms),
+//!            Some(String::from("http://www.w3.org/1999/xhtml/vocab#license")));
+//! assert_eq!(prefixes.resolve_term_or_curie_or_abs_iri("foaf:name", &terms),
+//!            Some(String::from("http://xmlns.com/foaf/0.1/name")));
+//! assert_eq!(prefixes.resolve_term_or_curie_or_abs_iri("http://example.com/p", &terms),
+//!            Some(String::from("http://example.com/p")));
+//! ```
+//!
+//! The prefixes in scope for an element are found with
+//! [`PrefixMapping::apply_rdfa_element()`], which handles the `@prefix`
+//! attribute of RDFa 1.1 as well as the `xmlns:` attributes and
+//! `@profile` documents used by RDFa 1.0 markup. A `@prefix` attribute
+//! on its own is read with [`PrefixMapping::extend_from_rdfa_prefix()`]
+//! and written with [`PrefixMapping::to_rdfa_prefix()`]. Its
+//! declarations can be read without applying them with
+//! [`prefix_directives()`].
+//!
+//! [RDFa]: https://www.w3.org/TR/rdfa-core/
+
+use crate::turtle::Directive;
+use crate::{syntax, DeclarationStyle, PrefixFormatter, PrefixMapping};
+use std::fmt;
+use std::iter::FusedIterator;
+
+/// The term mappings and local default vocabulary used when
+/// resolving RDFa terms.
+///
+/// # Examples
+///
+/// ```
+/// use curie::rdfa::TermMapping;
+///
+/// let mut terms = TermMapping::default();
+/// terms.add_term("describedby", "http://www.w3.org/2007/05/powder-s#describedby");
+///
+/// // Terms are first matched case-sensitively, then case-insensitively.
+/// assert_eq!(terms.resolve_term("describedBy"),
+///            Some(String::from("http://www.w3.org/2007/05/powder-s#describedby")));
+///
+/// // Once a vocabulary is set, it is used for all terms.
+/// terms.set_vocabulary("http://schema.org/");
+/// assert_eq!(terms.resolve_term("describedby"),
+///            Some(String::from("http://schema.org/describedby")));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TermMapping {
+    vocabulary: Option<String>,
+    terms: indexmap::IndexMap<String, String>,
+}
+
+/// The prefixes of the [RDFa 1.1 initial context].
+///
+/// [RDFa 1.1 initial context]: https://www.w3.org/2011/rdfa-context/rdfa-1.1
+#[cfg(feature = "rdfa-initial-context")]
+const INITIAL_PREFIXES: &[(&str, &str)] = &[
+    ("as", "https://www.w3.org/ns/activitystreams#"),
+    ("cc", "http://creativecommons.org/ns#"),
+    ("csvw", "http://www.w3.org/ns/csvw#"),
+    ("ctag", "http://commontag.org/ns#"),
+    ("dc", "http://purl.org/dc/terms/"),
+    ("dc11", "http://purl.org/dc/elements/1.1/"),
+    ("dcat", "http://www.w3.org/ns/dcat#"),
+    ("dcterms", "http://purl.org/dc/terms/"),
+    ("dqv", "http://www.w3.org/ns/dqv#"),
+    ("duv", "https://www.w3.org/ns/duv#"),
+    ("foaf", "http://xmlns.com/foaf/0.1/"),
+    ("gr", "http://purl.org/goodrelations/v1#"),
+    ("grddl", "http://www.w3.org/2003/g/data-view#"),
+    ("ical", "http://www.w3.org/2002/12/cal/icaltzd#"),
+    ("jsonld", "http://www.w3.org/ns/json-ld#"),
+    ("ldp", "http://www.w3.org/ns/ldp#"),
+    ("ma", "http://www.w3.org/ns/ma-ont#"),
+    ("oa", "http://www.w3.org/ns/oa#"),
+    ("odrl", "http://www.w3.org/ns/odrl/2/"),
+    ("og", "http://ogp.me/ns#"),
+    ("org", "http://www.w3.org/ns/org#"),
+    ("owl", "http://www.w3.org/2002/07/owl#"),
+    ("prov", "http://www.w3.org/ns/prov#"),
+    ("qb", "http://purl.org/linked-data/cube#"),
+    ("rdf", "http://www.w3.org/1999/02/22-rdf-syntax-ns#"),
+    ("rdfa", "http://www.w3.org/ns/rdfa#"),
+    ("rdfs", "http://www.w3.org/2000/01/rdf-schema#"),
+    ("rev", "http://purl.org/stuff/rev#"),
+    ("rif", "http://www.w3.org/2007/rif#"),
+    ("rr", "http://www.w3.org/ns/r2rml#"),
+    ("schema", "http://schema.org/"),
+    ("sd", "http://www.w3.org/ns/sparql-service-description#"),
+    ("sioc", "http://rdfs.org/sioc/ns#"),
+    ("skos", "http://www.w3.org/2004/02/skos/core#"),
+    ("skosxl", "http://www.w3.org/2008/05/skos-xl#"),
+    ("sosa", "http://www.w3.org/ns/sosa/"),
+    ("ssn", "http://www.w3.org/ns/ssn/"),
+    ("time", "http://www.w3.org/2006/time#"),
+    ("v", "http://rdf.data-vocabulary.org/#"),
+    ("vcard", "http://www.w3.org/2006/vcard/ns#"),
+    ("void", "http://rdfs.org/ns/void#"),
+    ("wdr", "http://www.w3.org/2007/05/powder#"),
+    ("wdrs", "http://www.w3.org/2007/05/powder-s#"),
+    ("xhv", "http://www.w3.org/1999/xhtml/vocab#"),
+    ("xml", "http://www.w3.org/XML/1998/namespace"),
+    ("xsd", "http://www.w3.org/2001/XMLSchema#"),
+];
+
+/// The terms of the RDFa 1.1 initial context.
+#[cfg(feature = "rdfa-initial-context")]
+const INITIAL_TERMS: &[(&str, &str)] = &[
+    (
+        "describedby",
+        "http://www.w3.org/2007/05/powder-s#describedby",
+    ),
+    ("license", "http://www.w3.org/1999/xhtml/vocab#license"),
+    ("role", "http://www.w3.org/1999/xhtml/vocab#role"),
+];
+
+impl TermMapping {
+    /// Construct a term mapping with the terms of the
+    /// [RDFa 1.1 initial context], which an RDFa processor starts
+    /// with.
+    ///
+    /// This requires the `rdfa-initial-context` feature.
+    ///
+    /// [RDFa 1.1 initial context]: https://www.w3.org/2011/rdfa-context/rdfa-1.1
+    #[cfg(feature = "rdfa-initial-context")]
+    #[must_use]
+    pub fn rdfa_initial_context() -> Self {
+        let mut terms = TermMapping::default();
+        for &(term, iri) in INITIAL_TERMS {
+            terms.add_term(term, iri);
+        }
+        terms
+    }
+
+    /// Set the local default vocabulary, as done by the RDFa
+    /// `@vocab` attribute.
+    pub fn set_vocabulary(&mut self, vocabulary: &str) {
+        self.vocabulary = Some(String::from(vocabulary));
+    }
+
+    /// Clear the local default vocabulary.
+    ///
+    /// An empty `@vocab` attribute has this effect in RDFa.
+    pub fn clear_vocabulary(&mut self) {
+        self.vocabulary = None;
+    }
+
+    /// Add a term to the mapping.
+    pub fn add_term(&mut self, term: &str, iri: &str) {
+        self.terms.insert(String::from(term), String::from(iri));
+    }
+
+    /// Resolve a term to an IRI.
+    ///
+    /// If a local default vocabulary has been set, the term is appended
+    /// to it. Otherwise, the term is looked up in the term mappings,
+    /// first case-sensitively and then case-insensitively.
+    ///
+    /// Returns `None` if the term has no associated IRI, in which case
+    /// an RDFa processor ignores it.
+    #[must_use]
+    pub fn resolve_term(&self, term: &str) -> Option<String> {
+        if let Some(ref vocabulary) = self.vocabulary {
+            return Some(vocabulary.clone() + term);
+        }
+        self.terms
+            .get(term)
+            .or_else(|| {
+                self.terms
+                    .iter()
+                    .find(|(t, _)| t.to_lowercase() == term.to_lowercase())
+                    .map(|(_, iri)| iri)
+            })
+            .cloned()
+    }
+}
+
+impl PrefixMapping {
+    /// Construct a mapping with the prefixes of the
+    /// [RDFa 1.1 initial context], which an RDFa processor starts
+    /// with.
+    ///
+    /// The terms of the initial context are in
+    /// [`TermMapping::rdfa_initial_context()`].
+    ///
+    /// This requires the `rdfa-initial-context` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "rdfa-initial-context")] {
+    /// use curie::PrefixMapping;
+    ///
+    /// let mapping = PrefixMapping::rdfa_initial_context();
+    /// assert_eq!(mapping.expand_curie_string("schema:Person"),
+    ///            Ok(String::from("http://schema.org/Person")));
+    /// # }
+    /// ```
+    ///
+    /// [RDFa 1.1 initial context]: https://www.w3.org/2011/rdfa-context/rdfa-1.1
+    #[cfg(feature = "rdfa-initial-context")]
+    #[must_use]
+    pub fn rdfa_initial_context() -> Self {
+        let mut mapping = PrefixMapping::default();
+        for &(prefix, namespace) in INITIAL_PREFIXES {
+            mapping
+                .add_prefix(prefix, namespace)
+                .expect("initial context prefixes are valid");
+        }
+        mapping
+    }
+
+    /// Resolve an RDFa `TERMorCURIEorAbsIRI` value.
+    ///
+    /// Following the RDFa Core processing rules, a value that is a term
+    /// is resolved using `terms`. Otherwise, it is expanded as a CURIE
+    /// if its prefix is mapped, and failing that, it is used as-is if it
+    /// is an absolute IRI.
+    ///
+    /// Returns `None` if the value can't be resolved, in which case an
+    /// RDFa processor ignores it.
+    ///
+    /// # See also
+    ///
+    /// * [`TermMapping::resolve_term()`]
+    /// * [`PrefixMapping::expand_curie_string()`]
+    #[must_use]
+    pub fn resolve_term_or_curie_or_abs_iri(
+        &self,
+        value: &str,
+        terms: &TermMapping,
+    ) -> Option<String> {
+        if syntax::is_term(value) {
+            return terms.resolve_term(value);
+        }
+        if value.contains(':') {
+            if let Ok(iri) = self.expand_curie_string(value) {
+                return Some(iri);
+            }
+        }
+        syntax::is_absolute_iri(value).then(|| String::from(value))
+    }
+}
+
+/// A problem with the prefix declarations of an element, which an RDFa
+/// processor reports as a warning before carrying on.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RdfaWarning {
+    /// A `@profile` document could not be loaded.
+    ProfileUnavailable(String),
+    /// A declaration in a `@prefix` or `xmlns:` attribute is malformed
+    /// or binds a reserved or protected prefix.
+    InvalidPrefixDeclaration(String),
+}
+
+impl fmt::Display for RdfaWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RdfaWarning::ProfileUnavailable(profile) => {
+                write!(f, "the profile `{profile}` could not be loaded")
+            }
+            RdfaWarning::InvalidPrefixDeclaration(declaration) => {
+                write!(f, "the prefix declaration `{declaration}` was ignored")
+            }
+        }
+    }
+}
+
+/// Read the declarations in the value of an RDFa `@prefix` attribute,
+/// without applying them to a mapping.
+///
+/// Each declaration is yielded as a [`Directive::Prefix`], with the
+/// namespace as written. Declarations that are malformed, or whose
+/// prefix isn't an `NCName`, are yielded as warnings, and reading
+/// carries on after them, as an RDFa processor does.
+///
+/// # Examples
+///
+/// ```
+/// use curie::rdfa::{self, RdfaWarning};
+/// use curie::turtle::Directive;
+///
+/// let mut directives = rdfa::prefix_directives("foaf: http://xmlns.com/foaf/0.1/ bad http://b/");
+/// assert_eq!(directives.next(), Some(Ok(Directive::Prefix {
+///     prefix: String::from("foaf"),
+///     iri: String::from("http://xmlns.com/foaf/0.1/"),
+/// })));
+/// assert_eq!(directives.next(),
+///            Some(Err(RdfaWarning::InvalidPrefixDeclaration(String::from("bad http://b/")))));
+/// assert_eq!(directives.next(), None);
+/// ```
+pub fn prefix_directives(value: &str) -> PrefixDirectives<'_> {
+    PrefixDirectives {
+        tokens: value.split_ascii_whitespace(),
+    }
+}
+
+/// An iterator over the declarations in an RDFa `@prefix` attribute.
+///
+/// This is created by [`prefix_directives()`].
+#[derive(Clone, Debug)]
+pub struct PrefixDirectives<'a> {
+    tokens: std::str::SplitAsciiWhitespace<'a>,
+}
+
+impl Iterator for PrefixDirectives<'_> {
+    type Item = Result<Directive, RdfaWarning>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let token = self.tokens.next()?;
+        let namespace = self.tokens.next();
+        Some(match (token.strip_suffix(':'), namespace) {
+            (Some(prefix), Some(namespace)) if syntax::is_ncname(prefix) => Ok(Directive::Prefix {
+                prefix: String::from(prefix),
+                iri: String::from(namespace),
+            }),
+            (_, Some(namespace)) => Err(RdfaWarning::InvalidPrefixDeclaration(format!(
+                "{token} {namespace}"
+            ))),
+            (_, None) => Err(RdfaWarning::InvalidPrefixDeclaration(String::from(token))),
+        })
+    }
+}
+
+impl FusedIterator for PrefixDirectives<'_> {}
+
+impl PrefixMapping {
+    /// Apply the prefix declarations of an HTML or XHTML element.
+    ///
+    /// Call this on a copy of the mapping of the parent element with
+    /// the attributes of the element, to get the mapping in scope for
+    /// the element and its children. The declarations are applied in
+    /// increasing order of precedence:
+    ///
+    /// 1. the prefixes of the documents referenced by `@profile`, as in
+    ///    RDFa 1.0 markup, in the order they are listed, using
+    ///    `load_profile` to load each one,
+    /// 2. `xmlns:` attributes, as in RDFa 1.0 markup,
+    /// 3. the `@prefix` attribute of RDFa 1.1.
+    ///
+    /// Attribute names are matched case-insensitively, as in HTML, but
+    /// prefixes are kept as written. Declarations that can't be used
+    /// are skipped and reported in the returned warnings; the other
+    /// declarations still apply.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::PrefixMapping;
+    ///
+    /// let mut mapping = PrefixMapping::default();
+    /// let warnings = mapping.apply_rdfa_element(
+    ///     [
+    ///         ("prefix", "dc: http://purl.org/dc/terms/"),
+    ///         ("xmlns:dc", "http://purl.org/dc/elements/1.1/"),
+    ///         ("xmlns:foaf", "http://xmlns.com/foaf/0.1/"),
+    ///         ("profile", "http://example.com/profile"),
+    ///     ],
+    ///     |profile| {
+    ///         let mut prefixes = PrefixMapping::default();
+    ///         prefixes.add_prefix("ex", &format!("{profile}#")).unwrap();
+    ///         Some(prefixes)
+    ///     },
+    /// );
+    ///
+    /// assert!(warnings.is_empty());
+    /// assert_eq!(mapping.expand_curie_string("dc:title"),
+    ///            Ok(String::from("http://purl.org/dc/terms/title")));
+    /// assert_eq!(mapping.expand_curie_string("foaf:name"),
+    ///            Ok(String::from("http://xmlns.com/foaf/0.1/name")));
+    /// assert_eq!(mapping.expand_curie_string("ex:a"),
+    ///            Ok(String::from("http://example.com/profile#a")));
+    /// ```
+    pub fn apply_rdfa_element<'a, I, F>(
+        &mut self,
+        attributes: I,
+        mut load_profile: F,
+    ) -> Vec<RdfaWarning>
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+        F: FnMut(&str) -> Option<PrefixMapping>,
+    {
+        let mut profiles = None;
+        let mut xmlns = Vec::new();
+        let mut prefix = None;
+        for (name, value) in attributes {
+            if name.eq_ignore_ascii_case("profile") {
+                profiles = Some(value);
+            } else if name.eq_ignore_ascii_case("prefix") {
+                prefix = Some(value);
+            } else if name.len() > 6
+                && name
+                    .get(..6)
+                    .is_some_and(|start| start.eq_ignore_ascii_case("xmlns:"))
+            {
+                // The first six bytes are ASCII, so this is on a
+                // character boundary.
+                xmlns.push((&name[6..], value));
+            }
+        }
+
+        let mut warnings = Vec::new();
+        for profile in profiles.into_iter().flat_map(str::split_ascii_whitespace) {
+            match load_profile(profile) {
+                Some(prefixes) => {
+                    for (prefix, namespace) in prefixes.mappings() {
+                        self.add_rdfa_prefix(prefix, namespace, &mut warnings);
+                    }
+                }
+                None => warnings.push(RdfaWarning::ProfileUnavailable(String::from(profile))),
+            }
+        }
+        for (prefix, namespace) in xmlns {
+            self.add_rdfa_prefix(prefix, namespace, &mut warnings);
+        }
+        if let Some(prefix) = prefix {
+            warnings.extend(self.extend_from_rdfa_prefix(prefix));
+        }
+        warnings
+    }
+
+    /// Add the declarations in the value of an RDFa `@prefix`
+    /// attribute, such as `"foaf: http://xmlns.com/foaf/0.1/"`, to
+    /// this mapping.
+    ///
+    /// Each declaration is a prefix followed by a colon, then
+    /// whitespace and the namespace. Declarations that can't be used
+    /// are skipped and reported in the returned warnings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::PrefixMapping;
+    /// use curie::rdfa::RdfaWarning;
+    ///
+    /// let mut mapping = PrefixMapping::default();
+    /// let warnings = mapping.extend_from_rdfa_prefix(
+    ///     "foaf: http://xmlns.com/foaf/0.1/\n dc: http://purl.org/dc/terms/ bad http://b/",
+    /// );
+    ///
+    /// assert_eq!(warnings, [RdfaWarning::InvalidPrefixDeclaration(String::from("bad http://b/"))]);
+    /// assert_eq!(mapping.to_rdfa_prefix(),
+    ///            "foaf: http://xmlns.com/foaf/0.1/ dc: http://purl.org/dc/terms/");
+    /// ```
+    pub fn extend_from_rdfa_prefix(&mut self, value: &str) -> Vec<RdfaWarning> {
+        let mut warnings = Vec::new();
+        for directive in prefix_directives(value) {
+            match directive {
+                Ok(Directive::Prefix { prefix, iri }) => {
+                    self.add_rdfa_prefix(&prefix, &iri, &mut warnings);
+                }
+                Ok(_) => {}
+                Err(warning) => warnings.push(warning),
+            }
+        }
+        warnings
+    }
+
+    /// Write the prefixes of this mapping as the value of an RDFa
+    /// `@prefix` attribute, on one line in the order they were added.
+    ///
+    /// The default prefix is not written. The value still has to be
+    /// escaped for the attribute. To lay out the value differently, use
+    /// a [`PrefixFormatter`] with [`DeclarationStyle::Rdfa`].
+    #[must_use]
+    pub fn to_rdfa_prefix(&self) -> String {
+        PrefixFormatter::new(DeclarationStyle::Rdfa).format(self)
+    }
+
+    fn add_rdfa_prefix(&mut self, prefix: &str, namespace: &str, warnings: &mut Vec<RdfaWarning>) {
+        if !syntax::is_ncname(prefix) || self.add_prefix(prefix, namespace).is_err() {
+            warnings.push(RdfaWarning::InvalidPrefixDeclaration(format!(
+                "{prefix}: {namespace}"
+            )));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_term_case_insensitive() {
+        let mut terms = TermMapping::default();
+        terms.add_term("Next", "http://example.com/Next");
+        terms.add_term("next", "http://example.com/next");
+
+        assert_eq!(
+            terms.resolve_term("Next"),
+            Some(String::from("http://example.com/Next"))
+        );
+        assert_eq!(
+            terms.resolve_term("next"),
+            Some(String::from("http://example.com/next"))
+        );
+        assert_eq!(
+            terms.resolve_term("NEXT"),
+            Some(String::from("http://example.com/Next"))
+        );
+        assert_eq!(terms.resolve_term("prev"), None);
+    }
+
+    #[test]
+    fn resolve_precedence() {
+        let mut prefixes = PrefixMapping::default();
+        prefixes
+            .add_prefix("foaf", "http://xmlns.com/foaf/0.1/")
+            .unwrap();
+        prefixes.set_default("http://example.com/default/");
+
+        let mut terms = TermMapping::default();
+        terms.add_term("name", "http://example.com/terms/name");
+
+        // Terms never use the default prefix.
+        assert_eq!(
+            prefixes.resolve_term_or_curie_or_abs_iri("name", &terms),
+            Some(String::from("http://example.com/terms/name"))
+        );
+        assert_eq!(
+            prefixes.resolve_term_or_curie_or_abs_iri("title", &terms),
+            None
+        );
+
+        // A vocabulary takes over from the term mappings.
+        terms.set_vocabulary("http://schema.org/");
+        assert_eq!(
+            prefixes.resolve_term_or_curie_or_abs_iri("name", &terms),
+            Some(String::from("http://schema.org/name"))
+        );
+        terms.clear_vocabulary();
+
+        // CURIEs with a mapped prefix are expanded.
+        assert_eq!(
+            prefixes.resolve_term_or_curie_or_abs_iri("foaf:name", &terms),
+            Some(String::from("http://xmlns.com/foaf/0.1/name"))
+        );
+
+        // Anything else with a scheme is an absolute IRI.
+        assert_eq!(
+            prefixes.resolve_term_or_curie_or_abs_iri("mailto:someone@example.com", &terms),
+            Some(String::from("mailto:someone@example.com"))
+        );
+
+        // Relative references are ignored.
+        assert_eq!(
+            prefixes.resolve_term_or_curie_or_abs_iri("#me", &terms),
+            None
+        );
+    }
+
+    #[cfg(feature = "rdfa-initial-context")]
+    #[test]
+    fn initial_context() {
+        let prefixes = PrefixMapping::rdfa_initial_context();
+        let terms = TermMapping::rdfa_initial_context();
+        assert_eq!(prefixes.mappings().count(), INITIAL_PREFIXES.len());
+        assert_eq!(
+            prefixes.resolve_term_or_curie_or_abs_iri("License", &terms),
+            Some(String::from("http://www.w3.org/1999/xhtml/vocab#license"))
+        );
+        assert_eq!(
+            prefixes.resolve_term_or_curie_or_abs_iri("dc:title", &terms),
+            Some(String::from("http://purl.org/dc/terms/title"))
+        );
+    }
+
+    #[test]
+    fn element_prefixes() {
+        let mut mapping = PrefixMapping::default();
+        mapping.add_prefix("ex", "http://example.com/").unwrap();
+
+        let warnings = mapping.apply_rdfa_element(
+            [
+                ("PROFILE", "http://example.com/a http://example.com/missing"),
+                ("XMLNS:ex", "http://example.org/"),
+                ("xmlns:_", "http://example.com/blank/"),
+                ("xmlns", "http://www.w3.org/1999/xhtml"),
+                (
+                    "Prefix",
+                    "  a: http://example.com/a/\n b http://example.com/b/ c:",
+                ),
+            ],
+            |profile| {
+                (profile == "http://example.com/a").then(|| {
+                    let mut prefixes = PrefixMapping::default();
+                    prefixes
+                        .add_prefix("a", "http://example.com/profile-a/")
+                        .unwrap();
+                    prefixes
+                        .add_prefix("p", "http://example.com/profile-p/")
+                        .unwrap();
+                    prefixes
+                })
+            },
+        );
+
+        assert_eq!(
+            warnings,
+            [
+                RdfaWarning::ProfileUnavailable(String::from("http://example.com/missing")),
+                RdfaWarning::InvalidPrefixDeclaration(String::from("_: http://example.com/blank/")),
+                RdfaWarning::InvalidPrefixDeclaration(String::from("b http://example.com/b/")),
+                RdfaWarning::InvalidPrefixDeclaration(String::from("c:")),
+            ]
+        );
+        assert_eq!(
+            mapping.mappings().collect::<Vec<_>>(),
+            [
+                ("ex", "http://example.org/"),
+                ("a", "http://example.com/a/"),
+                ("p", "http://example.com/profile-p/"),
+            ]
+        );
+    }
+
+    #[test]
+    fn element_multibyte_attributes() {
+        let mut mapping = PrefixMapping::default();
+        let warnings = mapping.apply_rdfa_element(
+            [
+                ("xmlnsé:x", "http://example.com/x/"),
+                ("é", "http://example.com/"),
+                ("xmlns:é", "http://example.com/e/"),
+            ],
+            |_| None,
+        );
+        assert!(warnings.is_empty());
+        assert_eq!(
+            mapping.mappings().collect::<Vec<_>>(),
+            [("é", "http://example.com/e/")]
+        );
+    }
+}
@@ -0,0 +1,298 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{CoverageReport, PrefixMapping};
+use indexmap::IndexMap;
+
+/// Commonly used prefixes, following the preferred prefixes of
+/// [prefix.cc](https://prefix.cc/) and the W3C.
+const BUNDLED: &[(&str, &str)] = &[
+    ("as", "https://www.w3.org/ns/activitystreams#"),
+    ("bibo", "http://purl.org/ontology/bibo/"),
+    ("cc", "http://creativecommons.org/ns#"),
+    ("csvw", "http://www.w3.org/ns/csvw#"),
+    ("dbo", "http://dbpedia.org/ontology/"),
+    ("dbr", "http://dbpedia.org/resource/"),
+    ("dc", "http://purl.org/dc/elements/1.1/"),
+    ("dcat", "http://www.w3.org/ns/dcat#"),
+    ("dcterms", "http://purl.org/dc/terms/"),
+    ("doap", "http://usefulinc.com/ns/doap#"),
+    ("dqv", "http://www.w3.org/ns/dqv#"),
+    ("foaf", "http://xmlns.com/foaf/0.1/"),
+    ("geo", "http://www.opengis.net/ont/geosparql#"),
+    ("gr", "http://purl.org/goodrelations/v1#"),
+    ("ldp", "http://www.w3.org/ns/ldp#"),
+    ("obo", "http://purl.obolibrary.org/obo/"),
+    ("odrl", "http://www.w3.org/ns/odrl/2/"),
+    ("og", "http://ogp.me/ns#"),
+    ("org", "http://www.w3.org/ns/org#"),
+    ("owl", "http://www.w3.org/2002/07/owl#"),
+    ("prov", "http://www.w3.org/ns/prov#"),
+    ("qb", "http://purl.org/linked-data/cube#"),
+    ("rdf", "http://www.w3.org/1999/02/22-rdf-syntax-ns#"),
+    ("rdfs", "http://www.w3.org/2000/01/rdf-schema#"),
+    ("schema", "http://schema.org/"),
+    ("sh", "http://www.w3.org/ns/shacl#"),
+    ("sioc", "http://rdfs.org/sioc/ns#"),
+    ("skos", "http://www.w3.org/2004/02/skos/core#"),
+    ("skosxl", "http://www.w3.org/2008/05/skos-xl#"),
+    ("sosa", "http://www.w3.org/ns/sosa/"),
+    ("ssn", "http://www.w3.org/ns/ssn/"),
+    ("time", "http://www.w3.org/2006/time#"),
+    ("vcard", "http://www.w3.org/2006/vcard/ns#"),
+    ("void", "http://rdfs.org/ns/void#"),
+    ("wd", "http://www.wikidata.org/entity/"),
+    ("wdt", "http://www.wikidata.org/prop/direct/"),
+    ("wgs84", "http://www.w3.org/2003/01/geo/wgs84_pos#"),
+    ("xsd", "http://www.w3.org/2001/XMLSchema#"),
+];
+
+/// A registry of community-standard prefixes, used to suggest prefixes
+/// for namespaces that a mapping doesn't cover.
+///
+/// [`Registry::bundled()`] has a small set of widely used prefixes.
+/// Data from registries such as [prefix.cc](https://prefix.cc/) or
+/// [Bioregistry](https://bioregistry.io/) can be added to it, or used
+/// on its own, by collecting pairs of prefixes and namespaces.
+///
+/// # Examples
+///
+/// ```
+/// use curie::{PrefixMapping, Registry};
+///
+/// let registry = Registry::bundled();
+/// assert_eq!(registry.namespace("foaf"), Some("http://xmlns.com/foaf/0.1/"));
+///
+/// let mut mapping = PrefixMapping::default();
+/// let report = mapping.coverage(["http://schema.org/Person", "http://example.com/x"]);
+/// let suggestions = report.suggestions(&registry);
+/// assert_eq!(suggestions[0].prefix(), "schema");
+///
+/// assert_eq!(mapping.apply_suggestions(&suggestions), 1);
+/// assert_eq!(mapping.expand_curie_string("schema:Person"),
+///            Ok(String::from("http://schema.org/Person")));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Registry {
+    entries: IndexMap<String, String>,
+}
+
+impl Registry {
+    /// Construct an empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Registry::default()
+    }
+
+    /// Construct a registry of widely used prefixes.
+    #[must_use]
+    pub fn bundled() -> Self {
+        BUNDLED.iter().copied().collect()
+    }
+
+    /// Add a prefix to the registry, replacing any namespace that it
+    /// was registered with.
+    pub fn insert(&mut self, prefix: &str, namespace: &str) {
+        self.entries
+            .insert(String::from(prefix), String::from(namespace));
+    }
+
+    /// The namespace registered for a prefix.
+    #[must_use]
+    pub fn namespace(&self, prefix: &str) -> Option<&str> {
+        self.entries.get(prefix).map(String::as_str)
+    }
+
+    /// Find the registered prefix for the longest registered namespace
+    /// that `namespace` starts with, returning the prefix and the
+    /// registered namespace.
+    ///
+    /// When several prefixes are registered for the same namespace,
+    /// the first one is used.
+    #[must_use]
+    pub fn prefix_for(&self, namespace: &str) -> Option<(&str, &str)> {
+        self.entries
+            .iter()
+            .filter(|(_, registered)| namespace.starts_with(registered.as_str()))
+            // `max_by_key` picks the last of equal keys, so search in
+            // reverse to prefer the first.
+            .rev()
+            .max_by_key(|(_, registered)| registered.len())
+            .map(|(prefix, registered)| (prefix.as_str(), registered.as_str()))
+    }
+
+    /// Iterate over the registered prefixes and namespaces, in the
+    /// order they were added.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries
+            .iter()
+            .map(|(prefix, namespace)| (prefix.as_str(), namespace.as_str()))
+    }
+}
+
+impl<P: AsRef<str>, N: AsRef<str>> FromIterator<(P, N)> for Registry {
+    fn from_iter<I: IntoIterator<Item = (P, N)>>(iter: I) -> Self {
+        let mut registry = Registry::new();
+        registry.extend(iter);
+        registry
+    }
+}
+
+impl<P: AsRef<str>, N: AsRef<str>> Extend<(P, N)> for Registry {
+    fn extend<I: IntoIterator<Item = (P, N)>>(&mut self, iter: I) {
+        for (prefix, namespace) in iter {
+            self.insert(prefix.as_ref(), namespace.as_ref());
+        }
+    }
+}
+
+/// A registered prefix suggested for IRIs that a mapping doesn't cover.
+///
+/// This is created by [`CoverageReport::suggestions()`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PrefixSuggestion {
+    unmatched: String,
+    prefix: String,
+    namespace: String,
+}
+
+impl PrefixSuggestion {
+    /// The inferred namespace of the unmatched IRIs.
+    #[must_use]
+    pub fn unmatched_namespace(&self) -> &str {
+        &self.unmatched
+    }
+
+    /// The suggested prefix.
+    #[must_use]
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// The namespace registered for the suggested prefix.
+    ///
+    /// This may be shorter than the inferred namespace.
+    #[must_use]
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+}
+
+impl CoverageReport {
+    /// Suggest registered prefixes for the namespaces that no prefix
+    /// matched.
+    ///
+    /// Each suggestion is for a distinct registered namespace, in the
+    /// order the unmatched namespaces were first seen. Unmatched
+    /// namespaces that aren't registered get no suggestion.
+    #[must_use]
+    pub fn suggestions(&self, registry: &Registry) -> Vec<PrefixSuggestion> {
+        let mut suggestions: Vec<PrefixSuggestion> = Vec::new();
+        for unmatched in self.unmatched_namespaces() {
+            if let Some((prefix, namespace)) = registry.prefix_for(unmatched) {
+                if suggestions
+                    .iter()
+                    .all(|suggestion| suggestion.namespace != namespace)
+                {
+                    suggestions.push(PrefixSuggestion {
+                        unmatched: String::from(unmatched),
+                        prefix: String::from(prefix),
+                        namespace: String::from(namespace),
+                    });
+                }
+            }
+        }
+        suggestions
+    }
+}
+
+impl PrefixMapping {
+    /// Add the prefixes suggested by [`CoverageReport::suggestions()`].
+    ///
+    /// A suggestion is skipped if its prefix is already mapped, so that
+    /// existing bindings are never changed.
+    ///
+    /// Returns the number of prefixes added.
+    pub fn apply_suggestions(&mut self, suggestions: &[PrefixSuggestion]) -> usize {
+        let mut added = 0;
+        for suggestion in suggestions {
+            if !self.mapping.contains_key(&suggestion.prefix)
+                && self
+                    .add_prefix(&suggestion.prefix, &suggestion.namespace)
+                    .is_ok()
+            {
+                added += 1;
+            }
+        }
+        added
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn longest_match() {
+        let registry: Registry = [
+            ("ex", "http://example.com/"),
+            ("exv", "http://example.com/vocab/"),
+            ("ex2", "http://example.com/vocab/"),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(
+            registry.prefix_for("http://example.com/vocab/a/"),
+            Some(("exv", "http://example.com/vocab/"))
+        );
+        assert_eq!(
+            registry.prefix_for("http://example.com/other/"),
+            Some(("ex", "http://example.com/"))
+        );
+        assert_eq!(registry.prefix_for("http://example.org/"), None);
+    }
+
+    #[test]
+    fn suggestions() {
+        let mut mapping = PrefixMapping::default();
+        mapping
+            .add_prefix("foaf", "http://example.com/not-foaf/")
+            .unwrap();
+        let report = mapping.coverage([
+            "http://xmlns.com/foaf/0.1/name",
+            "http://purl.obolibrary.org/obo/GO_0008150",
+            "http://purl.obolibrary.org/obo/go/extra",
+            "http://unregistered.example/a",
+        ]);
+
+        let suggestions = report.suggestions(&Registry::bundled());
+        assert_eq!(
+            suggestions
+                .iter()
+                .map(|s| (s.unmatched_namespace(), s.prefix(), s.namespace()))
+                .collect::<Vec<_>>(),
+            [
+                (
+                    "http://xmlns.com/foaf/0.1/",
+                    "foaf",
+                    "http://xmlns.com/foaf/0.1/"
+                ),
+                (
+                    "http://purl.obolibrary.org/obo/",
+                    "obo",
+                    "http://purl.obolibrary.org/obo/"
+                ),
+            ]
+        );
+
+        // `foaf` is already bound, so it is left alone.
+        assert_eq!(mapping.apply_suggestions(&suggestions), 1);
+        assert_eq!(
+            mapping.expand_curie_string("foaf:name"),
+            Ok(String::from("http://example.com/not-foaf/name"))
+        );
+    }
+}
@@ -0,0 +1,131 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{InvalidPrefixError, PrefixMapping};
+
+impl PrefixMapping {
+    /// Move the binding of `old` to the prefix `new`.
+    ///
+    /// The namespace or URI template, the position of the prefix in
+    /// the order of [`PrefixMapping::mappings()`] and any
+    /// [reference pattern](PrefixMapping::set_reference_pattern())
+    /// move to the new prefix, and `old` is no longer bound.
+    ///
+    /// Returns `false` if `old` is not bound, in which case nothing is
+    /// changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidPrefixError::AlreadyBound`] if `new` is already
+    /// bound, [`InvalidPrefixError::ProtectedPrefix`] if `old` is
+    /// protected, and the error from checking `new` as
+    /// [`PrefixMapping::add_prefix()`] does if it can't be bound. The
+    /// mapping is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::{InvalidPrefixError, PrefixMapping};
+    ///
+    /// let mut mapping = PrefixMapping::default();
+    /// mapping.add_prefix("ns1", "http://xmlns.com/foaf/0.1/").unwrap();
+    /// mapping.add_prefix("dc", "http://purl.org/dc/terms/").unwrap();
+    ///
+    /// assert_eq!(mapping.rename_prefix("ns1", "foaf"), Ok(true));
+    /// assert_eq!(mapping.expand_curie_string("foaf:name"),
+    ///            Ok(String::from("http://xmlns.com/foaf/0.1/name")));
+    /// assert!(!mapping.contains_prefix("ns1"));
+    ///
+    /// assert_eq!(mapping.rename_prefix("foaf", "dc"),
+    ///            Err(InvalidPrefixError::AlreadyBound));
+    /// assert_eq!(mapping.rename_prefix("ns1", "ns2"), Ok(false));
+    /// ```
+    pub fn rename_prefix(&mut self, old: &str, new: &str) -> Result<bool, InvalidPrefixError> {
+        if !self.contains_prefix(old) {
+            return Ok(false);
+        }
+        if old == new {
+            return Ok(true);
+        }
+        if self.protected.contains(old) {
+            return Err(InvalidPrefixError::ProtectedPrefix);
+        }
+        if self.contains_prefix(new) {
+            return Err(InvalidPrefixError::AlreadyBound);
+        }
+        self.check_prefix(new)?;
+
+        if let Some((index, _, namespace)) = self.mapping.shift_remove_full(old) {
+            self.mapping
+                .shift_insert(index, String::from(new), namespace);
+        }
+        if let Some((index, _, template)) = self.templates.shift_remove_full(old) {
+            self.templates
+                .shift_insert(index, String::from(new), template);
+        }
+        #[cfg(feature = "regex")]
+        if let Some(pattern) = self.patterns.shift_remove(old) {
+            self.patterns.insert(String::from(new), pattern);
+        }
+        if !self.prefix_ids.contains(new) {
+            self.prefix_ids.insert(String::from(new));
+        }
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rename() {
+        let mut mapping = PrefixMapping::default();
+        mapping.add_prefix("a", "http://example.com/a/").unwrap();
+        mapping.add_prefix("b", "http://example.com/b/").unwrap();
+        mapping.add_prefix("c", "http://example.com/c/").unwrap();
+        mapping
+            .add_prefix_template("t", "http://example.com/{id}")
+            .unwrap();
+
+        assert_eq!(mapping.rename_prefix("b", "z"), Ok(true));
+        let prefixes: Vec<&str> = mapping.mappings().map(|(p, _)| p).collect();
+        assert_eq!(prefixes, ["a", "z", "c"]);
+        assert_eq!(mapping.rename_prefix("t", "u"), Ok(true));
+        assert_eq!(
+            mapping.expand_curie_string("u:1"),
+            Ok(String::from("http://example.com/1"))
+        );
+        assert_eq!(mapping.rename_prefix("a", "a"), Ok(true));
+
+        let before = mapping.clone();
+        assert_eq!(
+            mapping.rename_prefix("a", "_"),
+            Err(InvalidPrefixError::ReservedPrefix)
+        );
+        assert_eq!(
+            mapping.rename_prefix("a", "u"),
+            Err(InvalidPrefixError::AlreadyBound)
+        );
+        mapping.protect_prefix("a");
+        assert_eq!(
+            mapping.rename_prefix("a", "y"),
+            Err(InvalidPrefixError::ProtectedPrefix)
+        );
+        assert_eq!(mapping, before);
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn rename_moves_pattern() {
+        let mut mapping = PrefixMapping::default();
+        mapping.add_prefix("a", "http://example.com/").unwrap();
+        mapping.set_reference_pattern("a", "^[0-9]+$").unwrap();
+        mapping.rename_prefix("a", "b").unwrap();
+        assert_eq!(mapping.reference_pattern("a"), None);
+        assert_eq!(mapping.reference_pattern("b"), Some("^[0-9]+$"));
+    }
+}
@@ -0,0 +1,366 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Resolving relative IRI references, following [RFC 3986, section 5].
+//!
+//! [RFC 3986, section 5]: https://www.rfc-editor.org/rfc/rfc3986#section-5
+
+use crate::{syntax, PrefixMapping};
+
+/// How a CURIE without a prefix is expanded.
+///
+/// JSON-LD, for example, expands a bare term with `@vocab` but a
+/// relative IRI with `@base`. See [`PrefixMapping::set_unprefixed()`].
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Unprefixed {
+    /// The reference is appended to the default prefix, like
+    /// `@vocab`.
+    #[default]
+    Vocab,
+    /// The reference is resolved against the base IRI as a relative
+    /// IRI reference, like `@base`.
+    Base,
+}
+
+/// The components of an IRI reference, as split by the regular
+/// expression in appendix B of RFC 3986.
+struct Components<'a> {
+    scheme: Option<&'a str>,
+    authority: Option<&'a str>,
+    path: &'a str,
+    query: Option<&'a str>,
+    fragment: Option<&'a str>,
+}
+
+impl<'a> Components<'a> {
+    fn parse(iri: &'a str) -> Self {
+        let (rest, fragment) = match iri.split_once('#') {
+            Some((rest, fragment)) => (rest, Some(fragment)),
+            None => (iri, None),
+        };
+        let (rest, query) = match rest.split_once('?') {
+            Some((rest, query)) => (rest, Some(query)),
+            None => (rest, None),
+        };
+        let (scheme, rest) = if syntax::is_absolute_iri(rest) {
+            let (scheme, rest) = rest.split_once(':').expect("an absolute IRI has a scheme");
+            (Some(scheme), rest)
+        } else {
+            (None, rest)
+        };
+        let (authority, path) = match rest.strip_prefix("//") {
+            Some(rest) => {
+                let end = rest.find('/').unwrap_or(rest.len());
+                (Some(&rest[..end]), &rest[end..])
+            }
+            None => (None, rest),
+        };
+        Components {
+            scheme,
+            authority,
+            path,
+            query,
+            fragment,
+        }
+    }
+}
+
+/// Resolve `reference` against the absolute IRI `base`.
+pub(crate) fn resolve(base: &str, reference: &str) -> String {
+    let base = Components::parse(base);
+    let reference = Components::parse(reference);
+
+    let (scheme, authority, path, query);
+    if reference.scheme.is_some() {
+        scheme = reference.scheme;
+        authority = reference.authority;
+        path = remove_dot_segments(reference.path);
+        query = reference.query;
+    } else {
+        scheme = base.scheme;
+        if reference.authority.is_some() {
+            authority = reference.authority;
+            path = remove_dot_segments(reference.path);
+            query = reference.query;
+        } else {
+            authority = base.authority;
+            if reference.path.is_empty() {
+                path = String::from(base.path);
+                query = reference.query.or(base.query);
+            } else {
+                path = if reference.path.starts_with('/') {
+                    remove_dot_segments(reference.path)
+                } else {
+                    remove_dot_segments(&merge(&base, reference.path))
+                };
+                query = reference.query;
+            }
+        }
+    }
+
+    let mut iri = String::new();
+    if let Some(scheme) = scheme {
+        iri.push_str(scheme);
+        iri.push(':');
+    }
+    if let Some(authority) = authority {
+        iri.push_str("//");
+        iri.push_str(authority);
+    }
+    iri.push_str(&path);
+    if let Some(query) = query {
+        iri.push('?');
+        iri.push_str(query);
+    }
+    if let Some(fragment) = reference.fragment {
+        iri.push('#');
+        iri.push_str(fragment);
+    }
+    iri
+}
+
+/// Merge a relative path with the path of the base, as in section
+/// 5.2.3.
+fn merge(base: &Components, path: &str) -> String {
+    if base.authority.is_some() && base.path.is_empty() {
+        format!("/{path}")
+    } else {
+        match base.path.rfind('/') {
+            Some(end) => format!("{}{path}", &base.path[..=end]),
+            None => String::from(path),
+        }
+    }
+}
+
+/// Remove `.` and `..` segments from a path, as in section 5.2.4.
+fn remove_dot_segments(path: &str) -> String {
+    let mut input = path;
+    let mut output: Vec<&str> = Vec::new();
+    while !input.is_empty() {
+        if let Some(rest) = input
+            .strip_prefix("../")
+            .or_else(|| input.strip_prefix("./"))
+        {
+            input = rest;
+        } else if input.starts_with("/./") {
+            input = &input[2..];
+        } else if input == "/." {
+            input = "/";
+        } else if input.starts_with("/../") {
+            input = &input[3..];
+            output.pop();
+        } else if input == "/.." {
+            input = "/";
+            output.pop();
+        } else if input == "." || input == ".." {
+            input = "";
+        } else {
+            // Move the first segment, with any leading `/`, to the
+            // output.
+            let start = usize::from(input.starts_with('/'));
+            let end = input[start..]
+                .find('/')
+                .map_or(input.len(), |end| end + start);
+            output.push(&input[..end]);
+            input = &input[end..];
+        }
+    }
+    output.concat()
+}
+
+impl PrefixMapping {
+    /// Resolve a relative IRI reference, such as `#green-goblin` or
+    /// `../people/`, against the [base IRI](PrefixMapping::set_base()).
+    ///
+    /// This follows the rules of [RFC 3986], including removing `.` and
+    /// `..` segments. An absolute IRI is returned with its `.` and `..`
+    /// segments removed, whether or not there is a base IRI.
+    ///
+    /// Returns `None` if `reference` is relative and there is no
+    /// absolute base IRI to resolve it against.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::PrefixMapping;
+    ///
+    /// let mut mapping = PrefixMapping::default();
+    /// assert_eq!(mapping.resolve_iri("#green-goblin"), None);
+    ///
+    /// mapping.set_base("http://example.org/people/spiderman");
+    /// assert_eq!(mapping.resolve_iri("#green-goblin").as_deref(),
+    ///            Some("http://example.org/people/spiderman#green-goblin"));
+    /// assert_eq!(mapping.resolve_iri("../places/nyc").as_deref(),
+    ///            Some("http://example.org/places/nyc"));
+    /// ```
+    ///
+    /// [RFC 3986]: https://www.rfc-editor.org/rfc/rfc3986#section-5.2
+    #[must_use]
+    pub fn resolve_iri(&self, reference: &str) -> Option<String> {
+        if syntax::is_absolute_iri(reference) {
+            return Some(resolve("", reference));
+        }
+        self.base
+            .as_deref()
+            .filter(|base| syntax::is_absolute_iri(base))
+            .map(|base| resolve(base, reference))
+    }
+
+    /// Choose how CURIEs without a prefix are expanded: appended to the
+    /// [default prefix](PrefixMapping::set_default()), which is what
+    /// happens unless this is called, or resolved against the
+    /// [base IRI](PrefixMapping::set_base()).
+    ///
+    /// This doesn't change how IRIs are shrunk, which only uses the
+    /// default prefix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::{PrefixMapping, Unprefixed};
+    ///
+    /// let mut mapping = PrefixMapping::default();
+    /// mapping.set_default("http://schema.org/");
+    /// mapping.set_base("http://example.com/people/");
+    ///
+    /// assert_eq!(mapping.expand_curie_string("name"),
+    ///            Ok(String::from("http://schema.org/name")));
+    ///
+    /// mapping.set_unprefixed(Unprefixed::Base);
+    /// assert_eq!(mapping.expand_curie_string("../places/nyc"),
+    ///            Ok(String::from("http://example.com/places/nyc")));
+    /// ```
+    pub fn set_unprefixed(&mut self, unprefixed: Unprefixed) {
+        self.unprefixed = unprefixed;
+    }
+
+    /// How CURIEs without a prefix are expanded.
+    ///
+    /// See [`PrefixMapping::set_unprefixed()`].
+    #[must_use]
+    pub fn unprefixed(&self) -> Unprefixed {
+        self.unprefixed
+    }
+
+    /// Resolve an IRI declared in a document against the base IRI if
+    /// it is relative, leaving it as it is if it can't be resolved.
+    pub(crate) fn resolve_declared_iri(&self, iri: &str) -> String {
+        if syntax::is_absolute_iri(iri) {
+            return String::from(iri);
+        }
+        self.resolve_iri(iri).unwrap_or_else(|| String::from(iri))
+    }
+
+    /// Set the base IRI to `base` resolved against the current base, as
+    /// a base directive in a document does.
+    pub(crate) fn set_relative_base(&mut self, base: &str) {
+        self.base = Some(self.resolve_declared_iri(base));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ExpansionError;
+
+    #[test]
+    fn rfc3986_examples() {
+        // The examples of sections 5.4.1 and 5.4.2.
+        let base = "http://a/b/c/d;p?q";
+        let examples = [
+            ("g:h", "g:h"),
+            ("g", "http://a/b/c/g"),
+            ("./g", "http://a/b/c/g"),
+            ("g/", "http://a/b/c/g/"),
+            ("/g", "http://a/g"),
+            ("//g", "http://g"),
+            ("?y", "http://a/b/c/d;p?y"),
+            ("g?y", "http://a/b/c/g?y"),
+            ("#s", "http://a/b/c/d;p?q#s"),
+            ("g#s", "http://a/b/c/g#s"),
+            ("g?y#s", "http://a/b/c/g?y#s"),
+            (";x", "http://a/b/c/;x"),
+            ("g;x", "http://a/b/c/g;x"),
+            ("g;x?y#s", "http://a/b/c/g;x?y#s"),
+            ("", "http://a/b/c/d;p?q"),
+            (".", "http://a/b/c/"),
+            ("./", "http://a/b/c/"),
+            ("..", "http://a/b/"),
+            ("../", "http://a/b/"),
+            ("../g", "http://a/b/g"),
+            ("../..", "http://a/"),
+            ("../../", "http://a/"),
+            ("../../g", "http://a/g"),
+            ("../../../g", "http://a/g"),
+            ("../../../../g", "http://a/g"),
+            ("/./g", "http://a/g"),
+            ("/../g", "http://a/g"),
+            ("g.", "http://a/b/c/g."),
+            (".g", "http://a/b/c/.g"),
+            ("g..", "http://a/b/c/g.."),
+            ("..g", "http://a/b/c/..g"),
+            ("./../g", "http://a/b/g"),
+            ("./g/.", "http://a/b/c/g/"),
+            ("g/./h", "http://a/b/c/g/h"),
+            ("g/../h", "http://a/b/c/h"),
+            ("g;x=1/./y", "http://a/b/c/g;x=1/y"),
+            ("g;x=1/../y", "http://a/b/c/y"),
+            ("g?y/./x", "http://a/b/c/g?y/./x"),
+            ("g?y/../x", "http://a/b/c/g?y/../x"),
+            ("g#s/./x", "http://a/b/c/g#s/./x"),
+            ("g#s/../x", "http://a/b/c/g#s/../x"),
+            ("http:g", "http:g"),
+        ];
+        let mut mapping = PrefixMapping::default();
+        mapping.set_base(base);
+        for (reference, expected) in examples {
+            assert_eq!(
+                mapping.resolve_iri(reference).as_deref(),
+                Some(expected),
+                "resolving {reference:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn unprefixed_base() {
+        let mut mapping = PrefixMapping::default();
+        mapping.set_default("http://example.com/vocab#");
+        mapping.set_unprefixed(Unprefixed::Base);
+        assert_eq!(mapping.unprefixed(), Unprefixed::Base);
+        assert_eq!(
+            mapping.expand_curie_string("#me"),
+            Err(ExpansionError::MissingBase)
+        );
+        mapping.set_base("http://example.com/doc");
+        assert_eq!(
+            mapping.expand_curie_string("#me"),
+            Ok(String::from("http://example.com/doc#me"))
+        );
+        // CURIEs with a prefix are unaffected.
+        mapping.add_prefix("ex", "http://example.com/ns/").unwrap();
+        assert_eq!(
+            mapping.expand_curie_string("ex:me"),
+            Ok(String::from("http://example.com/ns/me"))
+        );
+    }
+
+    #[test]
+    fn without_base() {
+        let mut mapping = PrefixMapping::default();
+        assert_eq!(
+            mapping.resolve_iri("http://a/b/../c").as_deref(),
+            Some("http://a/c")
+        );
+        mapping.set_base("relative/base");
+        assert_eq!(mapping.resolve_iri("x"), None);
+        mapping.set_base("http://example.com");
+        assert_eq!(
+            mapping.resolve_iri("x").as_deref(),
+            Some("http://example.com/x")
+        );
+    }
+}
@@ -0,0 +1,224 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{Curie, ExpansionError, PrefixMapping, Unprefixed};
+use std::borrow::Cow;
+use std::fmt;
+
+/// A CURIE whose prefix has already been looked up in a
+/// [`PrefixMapping`].
+///
+/// This holds the namespace and the reference, so producing the
+/// complete IRI requires no further lookups. This is useful when the
+/// same CURIE is expanded many times. A CURIE without a prefix that is
+/// a [term](PrefixMapping::add_term()) resolves to the term's IRI, with
+/// an empty reference. The reference of a CURIE whose prefix is bound
+/// to a [URI template](PrefixMapping::add_prefix_template()) goes
+/// between the parts of the template before and after the placeholder.
+/// With [`Unprefixed::Base`], a CURIE without a prefix that isn't a
+/// term resolves to the reference resolved against the base IRI, again
+/// with an empty reference.
+///
+/// This is created by [`PrefixMapping::resolve()`].
+///
+/// # Examples
+///
+/// ```
+/// use curie::{Curie, PrefixMapping};
+///
+/// let mut mapping = PrefixMapping::default();
+/// mapping.add_prefix("foaf", "http://xmlns.com/foaf/0.1/").unwrap();
+///
+/// let curie = Curie::new(Some("foaf"), "name");
+/// let resolved = mapping.resolve(&curie).unwrap();
+///
+/// assert_eq!(resolved.namespace(), "http://xmlns.com/foaf/0.1/");
+/// assert_eq!(resolved.to_iri(), "http://xmlns.com/foaf/0.1/name");
+/// assert_eq!(format!("{resolved}"), "http://xmlns.com/foaf/0.1/name");
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ResolvedCurie<'a> {
+    namespace: Cow<'a, str>,
+    reference: &'a str,
+    suffix: &'a str,
+}
+
+impl<'a> ResolvedCurie<'a> {
+    /// The namespace that the prefix was mapped to, the part of its
+    /// URI template before the placeholder, or the complete IRI of a
+    /// term or of a reference resolved against the base IRI.
+    #[must_use]
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// The reference portion of the CURIE, as it is appended to the
+    /// namespace. This is empty for a term or a reference resolved
+    /// against the base IRI.
+    #[must_use]
+    pub fn reference(&self) -> &'a str {
+        self.reference
+    }
+
+    /// The part of the URI template that the prefix was mapped to
+    /// after the placeholder. This is empty unless the prefix is bound
+    /// to a template.
+    #[must_use]
+    pub fn suffix(&self) -> &'a str {
+        self.suffix
+    }
+
+    /// The parts of the complete IRI, in order.
+    pub(crate) fn parts(&self) -> [&str; 3] {
+        [&self.namespace, self.reference, self.suffix]
+    }
+
+    /// The length of the complete IRI.
+    pub(crate) fn len(&self) -> usize {
+        self.parts().iter().map(|part| part.len()).sum()
+    }
+
+    /// Build the complete IRI.
+    #[must_use]
+    pub fn to_iri(&self) -> String {
+        let mut iri = String::with_capacity(self.len());
+        for part in self.parts() {
+            iri.push_str(part);
+        }
+        iri
+    }
+}
+
+impl fmt::Display for ResolvedCurie<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.parts().iter().try_for_each(|part| f.write_str(part))
+    }
+}
+
+impl PrefixMapping {
+    /// Resolve the prefix of a [`Curie`], returning a [`ResolvedCurie`].
+    ///
+    /// The lookup of the prefix is done once, here, rather than every
+    /// time the IRI is needed. The CURIE is looked up as by
+    /// [`PrefixMapping::expand_curie()`]. Formatting the
+    /// [`ResolvedCurie`] with [`Display`](fmt::Display) writes the same
+    /// IRI directly to the formatter, without building a `String`.
+    ///
+    /// # Errors
+    ///
+    /// This will return [`ExpansionError`] if the prefix (or the default,
+    /// when there is no prefix) has no mapping, or if the reference can't
+    /// be appended to its namespace.
+    ///
+    /// # See also
+    ///
+    /// * [`PrefixMapping::expand_curie()`]
+    pub fn resolve<'a>(&'a self, curie: &Curie<'a>) -> Result<ResolvedCurie<'a>, ExpansionError> {
+        self.resolve_exploded(curie.prefix, curie.reference)
+    }
+
+    pub(crate) fn resolve_exploded<'a>(
+        &'a self,
+        prefix: Option<&str>,
+        reference: &'a str,
+    ) -> Result<ResolvedCurie<'a>, ExpansionError> {
+        if let Some(template) = prefix.and_then(|prefix| self.templates.get(prefix)) {
+            self.check_reference_chars(reference)?;
+            let (namespace, suffix) = template.split(reference)?;
+            return Ok(ResolvedCurie {
+                namespace: Cow::Borrowed(namespace),
+                reference,
+                suffix,
+            });
+        }
+        if prefix.is_none() {
+            if let Some(iri) = self.terms.get(reference) {
+                return Ok(ResolvedCurie {
+                    namespace: Cow::Borrowed(iri),
+                    reference: "",
+                    suffix: "",
+                });
+            }
+            if self.unprefixed == Unprefixed::Base {
+                self.check_reference_chars(reference)?;
+                let iri = self
+                    .resolve_iri(reference)
+                    .ok_or(ExpansionError::MissingBase)?;
+                return Ok(ResolvedCurie {
+                    namespace: Cow::Owned(iri),
+                    reference: "",
+                    suffix: "",
+                });
+            }
+        }
+        let namespace = self.namespace_for(prefix)?;
+        self.check_reference(namespace, reference)?;
+        Ok(ResolvedCurie {
+            namespace: Cow::Borrowed(namespace),
+            reference,
+            suffix: "",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_errors() {
+        let mut mapping = PrefixMapping::default();
+
+        assert_eq!(
+            mapping.resolve(&Curie::new(Some("foaf"), "name")),
+            Err(ExpansionError::UnknownPrefix(String::from("foaf")))
+        );
+        assert_eq!(
+            mapping.resolve(&Curie::new(None, "name")),
+            Err(ExpansionError::MissingDefault)
+        );
+
+        mapping.set_default("http://example.com/");
+        let resolved = mapping.resolve(&Curie::new(None, "name")).unwrap();
+        assert_eq!(resolved.namespace(), "http://example.com/");
+        assert_eq!(resolved.reference(), "name");
+        assert_eq!(resolved.to_iri(), "http://example.com/name");
+    }
+
+    #[test]
+    fn resolve_term() {
+        let mut mapping = PrefixMapping::default();
+        mapping.add_term("name", "http://xmlns.com/foaf/0.1/name");
+        let curie = Curie::new(None, "name");
+        let resolved = mapping.resolve(&curie).unwrap();
+        assert_eq!(Ok(resolved.to_iri()), mapping.expand_curie(&curie));
+        assert_eq!(resolved.to_string(), "http://xmlns.com/foaf/0.1/name");
+        assert_eq!(resolved.reference(), "");
+    }
+
+    #[test]
+    fn resolve_against_base() {
+        let mut mapping = PrefixMapping::default();
+        mapping.set_default("http://example.com/vocab/");
+        mapping.set_unprefixed(Unprefixed::Base);
+        let curie = Curie::new(None, "#me");
+        assert_eq!(mapping.resolve(&curie), Err(ExpansionError::MissingBase));
+
+        mapping.set_base("http://example.org/people/alice");
+        let expanded = mapping.expand_curie(&curie).unwrap();
+        assert_eq!(expanded, "http://example.org/people/alice#me");
+        let resolved = mapping.resolve(&curie).unwrap();
+        assert_eq!(resolved.to_iri(), expanded);
+        assert_eq!(format!("<{resolved}>"), format!("<{expanded}>"));
+        let mut out = String::new();
+        mapping.write_expanded(&curie, &mut out).unwrap();
+        assert_eq!(out, expanded);
+        assert_eq!(
+            mapping.expand_curie_reuse(&curie, |iri| iri.to_owned()),
+            Ok(expanded)
+        );
+    }
+}
@@ -0,0 +1,118 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{telemetry, Curie, ExpansionError, PrefixMapping, ResolvedCurie};
+use std::cell::RefCell;
+
+/// Scratch buffers larger than this are released after use, so that
+/// one unusually long IRI doesn't hold on to memory indefinitely.
+const MAX_RETAINED_CAPACITY: usize = 64 * 1024;
+
+thread_local! {
+    static SCRATCH: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+impl PrefixMapping {
+    /// Expand a parsed [`Curie`] into a reusable thread-local buffer,
+    /// passing the complete IRI to `f`.
+    ///
+    /// After the first call on a thread, this usually doesn't allocate,
+    /// which makes it suitable for tight loops where the IRI is only
+    /// needed briefly, such as for a lookup or to write it out.
+    ///
+    /// If `f` itself calls this method, the nested call falls back to
+    /// allocating a new buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::{Curie, PrefixMapping};
+    ///
+    /// let mut mapping = PrefixMapping::default();
+    /// mapping.add_prefix("foaf", "http://xmlns.com/foaf/0.1/").unwrap();
+    ///
+    /// let curie = Curie::new(Some("foaf"), "Agent");
+    /// let len = mapping.expand_curie_reuse(&curie, |iri| iri.len()).unwrap();
+    /// assert_eq!(len, "http://xmlns.com/foaf/0.1/Agent".len());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This will return [`ExpansionError`] if the expansion fails, in
+    /// which case `f` is not called.
+    ///
+    /// # See also
+    ///
+    /// * [`PrefixMapping::expand_curie()`]
+    /// * [`PrefixMapping::write_expanded()`]
+    pub fn expand_curie_reuse<R, F: FnOnce(&str) -> R>(
+        &self,
+        curie: &Curie,
+        f: F,
+    ) -> Result<R, ExpansionError> {
+        let resolved = self.resolve(curie);
+        telemetry::record_expansion(resolved.as_ref().map(ResolvedCurie::len));
+        let resolved = resolved?;
+        Ok(SCRATCH.with(|scratch| match scratch.try_borrow_mut() {
+            Ok(mut buffer) => {
+                buffer.clear();
+                for part in resolved.parts() {
+                    buffer.push_str(part);
+                }
+                let result = f(&buffer);
+                if buffer.capacity() > MAX_RETAINED_CAPACITY {
+                    *buffer = String::new();
+                }
+                result
+            }
+            Err(_) => f(&resolved.to_iri()),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expand_reuse_nested() {
+        let mut mapping = PrefixMapping::default();
+        mapping.set_default("http://example.com/");
+
+        let outer = Curie::new(None, "outer");
+        let inner = Curie::new(None, "inner");
+        let both = mapping
+            .expand_curie_reuse(&outer, |outer_iri| {
+                let inner_iri = mapping
+                    .expand_curie_reuse(&inner, |iri| iri.to_owned())
+                    .unwrap();
+                format!("{outer_iri} {inner_iri}")
+            })
+            .unwrap();
+        assert_eq!(both, "http://example.com/outer http://example.com/inner");
+    }
+
+    #[test]
+    fn expand_reuse_term() {
+        let mut mapping = PrefixMapping::default();
+        mapping.add_term("name", "http://xmlns.com/foaf/0.1/name");
+        let curie = Curie::new(None, "name");
+        assert_eq!(
+            mapping.expand_curie_reuse(&curie, |iri| iri.to_owned()),
+            mapping.expand_curie(&curie)
+        );
+    }
+
+    #[test]
+    fn expand_reuse_error() {
+        let mapping = PrefixMapping::default();
+        let curie = Curie::new(Some("foaf"), "Agent");
+        assert_eq!(
+            mapping.expand_curie_reuse(&curie, |_| unreachable!()),
+            Err::<(), _>(ExpansionError::UnknownPrefix(String::from("foaf")))
+        );
+    }
+}
@@ -0,0 +1,108 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{Curie, PrefixMapping, ShrinkError};
+use std::borrow::Cow;
+
+impl PrefixMapping {
+    /// Shrink an IRI, only returning a [`Curie`] if writing it out and
+    /// expanding it again gives back exactly the same IRI.
+    ///
+    /// [`PrefixMapping::shrink_iri()`] works on the IRI alone, so the
+    /// CURIE that it returns may not survive being written: a reference
+    /// of the default namespace that contains a colon is read back as
+    /// a prefixed CURIE, and a reference may be refused when it is
+    /// expanded, for example with
+    /// [`PrefixMapping::set_strict_references()`]. This checks the
+    /// result by expanding its string form.
+    ///
+    /// # Errors
+    ///
+    /// As for [`PrefixMapping::shrink_iri()`], and returns
+    /// [`ShrinkError::RoundTripMismatch`] if the CURIE doesn't expand back
+    /// to the IRI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::{Curie, PrefixMapping};
+    ///
+    /// let mut mapping = PrefixMapping::default();
+    /// mapping.set_default("http://example.com/");
+    /// mapping.add_prefix("urn", "http://example.com/elsewhere/").unwrap();
+    ///
+    /// assert_eq!(mapping.shrink_iri_verified("http://example.com/a"),
+    ///            Ok(Curie::new(None, "a")));
+    ///
+    /// // `urn:b` would be read back with the `urn` prefix.
+    /// assert_eq!(mapping.shrink_iri("http://example.com/urn:b"),
+    ///            Ok(Curie::new(None, "urn:b")));
+    /// assert!(mapping.shrink_iri_verified("http://example.com/urn:b").is_err());
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`PrefixMapping::compact_iri_verified()`]
+    pub fn shrink_iri_verified<'a>(&'a self, iri: &'a str) -> Result<Curie<'a>, ShrinkError> {
+        let curie = self.shrink_iri(iri)?;
+        match self.expand_curie_string(&curie.to_string()) {
+            Ok(expanded) if expanded == iri => Ok(curie),
+            _ => Err(ShrinkError::RoundTripMismatch),
+        }
+    }
+
+    /// Write an IRI as a CURIE if it can be shrunk safely, or as the
+    /// full IRI otherwise.
+    ///
+    /// This uses [`PrefixMapping::shrink_iri_verified()`], so whatever
+    /// is returned reads back as the same IRI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::PrefixMapping;
+    ///
+    /// let mut mapping = PrefixMapping::default();
+    /// mapping.add_prefix("ex", "http://example.com/").unwrap();
+    ///
+    /// assert_eq!(mapping.compact_iri_verified("http://example.com/a"), "ex:a");
+    /// assert_eq!(mapping.compact_iri_verified("http://example.org/a"),
+    ///            "http://example.org/a");
+    /// ```
+    #[must_use]
+    pub fn compact_iri_verified<'a>(&self, iri: &'a str) -> Cow<'a, str> {
+        match self.shrink_iri_verified(iri) {
+            Ok(curie) => Cow::Owned(curie.to_string()),
+            Err(_) => Cow::Borrowed(iri),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips() {
+        let mut mapping = PrefixMapping::default();
+        mapping.add_prefix("ex", "http://example.com/ns#").unwrap();
+        mapping.set_strict_references(true);
+
+        assert_eq!(
+            mapping.shrink_iri_verified("http://example.com/ns#a"),
+            Ok(Curie::new(Some("ex"), "a"))
+        );
+        // Shrinks, but the reference is refused when expanded.
+        assert!(mapping.shrink_iri("http://example.com/ns#a b").is_ok());
+        assert!(mapping
+            .shrink_iri_verified("http://example.com/ns#a b")
+            .is_err());
+        assert_eq!(
+            mapping.compact_iri_verified("http://example.com/ns#a b"),
+            "http://example.com/ns#a b"
+        );
+    }
+}
@@ -0,0 +1,128 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! JSON Schema descriptions of the serialized forms of this crate's
+//! types, for use with the [`schemars`] crate.
+
+use crate::{CurieBuf, PrefixMapping};
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+use std::borrow::Cow;
+
+/// A CURIE is serialized as its string form, such as `"foaf:Agent"`.
+impl JsonSchema for CurieBuf {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("Curie")
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        Cow::Borrowed("curie::CurieBuf")
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "description": "A CURIE, such as `foaf:Agent`: a reference, \
+                            optionally preceded by a prefix and a colon."
+        })
+    }
+}
+
+/// A mapping is serialized as an object with its default namespace, if
+/// any, its base IRI, and objects of its prefixes and their namespaces,
+/// its URI templates and its terms, in order.
+impl JsonSchema for PrefixMapping {
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("PrefixMapping")
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        Cow::Borrowed("curie::PrefixMapping")
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "object",
+            "description": "A mapping from prefixes to namespace IRIs, \
+                            used to expand CURIEs.",
+            "properties": {
+                "default": {
+                    "type": ["string", "null"],
+                    "description": "The namespace of CURIEs without a prefix."
+                },
+                "prefixes": {
+                    "type": "object",
+                    "description": "The namespace of each prefix.",
+                    "propertyNames": { "not": { "const": "_" } },
+                    "additionalProperties": { "type": "string" }
+                },
+                "base": {
+                    "type": "string",
+                    "description": "The IRI that relative IRIs are resolved against."
+                },
+                "templates": {
+                    "type": "object",
+                    "description": "The URI template of each prefix bound to one, \
+                                    with an `{id}` or `$1` placeholder.",
+                    "propertyNames": { "not": { "const": "_" } },
+                    "additionalProperties": { "type": "string" }
+                },
+                "terms": {
+                    "type": "object",
+                    "description": "The IRI of each term.",
+                    "additionalProperties": { "type": "string" }
+                }
+            },
+            "additionalProperties": false
+        })
+    }
+}
+
+#[cfg(feature = "serde_with")]
+impl serde_with::schemars_1::JsonSchemaAs<CurieBuf> for crate::serde_as::CurieString {
+    fn inline_schema() -> bool {
+        CurieBuf::inline_schema()
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        CurieBuf::schema_name()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        CurieBuf::schema_id()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        CurieBuf::json_schema(generator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schemas() {
+        let schema = schemars::schema_for!(PrefixMapping);
+        let value = schema.as_value();
+        assert_eq!(value["title"], "PrefixMapping");
+        assert_eq!(
+            value["properties"]["prefixes"]["additionalProperties"]["type"],
+            "string"
+        );
+        assert_eq!(value["properties"]["base"]["type"], "string");
+        assert_eq!(
+            value["properties"]["terms"]["additionalProperties"]["type"],
+            "string"
+        );
+
+        let schema = schemars::schema_for!(Vec<CurieBuf>);
+        assert_eq!(schema.as_value()["items"]["type"], "string");
+    }
+}
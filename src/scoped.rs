@@ -0,0 +1,284 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::template::UriTemplate;
+use crate::{InvalidPrefixError, PrefixMapping, XmlnsError};
+use std::ops::Deref;
+
+/// A binding as it was before a scope changed it, to be restored when
+/// the scope is popped.
+#[derive(Debug)]
+enum Saved {
+    Prefix {
+        prefix: String,
+        namespace: Option<(usize, String)>,
+        template: Option<(usize, UriTemplate)>,
+    },
+    Default(Option<String>),
+}
+
+/// A [`PrefixMapping`] with a stack of scopes, for formats such as XML
+/// where prefixes are bound for an element and its children.
+///
+/// [`ScopedPrefixMapping::push_scope()`] opens a scope. Prefixes bound
+/// in it shadow the bindings of the enclosing scopes until
+/// [`ScopedPrefixMapping::pop_scope()`] closes it and restores them,
+/// in their original order. Only the bindings that a scope changes are
+/// saved, so the mapping is never copied.
+///
+/// The mapping in scope is available through [`Deref`], for expanding
+/// and shrinking.
+///
+/// # Examples
+///
+/// ```
+/// use curie::{PrefixMapping, ScopedPrefixMapping};
+///
+/// let mut mapping = ScopedPrefixMapping::new(PrefixMapping::default());
+/// mapping.add_prefix("ex", "http://example.com/").unwrap();
+///
+/// mapping.push_scope();
+/// mapping.extend_from_xmlns([("xmlns:ex", "http://example.org/")]).unwrap();
+/// assert_eq!(mapping.expand_curie_string("ex:a"),
+///            Ok(String::from("http://example.org/a")));
+///
+/// assert!(mapping.pop_scope());
+/// assert_eq!(mapping.expand_curie_string("ex:a"),
+///            Ok(String::from("http://example.com/a")));
+/// ```
+#[derive(Debug, Default)]
+pub struct ScopedPrefixMapping {
+    mapping: PrefixMapping,
+    /// The bindings saved by each open scope, innermost last.
+    scopes: Vec<Vec<Saved>>,
+}
+
+impl ScopedPrefixMapping {
+    /// Construct a scoped mapping with `mapping` as the outermost
+    /// bindings.
+    #[must_use]
+    pub fn new(mapping: PrefixMapping) -> Self {
+        ScopedPrefixMapping {
+            mapping,
+            scopes: Vec::new(),
+        }
+    }
+
+    /// Open a scope.
+    pub fn push_scope(&mut self) {
+        self.scopes.push(Vec::new());
+    }
+
+    /// Close the innermost scope, restoring the bindings that it
+    /// changed.
+    ///
+    /// Returns `false` if no scope is open.
+    pub fn pop_scope(&mut self) -> bool {
+        let Some(saved) = self.scopes.pop() else {
+            return false;
+        };
+        for saved in saved.into_iter().rev() {
+            match saved {
+                Saved::Prefix {
+                    prefix,
+                    namespace,
+                    template,
+                } => {
+                    self.mapping.mapping.shift_remove(&prefix);
+                    self.mapping.templates.shift_remove(&prefix);
+                    if let Some((index, namespace)) = namespace {
+                        self.mapping.mapping.shift_insert(index, prefix, namespace);
+                    } else if let Some((index, template)) = template {
+                        self.mapping.templates.shift_insert(index, prefix, template);
+                    }
+                }
+                Saved::Default(default) => self.mapping.default = default,
+            }
+        }
+        true
+    }
+
+    /// The number of open scopes.
+    #[must_use]
+    pub fn depth(&self) -> usize {
+        self.scopes.len()
+    }
+
+    /// Bind a prefix in the innermost scope.
+    ///
+    /// # Errors
+    ///
+    /// As for [`PrefixMapping::add_prefix()`].
+    pub fn add_prefix(&mut self, prefix: &str, value: &str) -> Result<(), InvalidPrefixError> {
+        self.save_prefix(prefix);
+        self.mapping.add_prefix(prefix, value)
+    }
+
+    /// Remove a prefix in the innermost scope.
+    ///
+    /// # Errors
+    ///
+    /// As for [`PrefixMapping::remove_prefix()`].
+    pub fn remove_prefix(&mut self, prefix: &str) -> Result<Option<String>, InvalidPrefixError> {
+        self.save_prefix(prefix);
+        self.mapping.remove_prefix(prefix)
+    }
+
+    /// Set the default prefix in the innermost scope.
+    pub fn set_default(&mut self, default: &str) {
+        self.save_default();
+        self.mapping.set_default(default);
+    }
+
+    /// Remove the default prefix in the innermost scope.
+    pub fn unset_default(&mut self) {
+        self.save_default();
+        self.mapping.unset_default();
+    }
+
+    /// Apply the XML namespace declarations among the attributes of an
+    /// element in the innermost scope.
+    ///
+    /// # Errors
+    ///
+    /// As for [`PrefixMapping::extend_from_xmlns()`].
+    pub fn extend_from_xmlns<'a, I>(&mut self, attributes: I) -> Result<(), XmlnsError>
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        let attributes: Vec<_> = attributes.into_iter().collect();
+        for &(name, _) in &attributes {
+            if name == "xmlns" {
+                self.save_default();
+            } else if let Some(prefix) = name.strip_prefix("xmlns:") {
+                self.save_prefix(prefix);
+            }
+        }
+        self.mapping.extend_from_xmlns(attributes)
+    }
+
+    /// Take the mapping, with the bindings of any open scopes.
+    #[must_use]
+    pub fn into_inner(self) -> PrefixMapping {
+        self.mapping
+    }
+
+    fn save_prefix(&mut self, prefix: &str) {
+        if let Some(scope) = self.scopes.last_mut() {
+            let namespace = self
+                .mapping
+                .mapping
+                .get_full(prefix)
+                .map(|(index, _, namespace)| (index, namespace.clone()));
+            let template = self
+                .mapping
+                .templates
+                .get_full(prefix)
+                .map(|(index, _, template)| (index, template.clone()));
+            scope.push(Saved::Prefix {
+                prefix: String::from(prefix),
+                namespace,
+                template,
+            });
+        }
+    }
+
+    fn save_default(&mut self) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.push(Saved::Default(self.mapping.default.clone()));
+        }
+    }
+}
+
+impl Deref for ScopedPrefixMapping {
+    type Target = PrefixMapping;
+
+    fn deref(&self) -> &PrefixMapping {
+        &self.mapping
+    }
+}
+
+impl From<PrefixMapping> for ScopedPrefixMapping {
+    fn from(mapping: PrefixMapping) -> Self {
+        ScopedPrefixMapping::new(mapping)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested_scopes() {
+        let mut base = PrefixMapping::default();
+        base.add_prefix("a", "http://example.com/a/").unwrap();
+        base.add_prefix("b", "http://example.com/b/").unwrap();
+        base.add_prefix("c", "http://example.com/c/").unwrap();
+        let mut mapping = ScopedPrefixMapping::new(base.clone());
+
+        mapping.push_scope();
+        mapping
+            .extend_from_xmlns([
+                ("xmlns", "http://example.com/"),
+                ("xmlns:a", "http://example.org/a/"),
+                ("xmlns:b", ""),
+                ("xmlns:d", "http://example.com/d/"),
+            ])
+            .unwrap();
+        mapping.push_scope();
+        mapping.add_prefix("a", "http://example.net/a/").unwrap();
+        mapping.remove_prefix("d").unwrap();
+        assert_eq!(mapping.depth(), 2);
+        assert_eq!(
+            mapping.expand_curie_string("a:x"),
+            Ok(String::from("http://example.net/a/x"))
+        );
+
+        assert!(mapping.pop_scope());
+        assert_eq!(
+            mapping.expand_curie_string("a:x"),
+            Ok(String::from("http://example.org/a/x"))
+        );
+        assert_eq!(
+            mapping.expand_curie_string("d:x"),
+            Ok(String::from("http://example.com/d/x"))
+        );
+        assert_eq!(
+            mapping.expand_curie_string("x"),
+            Ok(String::from("http://example.com/x"))
+        );
+        assert!(mapping.expand_curie_string("b:x").is_err());
+
+        assert!(mapping.pop_scope());
+        assert!(!mapping.pop_scope());
+        let prefixes: Vec<&str> = mapping.mappings().map(|(p, _)| p).collect();
+        assert_eq!(prefixes, ["a", "b", "c"]);
+        assert_eq!(mapping.into_inner(), base);
+    }
+
+    #[test]
+    fn outside_scopes() {
+        let mut base = PrefixMapping::default();
+        base.add_prefix_template("t", "http://example.com/{id}")
+            .unwrap();
+        let mut mapping = ScopedPrefixMapping::from(base);
+
+        // Changes outside of any scope are kept.
+        mapping.set_default("http://example.com/");
+        assert!(!mapping.pop_scope());
+        assert_eq!(mapping.default_namespace(), Some("http://example.com/"));
+
+        mapping.push_scope();
+        mapping.unset_default();
+        mapping.add_prefix("t", "http://example.org/").unwrap();
+        assert!(mapping.pop_scope());
+        assert_eq!(mapping.default_namespace(), Some("http://example.com/"));
+        assert_eq!(
+            mapping.expand_curie_string("t:1"),
+            Ok(String::from("http://example.com/1"))
+        );
+    }
+}
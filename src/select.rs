@@ -0,0 +1,153 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{syntax, PrefixMapping};
+use indexmap::IndexMap;
+
+impl PrefixMapping {
+    /// Choose a small set of the prefixes in this mapping that shrinks
+    /// at least a fraction `target` of a corpus of IRIs, using at most
+    /// `max_prefixes` prefixes.
+    ///
+    /// This is meant for a large mapping of candidates, such as one
+    /// built from a [`Registry`](crate::Registry), when deciding which
+    /// prefixes a document should declare. Each IRI counts once for
+    /// every time it appears in `iris`.
+    ///
+    /// Prefixes are chosen greedily: the next prefix is always the one
+    /// that shrinks the most IRIs that aren't shrunk yet, preferring
+    /// the one that saves the most text when several shrink as many.
+    /// This finds a near-minimal set, not necessarily the smallest.
+    /// Selection stops once the target is reached, the limit is hit, or
+    /// no remaining prefix helps.
+    ///
+    /// The returned mapping has the chosen prefixes in the order they
+    /// were chosen, so the most useful come first. It has no default
+    /// namespace and no URI templates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::PrefixMapping;
+    ///
+    /// let mut candidates = PrefixMapping::default();
+    /// candidates.add_prefix("ex", "http://example.com/").unwrap();
+    /// candidates.add_prefix("exv", "http://example.com/vocab/").unwrap();
+    /// candidates.add_prefix("foaf", "http://xmlns.com/foaf/0.1/").unwrap();
+    /// candidates.add_prefix("rare", "http://rare.example/").unwrap();
+    ///
+    /// let iris = [
+    ///     "http://example.com/vocab/a",
+    ///     "http://example.com/vocab/b",
+    ///     "http://example.com/c",
+    ///     "http://xmlns.com/foaf/0.1/name",
+    ///     "http://xmlns.com/foaf/0.1/name",
+    ///     "http://rare.example/x",
+    /// ];
+    ///
+    /// let chosen = candidates.select_prefixes(iris, 0.8, 10);
+    /// assert_eq!(chosen.mappings().map(|(p, _)| p).collect::<Vec<_>>(),
+    ///            ["ex", "foaf"]);
+    /// ```
+    #[must_use]
+    pub fn select_prefixes<I>(&self, iris: I, target: f64, max_prefixes: usize) -> PrefixMapping
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let mut counts: IndexMap<String, usize> = IndexMap::new();
+        for iri in iris {
+            *counts.entry(String::from(iri.as_ref())).or_default() += 1;
+        }
+        let total: usize = counts.values().sum();
+        let needed = (target.clamp(0.0, 1.0) * total as f64).ceil() as usize;
+
+        let mut uncovered: Vec<(&str, usize)> = counts
+            .iter()
+            .map(|(iri, &count)| (iri.as_str(), count))
+            .collect();
+        let mut covered = 0;
+        let mut selected = PrefixMapping::default();
+        while covered < needed && selected.mapping.len() < max_prefixes {
+            let best = self
+                .mapping
+                .iter()
+                .filter(|(prefix, _)| !selected.mapping.contains_key(*prefix))
+                .map(|(prefix, namespace)| {
+                    let (gain, saved) = uncovered
+                        .iter()
+                        .filter(|(iri, _)| syntax::strip_namespace(iri, namespace).is_some())
+                        .fold((0, 0), |(gain, saved), &(_, count)| {
+                            let per_use = (namespace.len() + 2).saturating_sub(prefix.len() + 1);
+                            (gain + count, saved + count * per_use)
+                        });
+                    ((gain, saved), prefix, namespace)
+                })
+                // `max_by_key` picks the last of equal keys, so search
+                // in reverse to prefer the first.
+                .rev()
+                .max_by_key(|&(key, _, _)| key);
+            let Some(((gain, _), prefix, namespace)) = best else {
+                break;
+            };
+            if gain == 0 {
+                break;
+            }
+            uncovered.retain(|(iri, _)| syntax::strip_namespace(iri, namespace).is_none());
+            covered += gain;
+            selected
+                .add_prefix(prefix, namespace)
+                .expect("prefixes in a mapping are valid");
+        }
+        selected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selection() {
+        let mut candidates = PrefixMapping::default();
+        candidates.add_prefix("a", "http://a.example/").unwrap();
+        candidates.add_prefix("b", "http://b.example/").unwrap();
+        candidates.add_prefix("c", "http://c.example/").unwrap();
+        let iris = [
+            "http://a.example/1",
+            "http://b.example/1",
+            "http://b.example/2",
+            "http://c.example/1",
+        ];
+
+        let names = |mapping: &PrefixMapping| {
+            mapping
+                .mappings()
+                .map(|(prefix, _)| String::from(prefix))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            names(&candidates.select_prefixes(iris, 0.0, 10)),
+            Vec::<String>::new()
+        );
+        assert_eq!(names(&candidates.select_prefixes(iris, 0.5, 10)), ["b"]);
+        // Ties go to the prefix that was added first.
+        assert_eq!(
+            names(&candidates.select_prefixes(iris, 0.75, 10)),
+            ["b", "a"]
+        );
+        assert_eq!(names(&candidates.select_prefixes(iris, 1.0, 2)), ["b", "a"]);
+        assert_eq!(
+            names(&candidates.select_prefixes(iris, 1.0, 10)),
+            ["b", "a", "c"]
+        );
+        // Unmatched IRIs can't be covered.
+        assert_eq!(
+            names(&candidates.select_prefixes(["http://d.example/1"], 1.0, 10)),
+            Vec::<String>::new()
+        );
+    }
+}
@@ -0,0 +1,94 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Adapters for use with the [`serde_with`] crate.
+//!
+//! This requires the `serde_with` feature.
+//!
+//! [`CurieString`] serializes a [`CurieBuf`] as its string form, such
+//! as `"foaf:Agent"`. As JSON object keys must be strings, this allows
+//! maps keyed by CURIEs to be serialized, as well as collections of
+//! CURIEs:
+//!
+//! ```
+//! use curie::CurieBuf;
+//! use curie::serde_as::CurieString;
+//! use serde::{Deserialize, Serialize};
+//! use serde_with::serde_as;
+//! use std::collections::BTreeMap;
+//!
+//! #[serde_as]
+//! #[derive(Debug, Deserialize, PartialEq, Serialize)]
+//! struct Labels {
+//!     #[serde_as(as = "BTreeMap<CurieString, _>")]
+//!     labels: BTreeMap<CurieBuf, String>,
+//!     #[serde_as(as = "Vec<CurieString>")]
+//!     hidden: Vec<CurieBuf>,
+//! }
+//!
+//! let mut labels = BTreeMap::new();
+//! labels.insert(CurieBuf::new(Some("foaf"), "Person"), String::from("Person"));
+//! let value = Labels {
+//!     labels,
+//!     hidden: vec![CurieBuf::new(Some("foaf"), "Agent")],
+//! };
+//!
+//! let json = serde_json::to_string(&value).unwrap();
+//! assert_eq!(json, r#"{"labels":{"foaf:Person":"Person"},"hidden":["foaf:Agent"]}"#);
+//! assert_eq!(serde_json::from_str::<Labels>(&json).unwrap(), value);
+//! ```
+
+use crate::{Curie, CurieBuf};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serializer};
+use serde_with::{DeserializeAs, SerializeAs};
+use std::borrow::Cow;
+
+/// Serialize a [`CurieBuf`] as a string, such as `"foaf:Agent"`.
+///
+/// See the [module documentation](self) for an example.
+#[derive(Clone, Copy, Debug)]
+pub struct CurieString;
+
+impl SerializeAs<CurieBuf> for CurieString {
+    fn serialize_as<S: Serializer>(source: &CurieBuf, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(source)
+    }
+}
+
+impl<'de> DeserializeAs<'de, CurieBuf> for CurieString {
+    fn deserialize_as<D: Deserializer<'de>>(deserializer: D) -> Result<CurieBuf, D::Error> {
+        let curie_str = Cow::<str>::deserialize(deserializer)?;
+        Curie::try_parse(&curie_str)
+            .map(CurieBuf::from)
+            .map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Serialize;
+    use serde_with::serde_as;
+
+    #[serde_as]
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Curies(#[serde_as(as = "Vec<CurieString>")] Vec<CurieBuf>);
+
+    #[test]
+    fn curie_strings() {
+        let curies = Curies(vec![
+            CurieBuf::new(Some("foaf"), "Agent"),
+            CurieBuf::new(None, "name"),
+        ]);
+        let json = serde_json::to_string(&curies).unwrap();
+        assert_eq!(json, r#"["foaf:Agent","name"]"#);
+        assert_eq!(serde_json::from_str::<Curies>(&json).unwrap(), curies);
+
+        let err = serde_json::from_str::<Curies>(r#"["1st:Agent"]"#).unwrap_err();
+        assert!(err.to_string().contains("`1st` is not a valid NCName"));
+    }
+}
@@ -0,0 +1,311 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Implementations of [`serde`]'s traits, with the `serde` feature.
+
+use crate::{Curie, CurieBuf, PrefixMapping};
+use serde::de::{Error as _, MapAccess, Visitor};
+use serde::ser::{SerializeMap, SerializeStruct};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
+use std::fmt;
+
+/// A CURIE is serialized as its string form, such as `"foaf:Agent"`.
+impl Serialize for Curie<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// A CURIE is deserialized from its string form, which is checked with
+/// [`Curie::try_parse()`].
+///
+/// The prefix and reference are borrowed from the input, so this only
+/// works with formats that can lend strings, and fails for strings
+/// that had to be unescaped. Use [`CurieBuf`] otherwise.
+impl<'de: 'c, 'c> Deserialize<'de> for Curie<'c> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let curie_str = <&'de str>::deserialize(deserializer)?;
+        Curie::try_parse(curie_str).map_err(D::Error::custom)
+    }
+}
+
+/// A CURIE is serialized as its string form, such as `"foaf:Agent"`.
+impl Serialize for CurieBuf {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// A CURIE is deserialized from its string form, which is checked with
+/// [`Curie::try_parse()`].
+impl<'de> Deserialize<'de> for CurieBuf {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let curie_str = Cow::<str>::deserialize(deserializer)?;
+        Curie::try_parse(&curie_str)
+            .map(CurieBuf::from)
+            .map_err(D::Error::custom)
+    }
+}
+
+/// A mapping is serialized as an object with its default namespace, or
+/// `null`, its base IRI, and objects of its prefixes and their
+/// namespaces, its prefixes bound to URI templates and its terms, each
+/// in the order they were added:
+///
+/// ```json
+/// {
+///   "default": "http://example.com/",
+///   "base": "http://example.com/doc",
+///   "prefixes": { "foaf": "http://xmlns.com/foaf/0.1/" },
+///   "templates": { "doi": "https://doi.org/{id}" },
+///   "terms": { "name": "http://xmlns.com/foaf/0.1/name" }
+/// }
+/// ```
+///
+/// `base`, `templates` and `terms` are left out when they are empty.
+/// Protection, strictness and the parent mapping are not serialized.
+impl Serialize for PrefixMapping {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("PrefixMapping", 5)?;
+        state.serialize_field("default", &self.default)?;
+        match self.base {
+            Some(ref base) => state.serialize_field("base", base)?,
+            None => state.skip_field("base")?,
+        }
+        state.serialize_field("prefixes", &Prefixes(self))?;
+        if self.templates.is_empty() {
+            state.skip_field("templates")?;
+        } else {
+            state.serialize_field("templates", &Templates(self))?;
+        }
+        if self.terms.is_empty() {
+            state.skip_field("terms")?;
+        } else {
+            state.serialize_field("terms", &Terms(self))?;
+        }
+        state.end()
+    }
+}
+
+/// The prefixes of a mapping, serialized as an object.
+struct Prefixes<'a>(&'a PrefixMapping);
+
+impl Serialize for Prefixes<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.mappings())
+    }
+}
+
+/// The URI templates of a mapping, serialized as an object.
+struct Templates<'a>(&'a PrefixMapping);
+
+impl Serialize for Templates<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.templates.len()))?;
+        for prefix in self.0.templates.keys() {
+            map.serialize_entry(prefix, &self.0.prefix_template(prefix))?;
+        }
+        map.end()
+    }
+}
+
+/// The terms of a mapping, serialized as an object.
+struct Terms<'a>(&'a PrefixMapping);
+
+impl Serialize for Terms<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.terms())
+    }
+}
+
+/// The fields of a serialized mapping.
+const FIELDS: &[&str] = &["default", "base", "prefixes", "templates", "terms"];
+
+/// A mapping is deserialized from the form that it is serialized to.
+/// Every field may be left out.
+///
+/// Prefixes are added with [`PrefixMapping::add_prefix()`] and
+/// templates with [`PrefixMapping::add_prefix_template()`], so a
+/// reserved prefix or an invalid template is an error.
+impl<'de> Deserialize<'de> for PrefixMapping {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_struct("PrefixMapping", FIELDS, PrefixMappingVisitor)
+    }
+}
+
+struct PrefixMappingVisitor;
+
+impl<'de> Visitor<'de> for PrefixMappingVisitor {
+    type Value = PrefixMapping;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a prefix mapping")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<PrefixMapping, A::Error> {
+        let mut mapping = PrefixMapping::default();
+        let mut seen = [false; FIELDS.len()];
+        while let Some(key) = map.next_key::<Cow<str>>()? {
+            let Some(field) = FIELDS.iter().position(|&field| field == key) else {
+                return Err(A::Error::unknown_field(&key, FIELDS));
+            };
+            if std::mem::replace(&mut seen[field], true) {
+                return Err(A::Error::duplicate_field(FIELDS[field]));
+            }
+            match FIELDS[field] {
+                "default" => {
+                    if let Some(default) = map.next_value::<Option<String>>()? {
+                        mapping.set_default(&default);
+                    }
+                }
+                "base" => {
+                    if let Some(base) = map.next_value::<Option<String>>()? {
+                        mapping.set_base(&base);
+                    }
+                }
+                "prefixes" => map.next_value_seed(EntriesSeed(&mut mapping, Entries::Prefixes))?,
+                "templates" => {
+                    map.next_value_seed(EntriesSeed(&mut mapping, Entries::Templates))?;
+                }
+                _ => map.next_value_seed(EntriesSeed(&mut mapping, Entries::Terms))?,
+            }
+        }
+        Ok(mapping)
+    }
+}
+
+/// Which of the objects of a serialized mapping is being read.
+#[derive(Clone, Copy)]
+enum Entries {
+    Prefixes,
+    Templates,
+    Terms,
+}
+
+/// Adds the entries of one of the objects of a serialized mapping to a
+/// mapping.
+struct EntriesSeed<'a>(&'a mut PrefixMapping, Entries);
+
+impl<'de> serde::de::DeserializeSeed<'de> for EntriesSeed<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for EntriesSeed<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self.1 {
+            Entries::Prefixes => "an object of prefixes and namespaces",
+            Entries::Templates => "an object of prefixes and URI templates",
+            Entries::Terms => "an object of terms and IRIs",
+        })
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        while let Some((key, value)) = map.next_entry::<Cow<str>, Cow<str>>()? {
+            match self.1 {
+                Entries::Prefixes => self.0.add_prefix(&key, &value).map_err(|err| {
+                    A::Error::custom(format_args!("can't add the prefix `{key}`: {err}"))
+                })?,
+                Entries::Templates => self.0.add_prefix_template(&key, &value).map_err(|err| {
+                    A::Error::custom(format_args!("can't add the template for `{key}`: {err}"))
+                })?,
+                Entries::Terms => self.0.add_term(&key, &value),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn curies() {
+        let curie = Curie::new(Some("foaf"), "Agent");
+        let json = serde_json::to_string(&curie).unwrap();
+        assert_eq!(json, r#""foaf:Agent""#);
+        assert_eq!(serde_json::from_str::<Curie>(&json).unwrap(), curie);
+        assert_eq!(
+            serde_json::from_str::<CurieBuf>(&json).unwrap(),
+            CurieBuf::from(curie)
+        );
+
+        // An escaped string can't be borrowed.
+        let escaped = r#""foaf:\u0041gent""#;
+        assert!(serde_json::from_str::<Curie>(escaped).is_err());
+        assert_eq!(
+            serde_json::from_str::<CurieBuf>(escaped).unwrap(),
+            CurieBuf::from(curie)
+        );
+
+        let err = serde_json::from_str::<CurieBuf>(r#""1st:Agent""#).unwrap_err();
+        assert!(err.to_string().contains("`1st` is not a valid NCName"));
+    }
+
+    #[test]
+    fn prefix_mappings() {
+        let mut mapping = PrefixMapping::default();
+        assert_eq!(
+            serde_json::to_string(&mapping).unwrap(),
+            r#"{"default":null,"prefixes":{}}"#
+        );
+
+        mapping.set_default("http://example.com/");
+        mapping.add_prefix("z", "http://z.example/").unwrap();
+        mapping.add_prefix("a", "http://a.example/").unwrap();
+        let json = serde_json::to_string(&mapping).unwrap();
+        assert_eq!(
+            json,
+            r#"{"default":"http://example.com/","prefixes":{"z":"http://z.example/","a":"http://a.example/"}}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<PrefixMapping>(&json).unwrap(),
+            mapping
+        );
+
+        assert_eq!(
+            serde_json::from_str::<PrefixMapping>("{}").unwrap(),
+            PrefixMapping::default()
+        );
+        let err =
+            serde_json::from_str::<PrefixMapping>(r#"{"prefixes":{"_":"http://b/"}}"#).unwrap_err();
+        assert!(err.to_string().contains("can't add the prefix `_`"));
+        assert!(serde_json::from_str::<PrefixMapping>(r#"{"parent":{}}"#).is_err());
+        let err =
+            serde_json::from_str::<PrefixMapping>(r#"{"templates":{"doi":"https://doi.org/"}}"#)
+                .unwrap_err();
+        assert!(err.to_string().contains("can't add the template for `doi`"));
+    }
+
+    #[test]
+    fn base_templates_and_terms() {
+        let mut mapping = PrefixMapping::default();
+        mapping.set_base("http://example.com/doc");
+        mapping
+            .add_prefix("foaf", "http://xmlns.com/foaf/0.1/")
+            .unwrap();
+        mapping
+            .add_prefix_template("doi", "https://doi.org/{id}")
+            .unwrap();
+        mapping.add_term("name", "http://xmlns.com/foaf/0.1/name");
+
+        let json = serde_json::to_string(&mapping).unwrap();
+        assert_eq!(
+            json,
+            r#"{"default":null,"base":"http://example.com/doc","prefixes":{"foaf":"http://xmlns.com/foaf/0.1/"},"templates":{"doi":"https://doi.org/{id}"},"terms":{"name":"http://xmlns.com/foaf/0.1/name"}}"#
+        );
+        let read = serde_json::from_str::<PrefixMapping>(&json).unwrap();
+        assert_eq!(read, mapping);
+        assert_eq!(read.base(), Some("http://example.com/doc"));
+    }
+}
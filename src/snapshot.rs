@@ -0,0 +1,196 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{Curie, CurieBuf, ExpansionError, InvalidPrefixError, PrefixMapping, ShrinkError};
+use arc_swap::ArcSwap;
+use std::sync::Arc;
+
+/// A [`PrefixMapping`] that is replaced as a whole when it changes, so
+/// that readers never wait.
+///
+/// Readers take a snapshot of the current mapping without locking, and
+/// keep seeing that snapshot, unaffected by later changes, for as long
+/// as they hold it. Writers change a copy of the current mapping and
+/// then publish it atomically. This makes reads as cheap as possible at
+/// the cost of copying the mapping for every change, which suits
+/// read-heavy services that are reconfigured occasionally.
+///
+/// Compare with [`ConcurrentPrefixMapping`](crate::ConcurrentPrefixMapping),
+/// which changes the mapping in place under a lock.
+///
+/// This requires the `arc-swap` feature.
+///
+/// # Examples
+///
+/// ```
+/// use curie::SnapshotPrefixMapping;
+///
+/// let mapping = SnapshotPrefixMapping::default();
+/// mapping.add_prefix("foaf", "http://xmlns.com/foaf/0.1/").unwrap();
+///
+/// let snapshot = mapping.snapshot();
+/// mapping.update(|m| m.add_prefix("foaf", "http://example.com/foaf/")).unwrap();
+///
+/// // The snapshot is unaffected by the change.
+/// assert_eq!(snapshot.expand_curie_string("foaf:Agent"),
+///            Ok(String::from("http://xmlns.com/foaf/0.1/Agent")));
+/// assert_eq!(mapping.expand_curie_string("foaf:Agent"),
+///            Ok(String::from("http://example.com/foaf/Agent")));
+/// ```
+#[derive(Debug, Default)]
+pub struct SnapshotPrefixMapping {
+    current: ArcSwap<PrefixMapping>,
+}
+
+impl SnapshotPrefixMapping {
+    /// Construct a snapshot mapping starting from `mapping`.
+    #[must_use]
+    pub fn new(mapping: PrefixMapping) -> Self {
+        SnapshotPrefixMapping {
+            current: ArcSwap::from_pointee(mapping),
+        }
+    }
+
+    /// Take a snapshot of the current mapping.
+    #[must_use]
+    pub fn snapshot(&self) -> Arc<PrefixMapping> {
+        self.current.load_full()
+    }
+
+    /// Replace the mapping.
+    pub fn store(&self, mapping: PrefixMapping) {
+        self.current.store(Arc::new(mapping));
+    }
+
+    /// Change a copy of the current mapping with `f` and publish it.
+    ///
+    /// If another writer publishes a mapping in the meantime, `f` is
+    /// called again with a copy of that one, so `f` may be called more
+    /// than once. Nothing is published if `f` returns an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error returned by `f`.
+    pub fn update<E, F>(&self, mut f: F) -> Result<(), E>
+    where
+        F: FnMut(&mut PrefixMapping) -> Result<(), E>,
+    {
+        loop {
+            let current = self.current.load_full();
+            let mut next = PrefixMapping::clone(&current);
+            f(&mut next)?;
+            let previous = self.current.compare_and_swap(&current, Arc::new(next));
+            if Arc::ptr_eq(&previous, &current) {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Set a default prefix and publish the change.
+    ///
+    /// See [`PrefixMapping::set_default()`].
+    pub fn set_default(&self, default: &str) {
+        self.current.rcu(|current| {
+            let mut next = PrefixMapping::clone(current);
+            next.set_default(default);
+            next
+        });
+    }
+
+    /// Add a prefix and publish the change.
+    ///
+    /// # Errors
+    ///
+    /// See [`PrefixMapping::add_prefix()`].
+    pub fn add_prefix(&self, prefix: &str, value: &str) -> Result<(), InvalidPrefixError> {
+        self.update(|mapping| mapping.add_prefix(prefix, value))
+    }
+
+    /// Remove a prefix and publish the change.
+    ///
+    /// # Errors
+    ///
+    /// See [`PrefixMapping::remove_prefix()`].
+    pub fn remove_prefix(&self, prefix: &str) -> Result<Option<String>, InvalidPrefixError> {
+        let mut removed = None;
+        self.update(|mapping| {
+            removed = mapping.remove_prefix(prefix)?;
+            Ok(())
+        })?;
+        Ok(removed)
+    }
+
+    /// Expand a CURIE using the current mapping.
+    ///
+    /// # Errors
+    ///
+    /// See [`PrefixMapping::expand_curie_string()`].
+    pub fn expand_curie_string(&self, curie_str: &str) -> Result<String, ExpansionError> {
+        self.current.load().expand_curie_string(curie_str)
+    }
+
+    /// Expand a parsed [`Curie`] using the current mapping.
+    ///
+    /// # Errors
+    ///
+    /// See [`PrefixMapping::expand_curie()`].
+    pub fn expand_curie(&self, curie: &Curie) -> Result<String, ExpansionError> {
+        self.current.load().expand_curie(curie)
+    }
+
+    /// Shrink an IRI using the current mapping, returning an owned CURIE.
+    ///
+    /// # Errors
+    ///
+    /// See [`PrefixMapping::shrink_iri()`].
+    pub fn shrink_iri(&self, iri: &str) -> Result<CurieBuf, ShrinkError> {
+        self.current.load().shrink_iri(iri).map(CurieBuf::from)
+    }
+}
+
+impl From<PrefixMapping> for SnapshotPrefixMapping {
+    fn from(mapping: PrefixMapping) -> Self {
+        SnapshotPrefixMapping::new(mapping)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn concurrent_updates() {
+        let mapping = SnapshotPrefixMapping::default();
+        std::thread::scope(|scope| {
+            for n in 0..8 {
+                let mapping = &mapping;
+                scope.spawn(move || {
+                    mapping
+                        .add_prefix(&format!("p{n}"), &format!("http://example.com/{n}/"))
+                        .unwrap();
+                });
+            }
+        });
+        // No update was lost.
+        assert_eq!(mapping.snapshot().mappings().count(), 8);
+
+        let before = mapping.snapshot();
+        assert_eq!(
+            mapping.update(|m| {
+                m.remove_prefix("p0")?;
+                m.add_prefix("_", "http://example.com/")
+            }),
+            Err(InvalidPrefixError::ReservedPrefix)
+        );
+        assert!(Arc::ptr_eq(&before, &mapping.snapshot()));
+
+        mapping.set_default("http://example.com/");
+        assert_eq!(
+            mapping.shrink_iri("http://example.com/x"),
+            Ok(CurieBuf::new(None, "x"))
+        );
+    }
+}
@@ -0,0 +1,158 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Ordering and grouping collections of CURIEs for people to read.
+//!
+//! CURIEs are ordered by prefix, with CURIEs that have no prefix first,
+//! and then by reference. Runs of digits are compared by their numeric
+//! value, so `ex:item2` comes before `ex:item10`, while zero-padded
+//! identifiers such as `GO:0000001` keep their usual order.
+//!
+//! ```
+//! use curie::Curie;
+//! use curie::sort;
+//!
+//! let mut curies = vec![
+//!     Curie::new(Some("GO"), "0008150"),
+//!     Curie::new(Some("ex"), "item10"),
+//!     Curie::new(Some("GO"), "0000001"),
+//!     Curie::new(Some("ex"), "item2"),
+//! ];
+//! sort::sort_curies(&mut curies);
+//! assert_eq!(
+//!     curies.iter().map(ToString::to_string).collect::<Vec<_>>(),
+//!     ["GO:0000001", "GO:0008150", "ex:item2", "ex:item10"]
+//! );
+//!
+//! let groups = sort::group_by_prefix(curies);
+//! assert_eq!(groups.keys().collect::<Vec<_>>(), [&Some("GO"), &Some("ex")]);
+//! assert_eq!(groups[&Some("ex")].len(), 2);
+//! ```
+
+use crate::Curie;
+use indexmap::IndexMap;
+use std::cmp::Ordering;
+
+/// Compare two CURIEs by prefix and then by reference, comparing runs
+/// of digits by their numeric value.
+///
+/// This is a total order: strings that only differ in the zero padding
+/// of their numbers are ordered as plain strings, so only equal CURIEs
+/// compare as equal.
+#[must_use]
+pub fn compare(a: &Curie, b: &Curie) -> Ordering {
+    match (a.prefix, b.prefix) {
+        (None, Some(_)) => Ordering::Less,
+        (Some(_), None) => Ordering::Greater,
+        (Some(a), Some(b)) => natural_cmp(a, b),
+        (None, None) => Ordering::Equal,
+    }
+    .then_with(|| natural_cmp(a.reference, b.reference))
+}
+
+/// Sort CURIEs into the order of [`compare()`].
+pub fn sort_curies(curies: &mut [Curie]) {
+    curies.sort_by(compare);
+}
+
+/// Group CURIEs by their prefix, with the groups and the CURIEs in
+/// each group in the order of [`compare()`].
+///
+/// CURIEs without a prefix are grouped under `None`, which comes first.
+#[must_use]
+pub fn group_by_prefix<'c, I>(curies: I) -> IndexMap<Option<&'c str>, Vec<Curie<'c>>>
+where
+    I: IntoIterator<Item = Curie<'c>>,
+{
+    let mut curies: Vec<Curie<'c>> = curies.into_iter().collect();
+    sort_curies(&mut curies);
+    let mut groups: IndexMap<Option<&'c str>, Vec<Curie<'c>>> = IndexMap::new();
+    for curie in curies {
+        groups.entry(curie.prefix).or_default().push(curie);
+    }
+    groups
+}
+
+/// Compare strings, treating runs of ASCII digits as numbers.
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a_rest, mut b_rest) = (a.as_bytes(), b.as_bytes());
+    while let (Some(&a_first), Some(&b_first)) = (a_rest.first(), b_rest.first()) {
+        let ordering = if a_first.is_ascii_digit() && b_first.is_ascii_digit() {
+            let (a_digits, a_tail) = split_digits(a_rest);
+            let (b_digits, b_tail) = split_digits(b_rest);
+            a_rest = a_tail;
+            b_rest = b_tail;
+            compare_numbers(a_digits, b_digits)
+        } else {
+            a_rest = &a_rest[1..];
+            b_rest = &b_rest[1..];
+            a_first.cmp(&b_first)
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    a_rest.len().cmp(&b_rest.len()).then_with(|| a.cmp(b))
+}
+
+/// Split the leading run of ASCII digits from `s`.
+fn split_digits(s: &[u8]) -> (&[u8], &[u8]) {
+    let len = s.iter().take_while(|b| b.is_ascii_digit()).count();
+    s.split_at(len)
+}
+
+/// Compare runs of ASCII digits by their value, without overflowing.
+fn compare_numbers(a: &[u8], b: &[u8]) -> Ordering {
+    let trim = |digits: &[u8]| -> usize { digits.iter().take_while(|&&d| d == b'0').count() };
+    let (a_zeros, b_zeros) = (trim(a), trim(b));
+    let (a_value, b_value) = (&a[a_zeros..], &b[b_zeros..]);
+    a_value
+        .len()
+        .cmp(&b_value.len())
+        .then_with(|| a_value.cmp(b_value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn natural_order() {
+        assert_eq!(natural_cmp("a2", "a10"), Ordering::Less);
+        assert_eq!(natural_cmp("a10b", "a10a"), Ordering::Greater);
+        assert_eq!(natural_cmp("0001", "1"), Ordering::Less);
+        assert_eq!(natural_cmp("1", "0001"), Ordering::Greater);
+        assert_eq!(natural_cmp("x", "x1"), Ordering::Less);
+        assert_eq!(
+            natural_cmp("99999999999999999999999", "100000000000000000000000"),
+            Ordering::Less
+        );
+        assert_eq!(natural_cmp("same", "same"), Ordering::Equal);
+    }
+
+    #[test]
+    fn grouping() {
+        let groups = group_by_prefix([
+            Curie::new(Some("p10"), "x"),
+            Curie::new(None, "z"),
+            Curie::new(Some("p2"), "x"),
+            Curie::new(Some("a"), "y10"),
+            Curie::new(Some("a"), "y9"),
+        ]);
+        assert_eq!(
+            groups
+                .iter()
+                .map(|(prefix, curies)| (*prefix, curies.iter().map(|c| c.reference).collect()))
+                .collect::<Vec<(_, Vec<_>)>>(),
+            [
+                (None, vec!["z"]),
+                (Some("a"), vec!["y9", "y10"]),
+                (Some("p2"), vec!["x"]),
+                (Some("p10"), vec!["x"]),
+            ]
+        );
+    }
+}
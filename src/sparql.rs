@@ -0,0 +1,241 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::turtle::{parse_directive, skip_space, Directive, ParsedDirective, Stop};
+use crate::{ParseError, ParseErrorKind, PrefixMapping};
+use std::iter::FusedIterator;
+use std::ops::Range;
+
+/// Read the `PREFIX` and `BASE` declarations in the prologue of a
+/// SPARQL query or update, without applying them to a mapping.
+///
+/// The prologue is read as by [`PrefixMapping::extend_from_sparql()`].
+/// IRIs are yielded as written, apart from decoding `\u` and `\U`
+/// escapes, so relative IRIs are left for the caller to resolve.
+/// Reading stops after the first error.
+///
+/// # Examples
+///
+/// ```
+/// use curie::sparql_directives;
+/// use curie::turtle::Directive;
+///
+/// let query = "BASE <http://example.com/>\n\
+///              PREFIX foaf: <http://xmlns.com/foaf/0.1/>\n\
+///              SELECT ?name WHERE { ?person foaf:name ?name }";
+/// let directives = sparql_directives(query)
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+/// assert_eq!(directives, [
+///     Directive::Base(String::from("http://example.com/")),
+///     Directive::Prefix {
+///         prefix: String::from("foaf"),
+///         iri: String::from("http://xmlns.com/foaf/0.1/"),
+///     },
+/// ]);
+/// ```
+pub fn sparql_directives(query: &str) -> SparqlDirectives<'_> {
+    SparqlDirectives {
+        query,
+        pos: 0,
+        done: false,
+    }
+}
+
+/// An iterator over the declarations in the prologue of a SPARQL query.
+///
+/// This is created by [`sparql_directives()`].
+#[derive(Clone, Debug)]
+pub struct SparqlDirectives<'a> {
+    query: &'a str,
+    /// Where the next declaration may start.
+    pos: usize,
+    /// Whether the end of the prologue or an error has been reached.
+    done: bool,
+}
+
+impl SparqlDirectives<'_> {
+    /// Read the next declaration, returning it with its span in the
+    /// query.
+    fn next_directive(&mut self) -> Option<Result<(Directive, Range<usize>), ParseError>> {
+        if self.done {
+            return None;
+        }
+        let query = self.query;
+        let pos = self.pos + skip_space(&query[self.pos..], true).unwrap_or(0);
+        // SPARQL has no `@prefix` form, and `@` can't start a query.
+        if pos == query.len() || query[pos..].starts_with('@') {
+            self.done = true;
+            return None;
+        }
+        match parse_directive(&query[pos..], true) {
+            Ok(Some(ParsedDirective { directive, len, .. })) => {
+                self.pos = pos + len;
+                Some(Ok((directive, pos..pos + len)))
+            }
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(Stop::Incomplete) => {
+                self.done = true;
+                let end = query.len();
+                Some(Err(ParseError::at(
+                    ParseErrorKind::UnexpectedEnd,
+                    query,
+                    end,
+                    end,
+                )))
+            }
+            Err(Stop::Error(kind, start, end)) => {
+                self.done = true;
+                Some(Err(ParseError::at(kind, query, pos + start, pos + end)))
+            }
+        }
+    }
+}
+
+impl Iterator for SparqlDirectives<'_> {
+    type Item = Result<Directive, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_directive()
+            .map(|result| result.map(|(directive, _)| directive))
+    }
+}
+
+impl FusedIterator for SparqlDirectives<'_> {}
+
+impl PrefixMapping {
+    /// Create a mapping from the prologue of a SPARQL query or update.
+    ///
+    /// See [`PrefixMapping::extend_from_sparql()`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] if a declaration is malformed.
+    pub fn from_sparql(query: &str) -> Result<PrefixMapping, ParseError> {
+        let mut mapping = PrefixMapping::default();
+        mapping.extend_from_sparql(query)?;
+        Ok(mapping)
+    }
+
+    /// Add the `PREFIX` declarations in the prologue of a SPARQL query
+    /// or update to this mapping, and set its base IRI from the last
+    /// `BASE` declaration. Relative IRIs in declarations are resolved
+    /// against the base IRI in effect.
+    ///
+    /// The prologue is the declarations at the start of the query,
+    /// along with any whitespace and comments between them. Reading
+    /// stops at the first thing that isn't a declaration, so the rest
+    /// of the query isn't checked. Keywords are case-insensitive.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] if a declaration is malformed.
+    /// Declarations read before the error was found remain in the
+    /// mapping.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::PrefixMapping;
+    ///
+    /// let mapping = PrefixMapping::from_sparql("
+    ///     BASE <http://example.com/>
+    ///     PREFIX foaf: <http://xmlns.com/foaf/0.1/>
+    ///     ## A comment.
+    ///     prefix dc: <http://purl.org/dc/terms/>
+    ///     SELECT ?name WHERE { ?person foaf:name ?name }
+    /// ").unwrap();
+    ///
+    /// assert_eq!(mapping.base(), Some("http://example.com/"));
+    /// assert_eq!(mapping.expand_curie_string("foaf:name"),
+    ///            Ok(String::from("http://xmlns.com/foaf/0.1/name")));
+    /// assert_eq!(mapping.mappings().count(), 2);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`sparql_directives()`](crate::sparql_directives)
+    pub fn extend_from_sparql(&mut self, query: &str) -> Result<(), ParseError> {
+        let mut directives = sparql_directives(query);
+        while let Some(result) = directives.next_directive() {
+            let (directive, span) = result?;
+            self.apply_directive(directive)
+                .map_err(|kind| ParseError::at(kind, query, span.start, span.end))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prologues() {
+        let mapping = PrefixMapping::from_sparql(
+            "PREFIX : <http://example.com/>\nASK { :a :b :c }\nPREFIX no: <http://no/>",
+        )
+        .unwrap();
+        assert_eq!(
+            mapping.expand_curie_string(":a"),
+            Ok(String::from("http://example.com/a"))
+        );
+        assert_eq!(mapping.mappings().count(), 1);
+        assert_eq!(mapping.base(), None);
+
+        // Relative IRIs are resolved against the base in effect.
+        let mapping =
+            PrefixMapping::from_sparql("BASE <http://a/b/>\nBASE <c/>\nPREFIX x: <../d#>").unwrap();
+        assert_eq!(mapping.base(), Some("http://a/b/c/"));
+        assert_eq!(
+            mapping.expand_curie_string("x:y"),
+            Ok(String::from("http://a/b/d#y"))
+        );
+
+        // Only the prologue is read, so the query isn't checked.
+        assert!(PrefixMapping::from_sparql("SELECT * WHERE { ?s ?p \"").is_ok());
+        assert!(PrefixMapping::from_sparql("@prefix ex: <x> .")
+            .unwrap()
+            .mappings()
+            .next()
+            .is_none());
+
+        let mut mapping = PrefixMapping::default();
+        let err = mapping
+            .extend_from_sparql("PREFIX ex: <http://example.com/>\nPREFIX ex2 <x>")
+            .unwrap_err();
+        assert_eq!(err.kind(), ParseErrorKind::InvalidDirective);
+        assert_eq!((err.line(), err.column()), (2, 8));
+        assert_eq!(mapping.mappings().count(), 1);
+
+        let err = PrefixMapping::from_sparql("PREFIX ex: <http://exa").unwrap_err();
+        assert_eq!(err.kind(), ParseErrorKind::UnexpectedEnd);
+    }
+
+    #[test]
+    fn directives() {
+        let mut directives = sparql_directives("BASE <a/>\nPREFIX ex: <b>\nPREFIX ex2 <c>");
+        assert_eq!(
+            directives.next(),
+            Some(Ok(Directive::Base(String::from("a/"))))
+        );
+        assert_eq!(
+            directives.next(),
+            Some(Ok(Directive::Prefix {
+                prefix: String::from("ex"),
+                iri: String::from("b"),
+            }))
+        );
+        let err = directives.next().unwrap().unwrap_err();
+        assert_eq!(err.kind(), ParseErrorKind::InvalidDirective);
+        assert_eq!(directives.next(), None);
+
+        assert_eq!(sparql_directives("ASK {}").count(), 0);
+    }
+}
@@ -0,0 +1,261 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{syntax, Curie, ExpansionError, InvalidPrefixError, ShrinkError};
+use std::fmt;
+
+/// Errors that might occur when adding a prefix to a
+/// [`StaticPrefixMapping`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StaticPrefixError {
+    /// The prefix can't be bound.
+    InvalidPrefix(InvalidPrefixError),
+    /// The mapping already holds as many prefixes as it can.
+    Full,
+}
+
+impl fmt::Display for StaticPrefixError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StaticPrefixError::InvalidPrefix(err) => err.fmt(f),
+            StaticPrefixError::Full => f.write_str("the mapping is full"),
+        }
+    }
+}
+
+impl std::error::Error for StaticPrefixError {}
+
+/// A prefix mapping with room for a fixed number of prefixes, which
+/// never allocates.
+///
+/// This is for constrained devices without an allocator. It borrows
+/// its prefixes and namespaces, which are usually string literals, and
+/// holds up to `N` of them. Expanded IRIs are written into a buffer
+/// supplied by the caller, such as a [`heapless::String`].
+///
+/// It supports the core of [`PrefixMapping`](crate::PrefixMapping):
+/// a default namespace, prefixes, expansion and shrinking.
+///
+/// This requires the `heapless` feature.
+///
+/// # Examples
+///
+/// ```
+/// use curie::{Curie, StaticPrefixMapping};
+///
+/// let mut mapping = StaticPrefixMapping::<4>::new();
+/// mapping.add_prefix("sosa", "http://www.w3.org/ns/sosa/").unwrap();
+///
+/// let mut iri = heapless::String::<64>::new();
+/// mapping.expand_curie_into(&Curie::new(Some("sosa"), "Observation"), &mut iri).unwrap();
+/// assert_eq!(iri, "http://www.w3.org/ns/sosa/Observation");
+///
+/// assert_eq!(mapping.shrink_iri("http://www.w3.org/ns/sosa/hasResult"),
+///            Ok(Curie::new(Some("sosa"), "hasResult")));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct StaticPrefixMapping<'a, const N: usize> {
+    default: Option<&'a str>,
+    mapping: heapless::Vec<(&'a str, &'a str), N>,
+}
+
+impl<'a, const N: usize> StaticPrefixMapping<'a, N> {
+    /// Construct an empty mapping.
+    #[must_use]
+    pub const fn new() -> Self {
+        StaticPrefixMapping {
+            default: None,
+            mapping: heapless::Vec::new(),
+        }
+    }
+
+    /// Set the default prefix.
+    pub fn set_default(&mut self, default: &'a str) {
+        self.default = Some(default);
+    }
+
+    /// Add a prefix to the mapping, replacing any namespace that it is
+    /// already bound to.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StaticPrefixError::InvalidPrefix`] if `prefix` is `_`,
+    /// and [`StaticPrefixError::Full`] if it is a new prefix and the
+    /// mapping already holds `N` prefixes.
+    pub fn add_prefix(
+        &mut self,
+        prefix: &'a str,
+        namespace: &'a str,
+    ) -> Result<(), StaticPrefixError> {
+        if prefix == "_" {
+            return Err(StaticPrefixError::InvalidPrefix(
+                InvalidPrefixError::ReservedPrefix,
+            ));
+        }
+        if let Some(entry) = self.mapping.iter_mut().find(|(p, _)| *p == prefix) {
+            entry.1 = namespace;
+            return Ok(());
+        }
+        self.mapping
+            .push((prefix, namespace))
+            .map_err(|_| StaticPrefixError::Full)
+    }
+
+    /// Remove a prefix from the mapping, returning whether it was
+    /// bound.
+    pub fn remove_prefix(&mut self, prefix: &str) -> bool {
+        match self.mapping.iter().position(|(p, _)| *p == prefix) {
+            Some(idx) => {
+                self.mapping.remove(idx);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Expand a [`Curie`], writing the IRI into `out`.
+    ///
+    /// Nothing is written if the CURIE can't be expanded, but `out` may
+    /// hold part of the IRI if it fills up.
+    ///
+    /// # Errors
+    ///
+    /// This will return [`ExpansionError`] if the expansion fails, and
+    /// [`ExpansionError::WriteFailed`] if `out` can't hold the IRI.
+    pub fn expand_curie_into<W: fmt::Write>(
+        &self,
+        curie: &Curie,
+        out: &mut W,
+    ) -> Result<(), ExpansionError> {
+        let namespace = match curie.prefix {
+            Some(prefix) => self
+                .mapping
+                .iter()
+                .find(|(p, _)| *p == prefix)
+                .map(|&(_, namespace)| namespace)
+                .ok_or(ExpansionError::Invalid)?,
+            None => self.default.ok_or(ExpansionError::MissingDefault)?,
+        };
+        syntax::check_append(namespace, curie.reference)?;
+        out.write_str(namespace)
+            .and_then(|()| out.write_str(curie.reference))
+            .map_err(|_| ExpansionError::WriteFailed)
+    }
+
+    /// Expand a CURIE given as a string, writing the IRI into `out`.
+    ///
+    /// # Errors
+    ///
+    /// As for [`StaticPrefixMapping::expand_curie_into()`].
+    pub fn expand_curie_string_into<W: fmt::Write>(
+        &self,
+        curie_str: &str,
+        out: &mut W,
+    ) -> Result<(), ExpansionError> {
+        self.expand_curie_into(&Curie::split(curie_str), out)
+    }
+
+    /// Shrink an IRI, returning a [`Curie`].
+    ///
+    /// As with [`PrefixMapping::shrink_iri()`](crate::PrefixMapping::shrink_iri),
+    /// the longest matching namespace is used. On ties, the default
+    /// namespace is used, and then the prefix that was added first.
+    ///
+    /// # Errors
+    ///
+    /// As for [`PrefixMapping::shrink_iri()`](crate::PrefixMapping::shrink_iri).
+    pub fn shrink_iri<'i>(&'i self, iri: &'i str) -> Result<Curie<'i>, ShrinkError> {
+        if iri.is_empty() {
+            return Err(ShrinkError::EmptyIri);
+        }
+        let candidates = self
+            .default
+            .map(|default| (None, default))
+            .into_iter()
+            .chain(self.mapping.iter().map(|&(prefix, ns)| (Some(prefix), ns)));
+        let mut best: Option<Curie> = None;
+        for (prefix, namespace) in candidates {
+            if let Some(reference) = syntax::strip_namespace(iri, namespace) {
+                if best
+                    .as_ref()
+                    .is_none_or(|best| reference.len() < best.reference.len())
+                {
+                    best = Some(Curie::new(prefix, reference));
+                }
+            }
+        }
+        best.ok_or(ShrinkError::NoMatch)
+    }
+
+    /// Return an iterator over the prefixes and their namespaces, in
+    /// the order they were added.
+    pub fn mappings(&self) -> impl Iterator<Item = (&'a str, &'a str)> + '_ {
+        self.mapping.iter().copied()
+    }
+
+    /// The number of prefixes in the mapping.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.mapping.len()
+    }
+
+    /// Whether the mapping has no prefixes.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.mapping.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_capacity() {
+        let mut mapping = StaticPrefixMapping::<2>::new();
+        mapping.add_prefix("a", "http://a.example/").unwrap();
+        mapping.add_prefix("b", "http://b.example/").unwrap();
+        assert_eq!(
+            mapping.add_prefix("c", "http://c.example/"),
+            Err(StaticPrefixError::Full)
+        );
+        assert_eq!(
+            mapping.add_prefix("_", "http://c.example/"),
+            Err(StaticPrefixError::InvalidPrefix(
+                InvalidPrefixError::ReservedPrefix
+            ))
+        );
+        // Rebinding doesn't need room.
+        mapping.add_prefix("b", "http://b.example/ns#").unwrap();
+        assert_eq!(mapping.len(), 2);
+
+        let mut out = heapless::String::<20>::new();
+        assert_eq!(
+            mapping.expand_curie_string_into("b:x#y", &mut out),
+            Err(ExpansionError::DuplicateFragment)
+        );
+        assert_eq!(
+            mapping.expand_curie_string_into("x", &mut out),
+            Err(ExpansionError::MissingDefault)
+        );
+        assert_eq!(
+            mapping.expand_curie_string_into("b:a-long-reference", &mut out),
+            Err(ExpansionError::WriteFailed)
+        );
+        out.clear();
+        mapping.expand_curie_string_into("a:x", &mut out).unwrap();
+        assert_eq!(out, "http://a.example/x");
+
+        assert!(mapping.remove_prefix("a"));
+        assert!(!mapping.remove_prefix("a"));
+        mapping.set_default("http://a.example/");
+        assert_eq!(
+            mapping.shrink_iri("http://a.example/x"),
+            Ok(Curie::new(None, "x"))
+        );
+        assert!(mapping.shrink_iri("http://c.example/x").is_err());
+    }
+}
@@ -0,0 +1,199 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::PrefixMapping;
+use std::fmt;
+
+/// A diagnostic for a prefix that has no mapping, along with
+/// the registered prefixes that it may have been a typo of.
+///
+/// This is created by [`PrefixMapping::unknown_prefix()`].
+///
+/// # Examples
+///
+/// ```
+/// use curie::PrefixMapping;
+///
+/// let mut mapping = PrefixMapping::default();
+/// mapping.add_prefix("foaf", "http://xmlns.com/foaf/0.1/").unwrap();
+///
+/// let diagnostic = mapping.unknown_prefix("fof");
+/// assert_eq!(diagnostic.suggestions(), ["foaf"]);
+/// assert_eq!(diagnostic.to_string(), "`fof` not found; did you mean `foaf`?");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnknownPrefix<'a> {
+    prefix: &'a str,
+    suggestions: Vec<&'a str>,
+}
+
+impl<'a> UnknownPrefix<'a> {
+    pub(crate) fn new(prefix: &'a str, suggestions: Vec<&'a str>) -> Self {
+        UnknownPrefix {
+            prefix,
+            suggestions,
+        }
+    }
+
+    /// The prefix that was not found.
+    #[must_use]
+    pub fn prefix(&self) -> &'a str {
+        self.prefix
+    }
+
+    /// The registered prefixes that are similar to the unknown prefix,
+    /// most similar first.
+    #[must_use]
+    pub fn suggestions(&self) -> &[&'a str] {
+        &self.suggestions
+    }
+}
+
+impl fmt::Display for UnknownPrefix<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "`{}` not found", self.prefix)?;
+        if let Some((last, rest)) = self.suggestions.split_last() {
+            f.write_str("; did you mean ")?;
+            for (idx, suggestion) in rest.iter().enumerate() {
+                if idx > 0 {
+                    f.write_str(", ")?;
+                }
+                write!(f, "`{suggestion}`")?;
+            }
+            match rest.len() {
+                0 => {}
+                1 => f.write_str(" or ")?,
+                _ => f.write_str(", or ")?,
+            }
+            write!(f, "`{last}`?")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for UnknownPrefix<'_> {}
+
+impl PrefixMapping {
+    /// Find registered prefixes that are similar to `prefix`.
+    ///
+    /// Similarity is measured by edit distance, counting adjacent
+    /// transpositions as a single edit. Only prefixes within a distance
+    /// proportional to the length of `prefix` are returned, closest
+    /// first, and in insertion order among equally close prefixes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::PrefixMapping;
+    ///
+    /// let mut mapping = PrefixMapping::default();
+    /// mapping.add_prefix("rdf", "http://www.w3.org/1999/02/22-rdf-syntax-ns#").unwrap();
+    /// mapping.add_prefix("rdfs", "http://www.w3.org/2000/01/rdf-schema#").unwrap();
+    /// mapping.add_prefix("foaf", "http://xmlns.com/foaf/0.1/").unwrap();
+    ///
+    /// assert_eq!(mapping.suggest_prefixes("rdsf"), ["rdf", "rdfs"]);
+    /// assert!(mapping.suggest_prefixes("skos").is_empty());
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`PrefixMapping::unknown_prefix()`]
+    #[must_use]
+    pub fn suggest_prefixes(&self, prefix: &str) -> Vec<&str> {
+        let max_distance = (prefix.chars().count() / 3).max(1);
+        let mut candidates: Vec<(usize, &str)> = self
+            .mapping
+            .keys()
+            .filter(|candidate| candidate.as_str() != prefix)
+            .map(|candidate| (edit_distance(prefix, candidate), candidate.as_str()))
+            .filter(|&(distance, _)| distance <= max_distance)
+            .collect();
+        candidates.sort_by_key(|&(distance, _)| distance);
+        candidates
+            .into_iter()
+            .map(|(_, candidate)| candidate)
+            .collect()
+    }
+
+    /// Build a diagnostic for a prefix that has no mapping.
+    ///
+    /// This is intended for use after an expansion has failed with
+    /// [`ExpansionError::UnknownPrefix`](crate::ExpansionError::UnknownPrefix), to
+    /// report the failure to a user along with likely corrections.
+    ///
+    /// # See also
+    ///
+    /// * [`PrefixMapping::suggest_prefixes()`]
+    #[must_use]
+    pub fn unknown_prefix<'a>(&'a self, prefix: &'a str) -> UnknownPrefix<'a> {
+        UnknownPrefix::new(prefix, self.suggest_prefixes(prefix))
+    }
+}
+
+/// The optimal string alignment distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    // Three rows of the distance matrix: two rows back, the previous
+    // row and the current row.
+    let mut before: Vec<usize> = vec![0; b.len() + 1];
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current: Vec<usize> = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        current[0] = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            current[j] = (previous[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(previous[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(before[j - 2] + 1);
+            }
+        }
+        std::mem::swap(&mut before, &mut previous);
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distances() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("foaf", "foaf"), 0);
+        assert_eq!(edit_distance("fof", "foaf"), 1);
+        assert_eq!(edit_distance("rdsf", "rdfs"), 1);
+        assert_eq!(edit_distance("dc", "dcterms"), 5);
+        assert_eq!(edit_distance("", "owl"), 3);
+        assert_eq!(edit_distance("schéma", "schema"), 1);
+    }
+
+    #[test]
+    fn display_suggestions() {
+        let mut mapping = PrefixMapping::default();
+        assert_eq!(mapping.unknown_prefix("ex").to_string(), "`ex` not found");
+
+        mapping.add_prefix("ex1", "http://example.com/1/").unwrap();
+        mapping.add_prefix("ex2", "http://example.com/2/").unwrap();
+        mapping.add_prefix("ex3", "http://example.com/3/").unwrap();
+        assert_eq!(
+            mapping.unknown_prefix("ex").to_string(),
+            "`ex` not found; did you mean `ex1`, `ex2`, or `ex3`?"
+        );
+
+        mapping.remove_prefix("ex3").unwrap();
+        assert_eq!(
+            mapping.unknown_prefix("ex").to_string(),
+            "`ex` not found; did you mean `ex1` or `ex2`?"
+        );
+    }
+}
@@ -0,0 +1,206 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Character classes and small grammar checks shared by the rest
+//! of the crate.
+
+use crate::ExpansionError;
+
+/// Is `c` allowed as the first character of an `NCName`?
+///
+/// This is the XML `NameStartChar` production without the `':'`.
+pub(crate) fn is_ncname_start_char(c: char) -> bool {
+    matches!(c,
+        'A'..='Z'
+        | '_'
+        | 'a'..='z'
+        | '\u{C0}'..='\u{D6}'
+        | '\u{D8}'..='\u{F6}'
+        | '\u{F8}'..='\u{2FF}'
+        | '\u{370}'..='\u{37D}'
+        | '\u{37F}'..='\u{1FFF}'
+        | '\u{200C}'..='\u{200D}'
+        | '\u{2070}'..='\u{218F}'
+        | '\u{2C00}'..='\u{2FEF}'
+        | '\u{3001}'..='\u{D7FF}'
+        | '\u{F900}'..='\u{FDCF}'
+        | '\u{FDF0}'..='\u{FFFD}'
+        | '\u{10000}'..='\u{EFFFF}')
+}
+
+/// Is `c` allowed after the first character of an `NCName`?
+pub(crate) fn is_ncname_char(c: char) -> bool {
+    is_ncname_start_char(c)
+        || matches!(c,
+            '-' | '.' | '0'..='9' | '\u{B7}' | '\u{300}'..='\u{36F}' | '\u{203F}'..='\u{2040}')
+}
+
+/// Is `s` an `NCName`?
+pub(crate) fn is_ncname(s: &str) -> bool {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) if is_ncname_start_char(c) => chars.all(is_ncname_char),
+        _ => false,
+    }
+}
+
+/// Is `s` a Turtle and SPARQL `PN_PREFIX`, or empty?
+///
+/// This is an `NCName` that doesn't start with `'_'` or end with `'.'`.
+pub(crate) fn is_pn_prefix(s: &str) -> bool {
+    s.is_empty() || (is_ncname(s) && !s.starts_with('_') && !s.ends_with('.'))
+}
+
+/// Is `s` an RDFa term?
+///
+/// A term is an `NCName` that may also contain `'/'` after its
+/// first character.
+pub(crate) fn is_term(s: &str) -> bool {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) if is_ncname_start_char(c) => chars.all(|c| is_ncname_char(c) || c == '/'),
+        _ => false,
+    }
+}
+
+/// Does `s` start with an RFC 3986 scheme followed by a `':'`?
+///
+/// This is the check used to tell an absolute IRI apart from
+/// a relative reference.
+pub(crate) fn is_absolute_iri(s: &str) -> bool {
+    match s.find(':') {
+        Some(idx) => {
+            let scheme = &s[..idx];
+            let mut chars = scheme.chars();
+            matches!(chars.next(), Some(c) if c.is_ascii_alphabetic())
+                && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        }
+        None => false,
+    }
+}
+
+/// Is `s` an absolute IRI without any characters that can never
+/// appear in an IRI?
+pub(crate) fn is_valid_absolute_iri(s: &str) -> bool {
+    is_absolute_iri(s) && find_invalid_iri_char(s).is_none()
+}
+
+/// Find the first character in `s` that can never appear in an IRI,
+/// returning its byte offset along with the character.
+///
+/// These are the control characters, the space, the backquote and
+/// the characters `<>"{}|\^`.
+pub(crate) fn find_invalid_iri_char(s: &str) -> Option<(usize, char)> {
+    s.char_indices().find(|&(_, c)| {
+        c.is_control()
+            || matches!(
+                c,
+                ' ' | '<' | '>' | '"' | '{' | '}' | '|' | '\\' | '^' | '`'
+            )
+    })
+}
+
+/// Decode a `\u` or `\U` escape at the start of `s`, which follows a
+/// backslash, returning the character and the length of the escape
+/// without the backslash.
+pub(crate) fn decode_uchar(s: &str) -> Option<(char, usize)> {
+    let digits = match s.as_bytes().first() {
+        Some(b'u') => 4,
+        Some(b'U') => 8,
+        _ => return None,
+    };
+    let hex = s.get(1..1 + digits)?;
+    if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let code = u32::from_str_radix(hex, 16).ok()?;
+    Some((char::from_u32(code)?, 1 + digits))
+}
+
+/// Check that `reference` can be appended to `namespace` to form an
+/// IRI with at most one fragment.
+pub(crate) fn check_append(namespace: &str, reference: &str) -> Result<(), ExpansionError> {
+    if namespace.contains('#') && reference.contains('#') {
+        Err(ExpansionError::DuplicateFragment)
+    } else {
+        Ok(())
+    }
+}
+
+/// Strip `namespace` from the start of `iri`, returning the reference.
+///
+/// This fails if the namespace ends within the authority of `iri`,
+/// such as `http://example.com` in `http://example.com.au/`.
+pub(crate) fn strip_namespace<'a>(iri: &'a str, namespace: &str) -> Option<&'a str> {
+    let reference = iri.strip_prefix(namespace)?;
+    let ends_in_authority = namespace
+        .split_once("://")
+        .is_some_and(|(_, rest)| !rest.contains(['/', '?', '#']));
+    if ends_in_authority && !reference.is_empty() && !reference.starts_with(['/', '?', '#']) {
+        None
+    } else {
+        Some(reference)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ncnames() {
+        assert!(is_ncname("foaf"));
+        assert!(is_ncname("_x"));
+        assert!(is_ncname("dc.terms-1"));
+        assert!(is_ncname("дц"));
+        assert!(!is_ncname(""));
+        assert!(!is_ncname("a b"));
+        assert!(!is_ncname("a:b"));
+        assert!(!is_ncname("-a"));
+        assert!(!is_ncname("a/b"));
+    }
+
+    #[test]
+    fn pn_prefixes() {
+        assert!(is_pn_prefix(""));
+        assert!(is_pn_prefix("dc.terms"));
+        assert!(!is_pn_prefix("_"));
+        assert!(!is_pn_prefix("ex."));
+        assert!(!is_pn_prefix("1a"));
+    }
+
+    #[test]
+    fn invalid_iri_chars() {
+        assert_eq!(find_invalid_iri_char("Person/extra?x=1#y"), None);
+        assert_eq!(find_invalid_iri_char("é"), None);
+        assert_eq!(find_invalid_iri_char("a b"), Some((1, ' ')));
+        assert_eq!(find_invalid_iri_char("é<"), Some((2, '<')));
+        assert_eq!(find_invalid_iri_char("a\tb"), Some((1, '\t')));
+    }
+
+    #[test]
+    fn terms() {
+        assert!(is_term("license"));
+        assert!(is_term("Person"));
+        assert!(is_term("dc/title"));
+        assert!(is_term("été"));
+        assert!(!is_term(""));
+        assert!(!is_term("foaf:name"));
+        assert!(!is_term("/path"));
+        assert!(!is_term("1st"));
+    }
+
+    #[test]
+    fn absolute_iris() {
+        assert!(is_absolute_iri("http://example.com/"));
+        assert!(is_absolute_iri("urn:isbn:0451450523"));
+        assert!(is_absolute_iri("foaf:name"));
+        assert!(!is_absolute_iri("#fragment"));
+        assert!(!is_absolute_iri("1http://example.com/"));
+        assert!(!is_absolute_iri(":name"));
+        assert!(!is_absolute_iri("name"));
+    }
+}
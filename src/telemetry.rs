@@ -0,0 +1,86 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Reporting through the `metrics` facade, when the `metrics` feature
+//! is enabled. Otherwise, these functions do nothing.
+//!
+//! The metrics are listed in the crate documentation.
+
+use crate::{ExpansionError, PrefixMapping};
+
+/// Record an expansion, given the length of the IRI or the error.
+#[inline]
+pub(crate) fn record_expansion(result: Result<usize, &ExpansionError>) {
+    #[cfg(feature = "metrics")]
+    {
+        let label = match result {
+            Ok(len) => {
+                metrics::histogram!("curie_expanded_iri_length").record(len as f64);
+                "ok"
+            }
+            Err(ExpansionError::Invalid | ExpansionError::UnknownPrefix(_)) => "unmapped_prefix",
+            Err(ExpansionError::MissingDefault | ExpansionError::MissingBase) => "missing_default",
+            Err(
+                ExpansionError::DuplicateFragment
+                | ExpansionError::InvalidCharacter { .. }
+                | ExpansionError::PatternMismatch { .. },
+            ) => "invalid_reference",
+            Err(ExpansionError::WriteFailed) => "write_failed",
+        };
+        metrics::counter!("curie_expansions_total", "result" => label).increment(1);
+    }
+    #[cfg(not(feature = "metrics"))]
+    let _ = result;
+}
+
+/// Record an attempt to shrink `iri`, noting whether more than one
+/// namespace of `mapping` could have been used.
+#[inline]
+pub(crate) fn record_shrink(mapping: &PrefixMapping, iri: &str, shrunk: bool) {
+    #[cfg(feature = "metrics")]
+    {
+        let label = if shrunk { "ok" } else { "no_match" };
+        metrics::counter!("curie_shrinks_total", "result" => label).increment(1);
+        if shrunk && mapping.shrink_candidates(iri) > 1 {
+            metrics::counter!("curie_shrink_ambiguities_total").increment(1);
+        }
+    }
+    #[cfg(not(feature = "metrics"))]
+    let _ = (mapping, iri, shrunk);
+}
+
+/// Record a lookup in a `SharedInterner`.
+#[cfg(feature = "dashmap")]
+#[inline]
+pub(crate) fn record_interner_lookup(hit: bool) {
+    #[cfg(feature = "metrics")]
+    {
+        let label = if hit { "hit" } else { "miss" };
+        metrics::counter!("curie_interner_lookups_total", "result" => label).increment(1);
+    }
+    #[cfg(not(feature = "metrics"))]
+    let _ = hit;
+}
+
+#[cfg(feature = "metrics")]
+impl PrefixMapping {
+    /// The number of namespaces and templates that could shrink `iri`.
+    fn shrink_candidates(&self, iri: &str) -> usize {
+        let default = self
+            .default
+            .as_deref()
+            .and_then(|default| crate::syntax::strip_namespace(iri, default));
+        let namespaces = self
+            .mapping
+            .values()
+            .filter(|namespace| crate::syntax::strip_namespace(iri, namespace).is_some());
+        let templates = self
+            .templates
+            .values()
+            .filter(|template| template.reference(iri).is_some());
+        usize::from(default.is_some()) + namespaces.count() + templates.count()
+    }
+}
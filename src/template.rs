@@ -0,0 +1,301 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{Curie, ExpansionError, InvalidPrefixError, PrefixMapping};
+use std::fmt;
+
+/// The placeholders in a URI template that the reference replaces:
+/// `{id}`, and `$1` as used by identifiers.org and the Bioregistry.
+const PLACEHOLDERS: [&str; 2] = ["{id}", "$1"];
+
+/// A URI template split around its placeholder.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct UriTemplate {
+    before: String,
+    placeholder: &'static str,
+    after: String,
+}
+
+impl UriTemplate {
+    fn parse(template: &str) -> Option<Self> {
+        let mut found = PLACEHOLDERS
+            .iter()
+            .filter(|&&placeholder| template.contains(placeholder));
+        let placeholder = *found.next()?;
+        if found.next().is_some() {
+            return None;
+        }
+        let (before, after) = template.split_once(placeholder)?;
+        if after.contains(placeholder) {
+            return None;
+        }
+        Some(UriTemplate {
+            before: String::from(before),
+            placeholder,
+            after: String::from(after),
+        })
+    }
+
+    /// The parts of the template before and after the placeholder,
+    /// for `reference` to be put between.
+    pub(crate) fn split(&self, reference: &str) -> Result<(&str, &str), ExpansionError> {
+        if reference.contains('#') && (self.before.contains('#') || self.after.contains('#')) {
+            return Err(ExpansionError::DuplicateFragment);
+        }
+        Ok((&self.before, &self.after))
+    }
+
+    /// Find the reference that expands to `iri`, if any.
+    pub(crate) fn reference<'a>(&self, iri: &'a str) -> Option<&'a str> {
+        let reference = iri
+            .strip_prefix(self.before.as_str())?
+            .strip_suffix(self.after.as_str())?;
+        (!reference.is_empty()).then_some(reference)
+    }
+}
+
+/// Errors that might occur when adding a URI template to a
+/// [`PrefixMapping`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TemplateError {
+    /// The template does not contain exactly one placeholder, `{id}`
+    /// or `$1`.
+    InvalidTemplate,
+    /// The prefix can't be bound.
+    InvalidPrefix(InvalidPrefixError),
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TemplateError::InvalidTemplate => {
+                f.write_str("the template must contain `{id}` or `$1` exactly once")
+            }
+            TemplateError::InvalidPrefix(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+impl PrefixMapping {
+    /// Bind a prefix to a URI template.
+    ///
+    /// Some identifier systems put the identifier in the middle of a
+    /// URI rather than at the end. The reference of a CURIE using the
+    /// prefix replaces the placeholder in the template when it is
+    /// expanded, and [`PrefixMapping::shrink_iri()`] matches IRIs
+    /// against the parts of the template on either side of it. The
+    /// reference is substituted as it is, without being encoded.
+    ///
+    /// The placeholder is either `{id}` or `$1`, the form used by the
+    /// URI formats of identifiers.org and the Bioregistry.
+    ///
+    /// A prefix is bound either to a namespace or to a template, so
+    /// this replaces any namespace that the prefix was bound to, and
+    /// [`PrefixMapping::add_prefix()`] replaces a template. Functions
+    /// that work with the namespace of a prefix, rather than expanding
+    /// a CURIE, don't see prefixes bound to templates.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TemplateError::InvalidTemplate`] if `template` doesn't
+    /// contain exactly one placeholder, and [`TemplateError::InvalidPrefix`]
+    /// if `prefix` can't be bound.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::{Curie, PrefixMapping};
+    ///
+    /// let mut mapping = PrefixMapping::default();
+    /// mapping
+    ///     .add_prefix_template("rec", "https://example.com/records/{id}/view")
+    ///     .unwrap();
+    ///
+    /// assert_eq!(mapping.expand_curie_string("rec:1234"),
+    ///            Ok(String::from("https://example.com/records/1234/view")));
+    /// assert_eq!(mapping.shrink_iri("https://example.com/records/1234/view"),
+    ///            Ok(Curie::new(Some("rec"), "1234")));
+    ///
+    /// mapping
+    ///     .add_prefix_template("pubmed", "https://www.ncbi.nlm.nih.gov/pubmed/$1")
+    ///     .unwrap();
+    /// assert_eq!(mapping.expand_curie_string("pubmed:22140235"),
+    ///            Ok(String::from("https://www.ncbi.nlm.nih.gov/pubmed/22140235")));
+    /// ```
+    pub fn add_prefix_template(
+        &mut self,
+        prefix: &str,
+        template: &str,
+    ) -> Result<(), TemplateError> {
+        let template = UriTemplate::parse(template).ok_or(TemplateError::InvalidTemplate)?;
+        self.check_prefix(prefix)
+            .and_then(|()| self.check_namespace(&format!("{}{}", template.before, template.after)))
+            .map_err(TemplateError::InvalidPrefix)?;
+        if self.protected.contains(prefix) {
+            return Err(TemplateError::InvalidPrefix(
+                InvalidPrefixError::ProtectedPrefix,
+            ));
+        }
+        if self.strict_rebinding
+            && (self.mapping.contains_key(prefix)
+                || self
+                    .templates
+                    .get(prefix)
+                    .is_some_and(|bound| *bound != template))
+        {
+            return Err(TemplateError::InvalidPrefix(
+                InvalidPrefixError::AlreadyBound,
+            ));
+        }
+        self.mapping.shift_remove(prefix);
+        self.templates.insert(String::from(prefix), template);
+        if !self.prefix_ids.contains(prefix) {
+            self.prefix_ids.insert(String::from(prefix));
+        }
+        Ok(())
+    }
+
+    /// The URI template that a prefix is bound to, if any.
+    #[must_use]
+    pub fn prefix_template(&self, prefix: &str) -> Option<String> {
+        self.templates.get(prefix).map(|template| {
+            format!(
+                "{}{}{}",
+                template.before, template.placeholder, template.after
+            )
+        })
+    }
+
+    /// Shrink an IRI using the URI templates, keeping the shortest
+    /// reference and, on ties, the template that was added first.
+    pub(crate) fn shrink_with_templates<'a>(&'a self, iri: &'a str) -> Option<Curie<'a>> {
+        let mut best: Option<Curie> = None;
+        for (prefix, template) in &self.templates {
+            if let Some(reference) = template.reference(iri) {
+                if best
+                    .as_ref()
+                    .is_none_or(|best| reference.len() < best.reference.len())
+                {
+                    best = Some(Curie::new(Some(prefix), reference));
+                }
+            }
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn templates() {
+        let mut mapping = PrefixMapping::default();
+        assert_eq!(
+            mapping.add_prefix_template("a", "https://example.com/"),
+            Err(TemplateError::InvalidTemplate)
+        );
+        assert_eq!(
+            mapping.add_prefix_template("a", "https://example.com/{id}/{id}"),
+            Err(TemplateError::InvalidTemplate)
+        );
+        assert_eq!(
+            mapping.add_prefix_template("a", "https://example.com/{id}/$1"),
+            Err(TemplateError::InvalidTemplate)
+        );
+        assert_eq!(
+            mapping.add_prefix_template("_", "https://example.com/{id}"),
+            Err(TemplateError::InvalidPrefix(
+                InvalidPrefixError::ReservedPrefix
+            ))
+        );
+
+        mapping.add_prefix("a", "https://example.com/a/").unwrap();
+        mapping
+            .add_prefix_template("a", "https://example.com/?id={id}&v=2")
+            .unwrap();
+        assert_eq!(mapping.mappings().count(), 0);
+        assert_eq!(
+            mapping.prefix_template("a").as_deref(),
+            Some("https://example.com/?id={id}&v=2")
+        );
+        assert_eq!(
+            mapping.expand_curie_string("a:x/y"),
+            Ok(String::from("https://example.com/?id=x/y&v=2"))
+        );
+
+        // The reference can't be empty, and both parts must match.
+        assert!(mapping.shrink_iri("https://example.com/?id=&v=2").is_err());
+        assert!(mapping.shrink_iri("https://example.com/?id=x&v=3").is_err());
+
+        mapping
+            .add_prefix_template("c", "https://example.com/entry/$1.html")
+            .unwrap();
+        assert_eq!(
+            mapping.prefix_template("c").as_deref(),
+            Some("https://example.com/entry/$1.html")
+        );
+        assert_eq!(
+            mapping.shrink_iri("https://example.com/entry/42.html"),
+            Ok(Curie::new(Some("c"), "42"))
+        );
+
+        // Everything that expands a CURIE goes through the template.
+        for curie in [Curie::new(Some("a"), "x"), Curie::new(Some("c"), "42")] {
+            let expanded = mapping.expand_curie(&curie).unwrap();
+            let resolved = mapping.resolve(&curie).unwrap();
+            assert_eq!(resolved.to_iri(), expanded);
+            assert_eq!(resolved.to_string(), expanded);
+            let mut out = String::new();
+            mapping.write_expanded(&curie, &mut out).unwrap();
+            assert_eq!(out, expanded);
+            let mut bytes = Vec::new();
+            mapping.write_expanded_io(&curie, &mut bytes).unwrap();
+            assert_eq!(bytes, expanded.as_bytes());
+            assert_eq!(
+                mapping.expand_curie_reuse(&curie, |iri| iri.to_owned()),
+                Ok(expanded)
+            );
+        }
+
+        mapping.add_prefix("a", "https://example.com/a/").unwrap();
+        assert_eq!(mapping.prefix_template("a"), None);
+        mapping
+            .add_prefix_template("b", "https://example.com/{id}")
+            .unwrap();
+        mapping.remove_prefix("b").unwrap();
+        assert_eq!(
+            mapping.expand_curie_string("b:x"),
+            Err(ExpansionError::UnknownPrefix(String::from("b")))
+        );
+    }
+
+    #[test]
+    fn shrink_prefers_shortest_reference() {
+        let mut mapping = PrefixMapping::default();
+        mapping
+            .add_prefix_template("rec", "https://example.com/$1")
+            .unwrap();
+        mapping.add_prefix("sub", "https://example.com/a/").unwrap();
+        assert_eq!(
+            mapping.shrink_iri("https://example.com/a/b"),
+            Ok(Curie::new(Some("sub"), "b"))
+        );
+        assert_eq!(
+            mapping.shrink_iri("https://example.com/b"),
+            Ok(Curie::new(Some("rec"), "b"))
+        );
+
+        // On ties, the template is used.
+        mapping.add_prefix("ex", "https://example.com/").unwrap();
+        assert_eq!(
+            mapping.shrink_iri("https://example.com/b"),
+            Ok(Curie::new(Some("rec"), "b"))
+        );
+    }
+}
@@ -0,0 +1,127 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{Mappings, PrefixMapping};
+
+impl PrefixMapping {
+    /// Map a whole term to an IRI.
+    ///
+    /// Unlike a prefix, a term only matches when it is the entire
+    /// token, as with the term definitions of a JSON-LD context. Terms
+    /// are checked before a CURIE is split into its prefix and
+    /// reference, so a term containing `':'` takes precedence over the
+    /// prefix it starts with. When shrinking, an IRI that a term maps
+    /// to is shrunk to the term.
+    ///
+    /// Adding a term that is already mapped replaces its IRI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::{Curie, PrefixMapping};
+    ///
+    /// let mut mapping = PrefixMapping::default();
+    /// mapping.set_default("http://schema.org/");
+    /// mapping.add_term("name", "http://xmlns.com/foaf/0.1/name");
+    ///
+    /// assert_eq!(mapping.expand_curie_string("name"),
+    ///            Ok(String::from("http://xmlns.com/foaf/0.1/name")));
+    /// assert_eq!(mapping.expand_curie_string("Person"),
+    ///            Ok(String::from("http://schema.org/Person")));
+    /// assert_eq!(mapping.shrink_iri("http://xmlns.com/foaf/0.1/name"),
+    ///            Ok(Curie::new(None, "name")));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`PrefixMapping::remove_term()`]
+    pub fn add_term(&mut self, term: &str, iri: &str) {
+        self.terms.insert(String::from(term), String::from(iri));
+    }
+
+    /// Remove a term, returning the IRI it was mapped to.
+    pub fn remove_term(&mut self, term: &str) -> Option<String> {
+        self.terms.shift_remove(term)
+    }
+
+    /// The IRI that `term` is mapped to, if it is a term.
+    #[must_use]
+    pub fn term(&self, term: &str) -> Option<&str> {
+        self.terms.get(term).map(String::as_str)
+    }
+
+    /// Return an iterator over the terms and their IRIs.
+    ///
+    /// The iterator yields terms in the same order they were added.
+    #[must_use]
+    pub fn terms(&self) -> Mappings<'_> {
+        Mappings::new(&self.terms)
+    }
+
+    /// The term that maps to exactly `iri`, if there is one.
+    ///
+    /// When several terms map to the same IRI, the one added first is
+    /// used.
+    pub(crate) fn term_for_iri(&self, iri: &str) -> Option<&str> {
+        self.terms
+            .iter()
+            .find(|&(_, term_iri)| term_iri == iri)
+            .map(|(term, _)| term.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Curie, ExpansionError, PrefixMapping};
+
+    #[test]
+    fn terms_before_prefixes() {
+        let mut mapping = PrefixMapping::default();
+        mapping
+            .add_prefix("foaf", "http://xmlns.com/foaf/0.1/")
+            .unwrap();
+        mapping.add_term("foaf:nick", "http://example.com/nickname");
+        mapping.add_term("knows", "http://xmlns.com/foaf/0.1/knows");
+
+        assert_eq!(
+            mapping.expand_curie_string("foaf:nick"),
+            Ok(String::from("http://example.com/nickname"))
+        );
+        assert_eq!(
+            mapping.expand_curie_string("foaf:name"),
+            Ok(String::from("http://xmlns.com/foaf/0.1/name"))
+        );
+        assert_eq!(
+            mapping.expand_curie(&Curie::new(None, "knows")),
+            Ok(String::from("http://xmlns.com/foaf/0.1/knows"))
+        );
+        assert_eq!(
+            mapping.expand_curie_string("name"),
+            Err(ExpansionError::MissingDefault)
+        );
+
+        // An exact match with a term is preferred to a prefix.
+        assert_eq!(
+            mapping.shrink_iri("http://xmlns.com/foaf/0.1/knows"),
+            Ok(Curie::new(None, "knows"))
+        );
+        assert_eq!(
+            mapping.shrink_iri("http://xmlns.com/foaf/0.1/name"),
+            Ok(Curie::new(Some("foaf"), "name"))
+        );
+
+        assert_eq!(
+            mapping.terms().map(|(term, _)| term).collect::<Vec<_>>(),
+            ["foaf:nick", "knows"]
+        );
+        assert_eq!(
+            mapping.remove_term("knows"),
+            Some(String::from("http://xmlns.com/foaf/0.1/knows"))
+        );
+        assert_eq!(mapping.term("knows"), None);
+        assert_eq!(mapping.remove_term("knows"), None);
+    }
+}
@@ -0,0 +1,253 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Assertions for use in test suites of code using this crate.
+//!
+//! The [`assert_expands_to!`] and [`assert_shrinks_to!`] macros check
+//! the result of expanding a CURIE or shrinking an IRI. When they fail,
+//! the panic message includes the mapping entries that were relevant
+//! to the operation.
+//!
+//! ```
+//! use curie::{assert_expands_to, assert_shrinks_to, PrefixMapping};
+//!
+//! let mut mapping = PrefixMapping::default();
+//! mapping.add_prefix("foaf", "http://xmlns.com/foaf/0.1/").unwrap();
+//!
+//! assert_expands_to!(mapping, "foaf:Agent", "http://xmlns.com/foaf/0.1/Agent");
+//! assert_shrinks_to!(mapping, "http://xmlns.com/foaf/0.1/Agent", "foaf:Agent");
+//! ```
+//!
+//! [`assert_expands_to!`]: crate::assert_expands_to
+//! [`assert_shrinks_to!`]: crate::assert_shrinks_to
+
+use crate::{Curie, PrefixMapping};
+use std::fmt::Write;
+
+/// Assert that a CURIE expands to the expected IRI.
+///
+/// This is the function behind [`assert_expands_to!`](crate::assert_expands_to).
+///
+/// # Panics
+///
+/// Panics if `curie` does not expand to `expected`. The message
+/// includes what the prefix of `curie` is bound to, whether a
+/// namespace, a URI template or one inherited from a parent mapping,
+/// or for a CURIE without a prefix, the term or default it uses.
+#[track_caller]
+pub fn assert_expands_to(mapping: &PrefixMapping, curie: &str, expected: &str) {
+    let actual = mapping.expand_curie_string(curie);
+    if actual.as_deref() == Ok(expected) {
+        return;
+    }
+
+    let mut message = format!(
+        "assertion failed: `{curie}` expands to `{expected}`\n  \
+         expected: {expected:?}\n  \
+         found: {actual:?}\n  \
+         relevant mappings:\n"
+    );
+    // Templates and terms are only looked up in the mapping itself, and
+    // namespaces in its parents too.
+    let layers = || std::iter::successors(Some(mapping), |layer| layer.parent.as_deref());
+    let source = |layer: &PrefixMapping| {
+        if std::ptr::eq(layer, mapping) {
+            ""
+        } else {
+            " (from a parent)"
+        }
+    };
+    let Curie { prefix, reference } = Curie::split(curie);
+    match prefix {
+        Some(prefix) => {
+            if let Some(template) = mapping.prefix_template(prefix) {
+                writeln!(message, "    {prefix:?} => template {template:?}")
+            } else if let Some((layer, namespace)) = layers().find_map(|layer| {
+                layer
+                    .get_expansion(prefix)
+                    .map(|namespace| (layer, namespace))
+            }) {
+                writeln!(message, "    {prefix:?} => {namespace:?}{}", source(layer))
+            } else {
+                writeln!(message, "    {prefix:?} is not mapped")
+            }
+        }
+        None => {
+            if let Some(iri) = mapping.term(reference) {
+                writeln!(message, "    term {reference:?} => {iri:?}")
+            } else if let Some((layer, default)) =
+                layers().find_map(|layer| layer.default.as_deref().map(|default| (layer, default)))
+            {
+                writeln!(message, "    default => {default:?}{}", source(layer))
+            } else {
+                writeln!(message, "    no default is set")
+            }
+        }
+    }
+    .unwrap();
+    panic!("{message}");
+}
+
+/// Assert that an IRI shrinks to the expected CURIE.
+///
+/// This is the function behind [`assert_shrinks_to!`](crate::assert_shrinks_to).
+///
+/// # Panics
+///
+/// Panics if `iri` does not shrink to `expected`. The message includes
+/// the default and every mapping whose namespace is a prefix of `iri`.
+#[track_caller]
+pub fn assert_shrinks_to(mapping: &PrefixMapping, iri: &str, expected: &str) {
+    let actual = mapping.shrink_iri(iri).map(String::from);
+    if actual.as_deref() == Ok(expected) {
+        return;
+    }
+
+    let mut message = format!(
+        "assertion failed: `{iri}` shrinks to `{expected}`\n  \
+         expected: {expected:?}\n  \
+         found: {actual:?}\n  \
+         relevant mappings:\n"
+    );
+    let mut any_relevant = false;
+    if let Some(ref default) = mapping.default {
+        if iri.starts_with(default.as_str()) {
+            writeln!(message, "    default => {default:?}").unwrap();
+            any_relevant = true;
+        }
+    }
+    for (prefix, namespace) in mapping.mappings() {
+        if iri.starts_with(namespace) {
+            writeln!(message, "    {prefix:?} => {namespace:?}").unwrap();
+            any_relevant = true;
+        }
+    }
+    if !any_relevant {
+        writeln!(message, "    no namespace matches").unwrap();
+    }
+    panic!("{message}");
+}
+
+/// Assert that a CURIE expands to the expected IRI using a
+/// [`PrefixMapping`](crate::PrefixMapping).
+///
+/// On failure, the panic message includes the mapping entries
+/// relevant to the expansion.
+///
+/// # Examples
+///
+/// ```
+/// use curie::{assert_expands_to, PrefixMapping};
+///
+/// let mut mapping = PrefixMapping::default();
+/// mapping.set_default("http://example.com/");
+///
+/// assert_expands_to!(mapping, "Entity", "http://example.com/Entity");
+/// ```
+///
+/// ```should_panic
+/// use curie::{assert_expands_to, PrefixMapping};
+///
+/// let mapping = PrefixMapping::default();
+///
+/// // Panics with a message noting that "foaf" is not mapped.
+/// assert_expands_to!(mapping, "foaf:Agent", "http://xmlns.com/foaf/0.1/Agent");
+/// ```
+#[macro_export]
+macro_rules! assert_expands_to {
+    ($mapping:expr, $curie:expr, $expected:expr $(,)?) => {
+        $crate::testing::assert_expands_to(&$mapping, $curie, $expected)
+    };
+}
+
+/// Assert that an IRI shrinks to the expected CURIE using a
+/// [`PrefixMapping`](crate::PrefixMapping).
+///
+/// The expected CURIE is given in its string form. On failure, the
+/// panic message includes every mapping entry that matched the IRI.
+///
+/// # Examples
+///
+/// ```
+/// use curie::{assert_shrinks_to, PrefixMapping};
+///
+/// let mut mapping = PrefixMapping::default();
+/// mapping.add_prefix("foaf", "http://xmlns.com/foaf/0.1/").unwrap();
+///
+/// assert_shrinks_to!(mapping, "http://xmlns.com/foaf/0.1/Agent", "foaf:Agent");
+/// ```
+#[macro_export]
+macro_rules! assert_shrinks_to {
+    ($mapping:expr, $iri:expr, $expected:expr $(,)?) => {
+        $crate::testing::assert_shrinks_to(&$mapping, $iri, $expected)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::PrefixMapping;
+
+    const FOAF_VOCAB: &str = "http://xmlns.com/foaf/0.1/";
+
+    #[test]
+    #[should_panic(expected = "\"foaf\" is not mapped")]
+    fn expands_to_reports_unmapped_prefix() {
+        let mapping = PrefixMapping::default();
+        assert_expands_to!(mapping, "foaf:Agent", "http://xmlns.com/foaf/0.1/Agent");
+    }
+
+    #[test]
+    #[should_panic(expected = "\"foaf\" => \"http://xmlns.com/foaf/0.1/\"")]
+    fn expands_to_reports_mapped_prefix() {
+        let mut mapping = PrefixMapping::default();
+        mapping.add_prefix("foaf", FOAF_VOCAB).unwrap();
+        assert_expands_to!(mapping, "foaf:Agent", "http://xmlns.com/foaf/0.1/Person");
+    }
+
+    #[test]
+    #[should_panic(expected = "\"doi\" => template \"https://doi.org/{id}\"")]
+    fn expands_to_reports_template() {
+        let mut mapping = PrefixMapping::default();
+        mapping
+            .add_prefix_template("doi", "https://doi.org/{id}")
+            .unwrap();
+        assert_expands_to!(mapping, "doi:10.1000/182", "https://doi.org/182");
+    }
+
+    #[test]
+    #[should_panic(expected = "term \"name\" => \"http://xmlns.com/foaf/0.1/name\"")]
+    fn expands_to_reports_term() {
+        let mut mapping = PrefixMapping::default();
+        mapping.add_term("name", "http://xmlns.com/foaf/0.1/name");
+        assert_expands_to!(mapping, "name", "http://schema.org/name");
+    }
+
+    #[test]
+    #[should_panic(expected = "\"foaf\" => \"http://xmlns.com/foaf/0.1/\" (from a parent)")]
+    fn expands_to_reports_parent() {
+        let mut parent = PrefixMapping::default();
+        parent.add_prefix("foaf", FOAF_VOCAB).unwrap();
+        let mapping = PrefixMapping::with_parent(std::sync::Arc::new(parent));
+        assert_expands_to!(mapping, "foaf:Agent", "http://xmlns.com/foaf/0.1/Person");
+    }
+
+    #[test]
+    #[should_panic(expected = "\"a\" => \"http://example.com/\"")]
+    fn shrinks_to_reports_candidates() {
+        let mut mapping = PrefixMapping::default();
+        mapping.add_prefix("a", "http://example.com/").unwrap();
+        mapping.add_prefix("b", "http://example.com/b/").unwrap();
+        assert_shrinks_to!(mapping, "http://example.com/b/thing", "a:b/thing");
+    }
+
+    #[test]
+    #[should_panic(expected = "no namespace matches")]
+    fn shrinks_to_reports_no_match() {
+        let mut mapping = PrefixMapping::default();
+        mapping.add_prefix("foaf", FOAF_VOCAB).unwrap();
+        assert_shrinks_to!(mapping, "http://example.com/thing", "ex:thing");
+    }
+}
@@ -0,0 +1,994 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Reading prefix declarations from [Turtle] and [TriG] documents.
+//!
+//! Both `@prefix` / `@base` and the SPARQL-style `PREFIX` / `BASE`
+//! directives are recognized wherever they appear between statements,
+//! not only at the start of the document. Everything else is skipped.
+//!
+//! The [`IncrementalImporter`] is fed the document in pieces as they
+//! arrive, so the mapping can be used for expansion while the rest of
+//! the document is still being read:
+//!
+//! ```
+//! use curie::turtle::IncrementalImporter;
+//!
+//! let mut importer = IncrementalImporter::new();
+//! importer.feed_line("@prefix foaf: <http://xmlns.com/foaf/0.1/> .").unwrap();
+//! importer.feed_line("<#me> a foaf:Person .").unwrap();
+//! assert_eq!(importer.mapping().expand_curie_string("foaf:Person"),
+//!            Ok(String::from("http://xmlns.com/foaf/0.1/Person")));
+//!
+//! importer.feed_chunk("PREFIX dc: <http://purl.org/dc/").unwrap();
+//! assert!(importer.mapping().expand_curie_string("dc:title").is_err());
+//! importer.feed_chunk("terms/>\n").unwrap();
+//! assert_eq!(importer.mapping().expand_curie_string("dc:title"),
+//!            Ok(String::from("http://purl.org/dc/terms/title")));
+//!
+//! let mapping = importer.finish().unwrap();
+//! assert_eq!(mapping.mappings().count(), 2);
+//! ```
+//!
+//! A whole document can be read at once with
+//! [`PrefixMapping::from_turtle()`].
+//!
+//! To see the directives themselves, to filter or log them before they
+//! are applied, [`directives()`] reads them from a document without
+//! applying them to a mapping. Each importer has such an iterator, and
+//! all of them yield a [`Directive`]:
+//!
+//! * [`directives()`] for Turtle and TriG documents,
+//! * [`sparql_directives()`](crate::sparql_directives) for the prologue
+//!   of a SPARQL query,
+//! * [`xmlns_directives()`](crate::xmlns_directives) for `xmlns`
+//!   attributes,
+//! * [`rdfa::prefix_directives()`](crate::rdfa::prefix_directives) for
+//!   RDFa `prefix` attributes, and
+//! * `jsonld::directives()` for JSON-LD contexts, with the `jsonld`
+//!   feature.
+//!
+//! `\u` and `\U` escapes in IRIs are decoded. When the directives are
+//! applied to a mapping, by [`PrefixMapping::extend_from_turtle()`] or
+//! the [`IncrementalImporter`], relative IRIs in prefix and base
+//! directives are resolved against the base IRI in effect at that
+//! point, and left as written if there is none. [`directives()`]
+//! yields IRIs unresolved, leaving resolution to the caller, for
+//! instance with [`PrefixMapping::resolve_iri()`].
+//!
+//! [Turtle]: https://www.w3.org/TR/turtle/
+//! [TriG]: https://www.w3.org/TR/trig/
+
+use crate::parse_error::{token_end, TextPosition};
+use crate::{syntax, InvalidPrefixError, ParseError, ParseErrorKind, PrefixMapping};
+use std::iter::FusedIterator;
+use std::ops::Range;
+
+/// A directive read from a document.
+///
+/// This is yielded by the directive iterators of all of the importers.
+/// Turtle, TriG and SPARQL only declare prefixes and base IRIs.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Directive {
+    /// A prefix declaration.
+    Prefix {
+        /// The prefix being declared, which may be empty.
+        prefix: String,
+        /// The namespace that the prefix is bound to.
+        iri: String,
+    },
+    /// A base IRI declaration.
+    Base(String),
+    /// A declaration of the default prefix, such as a JSON-LD `@vocab`
+    /// or an `xmlns` attribute.
+    DefaultPrefix(String),
+    /// A term definition, from a JSON-LD context.
+    Term {
+        /// The term being defined.
+        term: String,
+        /// The IRI that the whole term maps to.
+        iri: String,
+    },
+}
+
+/// A directive along with where its parts are in the text.
+pub(crate) struct ParsedDirective {
+    pub(crate) directive: Directive,
+    /// The length of the whole directive.
+    pub(crate) len: usize,
+    /// The prefix being declared, which is empty for a base directive.
+    pub(crate) prefix: Range<usize>,
+    /// The IRI, including its angle brackets.
+    pub(crate) iri: Range<usize>,
+}
+
+/// Why reading stopped before reaching a result.
+pub(crate) enum Stop {
+    /// More input is needed.
+    Incomplete,
+    /// An error about the span `start..end` of the text being read.
+    Error(ParseErrorKind, usize, usize),
+}
+
+/// The lexical context while skipping over a statement.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Mode {
+    Normal,
+    Comment,
+    Iri,
+    String { quote: u8, long: bool },
+}
+
+/// How far a statement that is not a directive has been skipped.
+#[derive(Debug)]
+struct Statement {
+    /// Relative to the start of the statement.
+    pos: usize,
+    mode: Mode,
+    /// The nesting of TriG graph blocks.
+    depth: usize,
+}
+
+/// Reads prefix declarations from a Turtle or TriG document that is
+/// fed in pieces.
+///
+/// Each directive is applied to the mapping as soon as it is complete,
+/// so [`IncrementalImporter::mapping()`] always reflects the document
+/// read so far.
+///
+/// See the [module documentation](crate::turtle) for an example.
+#[derive(Debug, Default)]
+pub struct IncrementalImporter {
+    mapping: PrefixMapping,
+    scanner: Scanner,
+}
+
+impl IncrementalImporter {
+    /// Construct an importer with an empty mapping.
+    #[must_use]
+    pub fn new() -> Self {
+        IncrementalImporter::default()
+    }
+
+    /// Construct an importer that adds to an existing mapping.
+    #[must_use]
+    pub fn with_mapping(mapping: PrefixMapping) -> Self {
+        IncrementalImporter {
+            mapping,
+            ..IncrementalImporter::default()
+        }
+    }
+
+    /// The mapping, with the directives read so far.
+    #[must_use]
+    pub fn mapping(&self) -> &PrefixMapping {
+        &self.mapping
+    }
+
+    /// The IRI of the most recent base directive, if any.
+    ///
+    /// This is also the [base IRI](PrefixMapping::base()) of the
+    /// mapping, so it is the mapping's base IRI before any base
+    /// directive has been read.
+    #[must_use]
+    pub fn base(&self) -> Option<&str> {
+        self.mapping.base()
+    }
+
+    /// Feed the next piece of the document.
+    ///
+    /// Pieces may be split anywhere, including in the middle of
+    /// a directive.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] if a malformed directive is found.
+    /// The importer should not be used after an error.
+    pub fn feed_chunk(&mut self, chunk: &str) -> Result<(), ParseError> {
+        self.scanner.push(chunk);
+        self.process(false)
+    }
+
+    /// Feed the next line of the document, without its line terminator.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] if a malformed directive is found.
+    /// The importer should not be used after an error.
+    pub fn feed_line(&mut self, line: &str) -> Result<(), ParseError> {
+        self.scanner.push(line);
+        self.scanner.push("\n");
+        self.process(false)
+    }
+
+    /// Signal the end of the document, returning the mapping.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] if the document ends in the middle
+    /// of a statement.
+    pub fn finish(mut self) -> Result<PrefixMapping, ParseError> {
+        self.process(true)?;
+        Ok(self.mapping)
+    }
+
+    fn process(&mut self, at_end: bool) -> Result<(), ParseError> {
+        while let Some((directive, span)) = self.scanner.next_directive(at_end)? {
+            if let Err(kind) = self.mapping.apply_directive(directive) {
+                return Err(self.scanner.error(kind, span.start, span.end));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl PrefixMapping {
+    /// Apply a directive read from a Turtle document or a SPARQL
+    /// prologue, resolving relative IRIs against the base IRI.
+    pub(crate) fn apply_directive(&mut self, directive: Directive) -> Result<(), ParseErrorKind> {
+        match directive {
+            Directive::Prefix { prefix, iri } => {
+                match self.add_prefix(&prefix, &self.resolve_declared_iri(&iri)) {
+                    Ok(()) => Ok(()),
+                    Err(InvalidPrefixError::ReservedPrefix | InvalidPrefixError::InvalidName) => {
+                        Err(ParseErrorKind::InvalidPrefix)
+                    }
+                    Err(InvalidPrefixError::ProtectedPrefix) => {
+                        Err(ParseErrorKind::ProtectedPrefix)
+                    }
+                    Err(InvalidPrefixError::InvalidNamespace) => Err(ParseErrorKind::InvalidIri),
+                    Err(InvalidPrefixError::AlreadyBound) => Err(ParseErrorKind::RedefinedPrefix),
+                }
+            }
+            Directive::Base(iri) => {
+                self.set_relative_base(&iri);
+                Ok(())
+            }
+            Directive::DefaultPrefix(iri) => {
+                self.set_default(&self.resolve_declared_iri(&iri));
+                Ok(())
+            }
+            Directive::Term { term, iri } => {
+                self.add_term(&term, &self.resolve_declared_iri(&iri));
+                Ok(())
+            }
+        }
+    }
+}
+
+impl PrefixMapping {
+    /// Create a mapping from the prefix and base declarations in a
+    /// Turtle or TriG document.
+    ///
+    /// See [`PrefixMapping::extend_from_turtle()`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] if a malformed directive is found, or
+    /// the document ends in the middle of a statement.
+    pub fn from_turtle(text: &str) -> Result<PrefixMapping, ParseError> {
+        let mut mapping = PrefixMapping::default();
+        mapping.extend_from_turtle(text)?;
+        Ok(mapping)
+    }
+
+    /// Add the prefix declarations in a Turtle or TriG document to this
+    /// mapping, and set its base IRI from the last base declaration.
+    /// Relative IRIs in declarations are resolved against the base IRI
+    /// in effect, as [`PrefixMapping::resolve_iri()`] does.
+    ///
+    /// Both `@prefix` / `@base` and `PREFIX` / `BASE` are read, wherever
+    /// they are in the document. To read a document as it arrives, use
+    /// an [`IncrementalImporter`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] if a malformed directive is found, or
+    /// the document ends in the middle of a statement. Declarations
+    /// read before the error was found remain in the mapping.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::PrefixMapping;
+    ///
+    /// let mapping = PrefixMapping::from_turtle("\
+    /// @base <http://example.com/> .
+    /// @prefix foaf: <http://xmlns.com/foaf/0.1/> .
+    /// PREFIX dc: <http://purl.org/dc/terms/>
+    /// @prefix ex: <vocab#> .
+    ///
+    /// <#me> a foaf:Person ; dc:title \"Me\" .
+    /// ").unwrap();
+    ///
+    /// assert_eq!(mapping.base(), Some("http://example.com/"));
+    /// assert_eq!(mapping.expand_curie_string("ex:Thing"),
+    ///            Ok(String::from("http://example.com/vocab#Thing")));
+    /// assert_eq!(mapping.expand_curie_string("dc:title"),
+    ///            Ok(String::from("http://purl.org/dc/terms/title")));
+    /// ```
+    pub fn extend_from_turtle(&mut self, text: &str) -> Result<(), ParseError> {
+        let mut importer = IncrementalImporter::with_mapping(std::mem::take(self));
+        let result = importer
+            .feed_chunk(text)
+            .and_then(|()| importer.process(true));
+        *self = importer.mapping;
+        result
+    }
+}
+
+/// Read the directives of a Turtle or TriG document as it arrives, in
+/// chunks, without applying them to a mapping.
+///
+/// The chunks are joined together, so they may be split anywhere, but
+/// lines must keep their line terminators. IRIs are yielded without
+/// being resolved against the base IRI. Reading stops after the first
+/// error.
+///
+/// # Examples
+///
+/// ```
+/// use curie::turtle::{self, Directive};
+///
+/// let doc = "@prefix ex: <http://example.com/> .\n\
+///            @base <http://example.com/base/> .\n\
+///            ex:a ex:b ex:c .\n\
+///            PREFIX dc: <http://purl.org/dc/terms/>\n";
+///
+/// // Any iterator of chunks will do, such as the lines of a file.
+/// let directives = turtle::directives(doc.split_inclusive('\n'))
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+/// assert_eq!(directives, [
+///     Directive::Prefix {
+///         prefix: String::from("ex"),
+///         iri: String::from("http://example.com/"),
+///     },
+///     Directive::Base(String::from("http://example.com/base/")),
+///     Directive::Prefix {
+///         prefix: String::from("dc"),
+///         iri: String::from("http://purl.org/dc/terms/"),
+///     },
+/// ]);
+/// ```
+pub fn directives<I>(chunks: I) -> Directives<I::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    Directives {
+        chunks: chunks.into_iter(),
+        scanner: Scanner::default(),
+        at_end: false,
+        done: false,
+    }
+}
+
+/// An iterator over the directives of a document.
+///
+/// This is created by [`directives()`].
+#[derive(Debug)]
+pub struct Directives<I> {
+    chunks: I,
+    scanner: Scanner,
+    /// Whether all of the chunks have been read.
+    at_end: bool,
+    /// Whether the end of the document or an error has been reached.
+    done: bool,
+}
+
+impl<I> Iterator for Directives<I>
+where
+    I: Iterator,
+    I::Item: AsRef<str>,
+{
+    type Item = Result<Directive, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            match self.scanner.next_directive(self.at_end) {
+                Ok(Some((directive, _))) => return Some(Ok(directive)),
+                Ok(None) if self.at_end => self.done = true,
+                Ok(None) => match self.chunks.next() {
+                    Some(chunk) => self.scanner.push(chunk.as_ref()),
+                    None => self.at_end = true,
+                },
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+        None
+    }
+}
+
+impl<I> FusedIterator for Directives<I>
+where
+    I: Iterator,
+    I::Item: AsRef<str>,
+{
+}
+
+/// Finds the directives in a document that arrives in pieces, skipping
+/// over other statements.
+#[derive(Debug, Default)]
+struct Scanner {
+    /// Input that has not been discarded yet.
+    buffer: String,
+    /// The length of the start of `buffer` that has been read.
+    consumed: usize,
+    /// The position in the input of the start of `buffer`.
+    position: TextPosition,
+    /// The statement being skipped, if any.
+    statement: Option<Statement>,
+}
+
+impl Scanner {
+    /// Add the next piece of the document, discarding what has been
+    /// read.
+    fn push(&mut self, chunk: &str) {
+        self.position.advance(&self.buffer[..self.consumed]);
+        self.buffer.drain(..self.consumed);
+        self.consumed = 0;
+        self.buffer.push_str(chunk);
+    }
+
+    /// Read the next directive, returning it with its span in the
+    /// buffer, or `None` if more input is needed or, `at_end`, there
+    /// are no more directives.
+    fn next_directive(
+        &mut self,
+        at_end: bool,
+    ) -> Result<Option<(Directive, Range<usize>)>, ParseError> {
+        loop {
+            if let Some(ref mut statement) = self.statement {
+                match skip_statement(&self.buffer.as_bytes()[self.consumed..], statement, at_end) {
+                    Some(len) => {
+                        self.consumed += len;
+                        self.statement = None;
+                        continue;
+                    }
+                    None if at_end => return Err(self.unexpected_end()),
+                    None => return Ok(None),
+                }
+            }
+
+            let rest = &self.buffer[self.consumed..];
+            match skip_space(rest, at_end) {
+                Some(len) => self.consumed += len,
+                None => {
+                    // An unterminated comment: keep it until its end
+                    // has been seen.
+                    self.consumed += rest.len() - rest.trim_start().len();
+                    return Ok(None);
+                }
+            }
+            let start = self.consumed;
+            if start == self.buffer.len() {
+                return Ok(None);
+            }
+
+            match parse_directive(&self.buffer[start..], at_end) {
+                Ok(Some(ParsedDirective { directive, len, .. })) => {
+                    self.consumed += len;
+                    return Ok(Some((directive, start..start + len)));
+                }
+                Ok(None) => {
+                    self.statement = Some(Statement {
+                        pos: 0,
+                        mode: Mode::Normal,
+                        depth: 0,
+                    });
+                }
+                Err(Stop::Incomplete) if at_end => return Err(self.unexpected_end()),
+                Err(Stop::Incomplete) => return Ok(None),
+                Err(Stop::Error(kind, from, to)) => {
+                    return Err(self.error(kind, start + from, start + to))
+                }
+            }
+        }
+    }
+
+    /// Construct an error about `start..end` of the buffer.
+    fn error(&self, kind: ParseErrorKind, start: usize, end: usize) -> ParseError {
+        let position = self.position.advanced(&self.buffer[..start]);
+        ParseError::new(kind, position, &self.buffer[start..end])
+    }
+
+    fn unexpected_end(&self) -> ParseError {
+        let end = self.buffer.len();
+        self.error(ParseErrorKind::UnexpectedEnd, end, end)
+    }
+}
+
+/// Find the length of the whitespace and comments at the start of
+/// `text`.
+///
+/// Returns `None` if `text` ends within a comment and more input
+/// may follow.
+pub(crate) fn skip_space(text: &str, at_end: bool) -> Option<usize> {
+    let mut rest = text;
+    loop {
+        rest = rest.trim_start();
+        if !rest.starts_with('#') {
+            return Some(text.len() - rest.len());
+        }
+        match rest.find(['\n', '\r']) {
+            Some(idx) => rest = &rest[idx..],
+            None if at_end => return Some(text.len()),
+            None => return None,
+        }
+    }
+}
+
+/// Can `b` continue a prefixed name or number after a `'.'`?
+fn is_name_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || matches!(b, b'_' | b'-' | b':' | b'%') || b >= 0x80
+}
+
+/// Continue skipping over a statement, returning its length once its
+/// end has been found.
+///
+/// Only ASCII bytes are significant, so scanning bytes is safe even
+/// though the text is UTF-8.
+fn skip_statement(bytes: &[u8], statement: &mut Statement, at_end: bool) -> Option<usize> {
+    while statement.pos < bytes.len() {
+        let b = bytes[statement.pos];
+        match statement.mode {
+            Mode::Comment => {
+                if b == b'\n' || b == b'\r' {
+                    statement.mode = Mode::Normal;
+                }
+            }
+            Mode::Iri => {
+                if b == b'>' {
+                    statement.mode = Mode::Normal;
+                }
+            }
+            Mode::String { quote, long } => {
+                if b == b'\\' {
+                    if statement.pos + 1 == bytes.len() {
+                        return None;
+                    }
+                    statement.pos += 2;
+                    continue;
+                }
+                if b == quote && long {
+                    // A long string may end with up to two quotes
+                    // before the closing ones, so take the whole run.
+                    let run = bytes[statement.pos..]
+                        .iter()
+                        .take_while(|&&c| c == quote)
+                        .count();
+                    if statement.pos + run == bytes.len() && !at_end {
+                        return None;
+                    }
+                    if run >= 3 {
+                        statement.mode = Mode::Normal;
+                    }
+                    statement.pos += run;
+                    continue;
+                } else if b == quote {
+                    statement.mode = Mode::Normal;
+                }
+            }
+            Mode::Normal => match b {
+                b'#' => statement.mode = Mode::Comment,
+                b'<' => statement.mode = Mode::Iri,
+                b'"' | b'\'' => {
+                    if bytes.len() - statement.pos < 3 && !at_end {
+                        return None;
+                    }
+                    let long = bytes[statement.pos..].starts_with(&[b; 3]);
+                    statement.mode = Mode::String { quote: b, long };
+                    if long {
+                        statement.pos += 3;
+                        continue;
+                    }
+                }
+                b'{' => statement.depth += 1,
+                b'}' => {
+                    statement.depth = statement.depth.saturating_sub(1);
+                    if statement.depth == 0 {
+                        return Some(statement.pos + 1);
+                    }
+                }
+                b'.' if statement.depth == 0 => match bytes.get(statement.pos + 1) {
+                    None if !at_end => return None,
+                    Some(&next) if is_name_byte(next) => {}
+                    _ => return Some(statement.pos + 1),
+                },
+                _ => {}
+            },
+        }
+        statement.pos += 1;
+    }
+    None
+}
+
+/// A position within a directive being parsed.
+struct Cursor<'a> {
+    text: &'a str,
+    pos: usize,
+    at_end: bool,
+}
+
+impl Cursor<'_> {
+    fn error(&self) -> Stop {
+        Stop::Error(
+            ParseErrorKind::InvalidDirective,
+            self.pos,
+            token_end(self.text, self.pos),
+        )
+    }
+
+    fn skip_space(&mut self) -> Result<(), Stop> {
+        match skip_space(&self.text[self.pos..], self.at_end) {
+            Some(len) => {
+                self.pos += len;
+                Ok(())
+            }
+            None => Err(Stop::Incomplete),
+        }
+    }
+
+    fn peek(&self) -> Result<char, Stop> {
+        self.text[self.pos..].chars().next().ok_or(Stop::Incomplete)
+    }
+
+    fn prefix(&mut self) -> Result<String, Stop> {
+        let rest = &self.text[self.pos..];
+        let len = rest
+            .find(|c: char| c == ':' || c == '<' || c.is_whitespace())
+            .ok_or(Stop::Incomplete)?;
+        if !rest[len..].starts_with(':') {
+            return Err(Stop::Error(
+                ParseErrorKind::InvalidDirective,
+                self.pos,
+                self.pos + len,
+            ));
+        }
+        let prefix = &rest[..len];
+        if !syntax::is_pn_prefix(prefix) {
+            return Err(Stop::Error(
+                ParseErrorKind::InvalidPrefix,
+                self.pos,
+                self.pos + len,
+            ));
+        }
+        self.pos += len + 1;
+        Ok(String::from(prefix))
+    }
+
+    fn iri(&mut self) -> Result<String, Stop> {
+        if self.peek()? != '<' {
+            return Err(self.error());
+        }
+        let rest = &self.text[self.pos + 1..];
+        let len = rest.find('>').ok_or(Stop::Incomplete)?;
+        let invalid = || Stop::Error(ParseErrorKind::InvalidIri, self.pos, self.pos + len + 2);
+        let iri = unescape_iri(&rest[..len]).ok_or_else(invalid)?;
+        if syntax::find_invalid_iri_char(&iri).is_some() {
+            return Err(invalid());
+        }
+        self.pos += len + 2;
+        Ok(iri)
+    }
+}
+
+/// Decode the `\u` and `\U` escapes in an `IRIREF`, which are the only
+/// escapes allowed there.
+fn unescape_iri(iri: &str) -> Option<String> {
+    let mut unescaped = String::with_capacity(iri.len());
+    let mut rest = iri;
+    while let Some(idx) = rest.find('\\') {
+        unescaped.push_str(&rest[..idx]);
+        let (c, len) = syntax::decode_uchar(&rest[idx + 1..])?;
+        unescaped.push(c);
+        rest = &rest[idx + 1 + len..];
+    }
+    unescaped.push_str(rest);
+    Some(unescaped)
+}
+
+/// Try to parse a directive at the start of `text`, returning it with
+/// its length, or `None` if `text` starts with some other statement.
+pub(crate) fn parse_directive(text: &str, at_end: bool) -> Result<Option<ParsedDirective>, Stop> {
+    let (word_start, at_form) = if text.starts_with('@') {
+        (1, true)
+    } else {
+        (0, false)
+    };
+    let word_len = text[word_start..]
+        .bytes()
+        .take_while(u8::is_ascii_alphabetic)
+        .count();
+    let word_end = word_start + word_len;
+    if word_end == text.len() && !at_end {
+        return Err(Stop::Incomplete);
+    }
+    let word = &text[word_start..word_end];
+    let is_prefix = if at_form {
+        match word {
+            "prefix" => true,
+            "base" => false,
+            _ => {
+                return Err(Stop::Error(
+                    ParseErrorKind::InvalidDirective,
+                    0,
+                    token_end(text, 0),
+                ))
+            }
+        }
+    } else {
+        // Without the `@`, the keyword must be followed by a separator
+        // so that prefixed names such as `base:x` are not mistaken for
+        // directives.
+        let separated = text[word_end..]
+            .chars()
+            .next()
+            .is_none_or(|c| c.is_whitespace() || c == '<' || c == '#');
+        match word {
+            _ if !separated => return Ok(None),
+            _ if word.eq_ignore_ascii_case("prefix") => true,
+            _ if word.eq_ignore_ascii_case("base") => false,
+            _ => return Ok(None),
+        }
+    };
+
+    let mut cursor = Cursor {
+        text,
+        pos: word_end,
+        at_end,
+    };
+    cursor.skip_space()?;
+    let prefix_start = cursor.pos;
+    let prefix = if is_prefix {
+        let prefix = cursor.prefix()?;
+        cursor.skip_space()?;
+        Some(prefix)
+    } else {
+        None
+    };
+    let prefix_span = prefix_start..prefix_start + prefix.as_ref().map_or(0, String::len);
+    let iri_start = cursor.pos;
+    let iri = cursor.iri()?;
+    let iri_span = iri_start..cursor.pos;
+    let directive = match prefix {
+        Some(prefix) => Directive::Prefix { prefix, iri },
+        None => Directive::Base(iri),
+    };
+    if at_form {
+        cursor.skip_space()?;
+        if cursor.peek()? != '.' {
+            return Err(cursor.error());
+        }
+        cursor.pos += 1;
+    }
+    Ok(Some(ParsedDirective {
+        directive,
+        len: cursor.pos,
+        prefix: prefix_span,
+        iri: iri_span,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOC: &str = r#"# A comment mentioning @prefix no: <http://example.com/no/> .
+@prefix ex: <http://example.com/> .
+@base <http://example.com/base/> .
+ex:a ex:b "a string with @prefix no: <x> . in it", """a long
+string with ". and ""quotes""""" ;
+    ex:c 1.5, ex:d.e .
+base:x prefix:y <z> .
+PREFIX dc: <http://purl.org/dc/terms/>
+GRAPH ex:g { ex:a ex:b ex:c . ex:d ex:e ex:f }
+prefix u: <http://example.com/é/>
+"#;
+
+    fn prefixes(mapping: &PrefixMapping) -> Vec<(&str, &str)> {
+        mapping.mappings().collect()
+    }
+
+    #[test]
+    fn whole_document() {
+        let mut importer = IncrementalImporter::new();
+        importer.feed_chunk(DOC).unwrap();
+        assert_eq!(importer.base(), Some("http://example.com/base/"));
+        let mapping = importer.finish().unwrap();
+        assert_eq!(
+            prefixes(&mapping),
+            [
+                ("ex", "http://example.com/"),
+                ("dc", "http://purl.org/dc/terms/"),
+                ("u", "http://example.com/é/"),
+            ]
+        );
+    }
+
+    #[test]
+    fn from_text() {
+        let mapping = PrefixMapping::from_turtle(DOC).unwrap();
+        assert_eq!(mapping.base(), Some("http://example.com/base/"));
+        assert_eq!(mapping.mappings().count(), 3);
+
+        // Declarations before an error are kept.
+        let mut mapping = PrefixMapping::default();
+        let err = mapping
+            .extend_from_turtle("@prefix ex: <http://example.com/> .\n@prefix _: <x> .\n")
+            .unwrap_err();
+        assert_eq!(err.kind(), ParseErrorKind::InvalidPrefix);
+        assert_eq!(prefixes(&mapping), [("ex", "http://example.com/")]);
+        assert_eq!(
+            PrefixMapping::from_turtle("ex:a ex:b").unwrap_err().kind(),
+            ParseErrorKind::UnexpectedEnd
+        );
+    }
+
+    #[test]
+    fn every_split() {
+        let mut expected = IncrementalImporter::new();
+        expected.feed_chunk(DOC).unwrap();
+        let expected = expected.finish().unwrap();
+
+        for idx in (0..DOC.len()).filter(|&idx| DOC.is_char_boundary(idx)) {
+            let mut importer = IncrementalImporter::new();
+            importer.feed_chunk(&DOC[..idx]).unwrap();
+            importer.feed_chunk(&DOC[idx..]).unwrap();
+            assert_eq!(importer.finish().unwrap(), expected, "split at {idx}");
+        }
+    }
+
+    #[test]
+    fn directives_apply_mid_stream() {
+        let mut importer = IncrementalImporter::new();
+        importer
+            .feed_line("@prefix ex: <http://example.com/> .")
+            .unwrap();
+        importer.feed_line("ex:a ex:b ex:c .").unwrap();
+        importer
+            .feed_line("@prefix ex: <http://example.org/> .")
+            .unwrap();
+        assert_eq!(
+            importer.mapping().expand_curie_string("ex:a"),
+            Ok(String::from("http://example.org/a"))
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let check = |doc: &str| {
+            let mut importer = IncrementalImporter::new();
+            let err = importer
+                .feed_chunk(doc)
+                .and_then(|()| importer.finish().map(|_| ()))
+                .unwrap_err();
+            (
+                err.kind(),
+                err.offset(),
+                err.line(),
+                err.column(),
+                err.slice().to_owned(),
+            )
+        };
+        assert_eq!(
+            check("ex:a ex:b ex:c .\n@prefix ex <http://example.com/> ."),
+            (
+                ParseErrorKind::InvalidDirective,
+                25,
+                2,
+                9,
+                String::from("ex")
+            )
+        );
+        assert_eq!(
+            check("@prefix _: <http://example.com/> ."),
+            (ParseErrorKind::InvalidPrefix, 8, 1, 9, String::from("_"))
+        );
+        assert_eq!(
+            check("@prefix ex: <http://example.com/a b> ."),
+            (
+                ParseErrorKind::InvalidIri,
+                12,
+                1,
+                13,
+                String::from("<http://example.com/a b>")
+            )
+        );
+        assert_eq!(
+            check("# é\n@prefix ex: <http://example.com/>"),
+            (ParseErrorKind::UnexpectedEnd, 38, 2, 34, String::new())
+        );
+        assert_eq!(
+            check("@keywords a ."),
+            (
+                ParseErrorKind::InvalidDirective,
+                0,
+                1,
+                1,
+                String::from("@keywords")
+            )
+        );
+        assert_eq!(
+            check("ex:a ex:b \"ex:c ."),
+            (ParseErrorKind::UnexpectedEnd, 17, 1, 18, String::new())
+        );
+    }
+
+    #[test]
+    fn position_across_chunks() {
+        let mut importer = IncrementalImporter::new();
+        importer
+            .feed_line("@prefix a: <http://example.com/a/> .")
+            .unwrap();
+        importer.feed_chunk("ex:a ex:b ex:c .\n  @pre").unwrap();
+        let err = importer.feed_chunk("fix b: <x y> .\n").unwrap_err();
+        assert_eq!((err.offset(), err.line(), err.column()), (67, 3, 14));
+        assert_eq!(err.slice(), "<x y>");
+    }
+
+    #[test]
+    fn protected_prefix() {
+        let mut mapping = PrefixMapping::default();
+        mapping.add_prefix("ex", "http://example.com/").unwrap();
+        mapping.protect_prefix("ex");
+
+        let mut importer = IncrementalImporter::with_mapping(mapping);
+        let err = importer
+            .feed_line("@prefix ex: <http://example.org/> .")
+            .unwrap_err();
+        assert_eq!(err.kind(), ParseErrorKind::ProtectedPrefix);
+        assert_eq!(err.slice(), "@prefix ex: <http://example.org/> .");
+    }
+
+    #[test]
+    fn redefined_prefix() {
+        let mut mapping = PrefixMapping::default();
+        mapping.set_strict_rebinding(true);
+        let err = mapping
+            .extend_from_turtle(
+                "@prefix ex: <http://example.com/> .\n\
+                 @prefix ex: <http://example.com/> .\n\
+                 @prefix ex: <http://example.org/> .\n",
+            )
+            .unwrap_err();
+        assert_eq!(err.kind(), ParseErrorKind::RedefinedPrefix);
+        assert_eq!(err.line(), 3);
+    }
+
+    #[test]
+    fn pull_directives() {
+        let all: Vec<Directive> = directives([DOC]).collect::<Result<_, _>>().unwrap();
+        assert_eq!(all.len(), 4);
+        assert_eq!(
+            all[1],
+            Directive::Base(String::from("http://example.com/base/"))
+        );
+
+        // Chunks of single characters give the same directives.
+        let chars: Vec<String> = DOC.chars().map(String::from).collect();
+        assert_eq!(
+            directives(&chars).collect::<Result<Vec<_>, _>>().unwrap(),
+            all
+        );
+
+        // Reading stops after an error.
+        let mut iter = directives(["@prefix : <x> .\n@prefix a: <x y> .\n@prefix b: <z> .\n"]);
+        assert!(
+            matches!(iter.next(), Some(Ok(Directive::Prefix { ref prefix, .. })) if prefix.is_empty())
+        );
+        let err = iter.next().unwrap().unwrap_err();
+        assert_eq!((err.kind(), err.line()), (ParseErrorKind::InvalidIri, 2));
+        assert!(iter.next().is_none());
+
+        let err = directives(["@prefix a: <x"]).last().unwrap().unwrap_err();
+        assert_eq!(err.kind(), ParseErrorKind::UnexpectedEnd);
+    }
+}
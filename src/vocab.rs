@@ -0,0 +1,225 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Constants for the well-known terms of the core RDF vocabularies.
+//!
+//! Each module is a [`Vocabulary`](crate::Vocabulary), with a
+//! [`Term`](crate::Term) constant for each of the commonly used terms,
+//! so that they can be referred to without string literals:
+//!
+//! ```
+//! use curie::vocab::{rdf, rdfs};
+//! use curie::{Curie, PrefixMapping};
+//!
+//! assert_eq!(rdf::TYPE.iri(), "http://www.w3.org/1999/02/22-rdf-syntax-ns#type");
+//! assert_eq!(rdfs::LABEL.curie(), Curie::new(Some("rdfs"), "label"));
+//!
+//! let mut mapping = PrefixMapping::default();
+//! mapping.add_vocabulary::<rdf::Rdf>().unwrap();
+//! assert_eq!(mapping.shrink_iri(rdf::TYPE.iri()), Ok(rdf::TYPE.curie()));
+//! ```
+//!
+//! The modules only cover the commonly used terms of each vocabulary.
+//! Others can be defined with the [`vocabulary!`](crate::vocabulary)
+//! macro.
+
+crate::vocabulary! {
+    /// The RDF vocabulary.
+    pub mod rdf, Rdf {
+        prefix: "rdf",
+        namespace: "http://www.w3.org/1999/02/22-rdf-syntax-ns#",
+        terms: {
+            TYPE = "type",
+            PROPERTY = "Property",
+            STATEMENT = "Statement",
+            SUBJECT = "subject",
+            PREDICATE = "predicate",
+            OBJECT = "object",
+            VALUE = "value",
+            LIST = "List",
+            FIRST = "first",
+            REST = "rest",
+            NIL = "nil",
+            LANG_STRING = "langString",
+            HTML = "HTML",
+            XML_LITERAL = "XMLLiteral",
+            JSON = "JSON",
+        }
+    }
+}
+
+crate::vocabulary! {
+    /// The RDF Schema vocabulary.
+    pub mod rdfs, Rdfs {
+        prefix: "rdfs",
+        namespace: "http://www.w3.org/2000/01/rdf-schema#",
+        terms: {
+            RESOURCE = "Resource",
+            CLASS = "Class",
+            LITERAL = "Literal",
+            DATATYPE = "Datatype",
+            CONTAINER = "Container",
+            SUB_CLASS_OF = "subClassOf",
+            SUB_PROPERTY_OF = "subPropertyOf",
+            DOMAIN = "domain",
+            RANGE = "range",
+            LABEL = "label",
+            COMMENT = "comment",
+            MEMBER = "member",
+            SEE_ALSO = "seeAlso",
+            IS_DEFINED_BY = "isDefinedBy",
+        }
+    }
+}
+
+crate::vocabulary! {
+    /// The OWL 2 vocabulary.
+    pub mod owl, Owl {
+        prefix: "owl",
+        namespace: "http://www.w3.org/2002/07/owl#",
+        terms: {
+            CLASS = "Class",
+            THING = "Thing",
+            NOTHING = "Nothing",
+            ONTOLOGY = "Ontology",
+            IMPORTS = "imports",
+            VERSION_IRI = "versionIRI",
+            NAMED_INDIVIDUAL = "NamedIndividual",
+            OBJECT_PROPERTY = "ObjectProperty",
+            DATATYPE_PROPERTY = "DatatypeProperty",
+            ANNOTATION_PROPERTY = "AnnotationProperty",
+            FUNCTIONAL_PROPERTY = "FunctionalProperty",
+            TRANSITIVE_PROPERTY = "TransitiveProperty",
+            SYMMETRIC_PROPERTY = "SymmetricProperty",
+            RESTRICTION = "Restriction",
+            ON_PROPERTY = "onProperty",
+            SOME_VALUES_FROM = "someValuesFrom",
+            ALL_VALUES_FROM = "allValuesFrom",
+            EQUIVALENT_CLASS = "equivalentClass",
+            EQUIVALENT_PROPERTY = "equivalentProperty",
+            DISJOINT_WITH = "disjointWith",
+            INVERSE_OF = "inverseOf",
+            SAME_AS = "sameAs",
+            DIFFERENT_FROM = "differentFrom",
+            DEPRECATED = "deprecated",
+        }
+    }
+}
+
+crate::vocabulary! {
+    /// The XML Schema datatypes used by RDF.
+    pub mod xsd, Xsd {
+        prefix: "xsd",
+        namespace: "http://www.w3.org/2001/XMLSchema#",
+        terms: {
+            STRING = "string",
+            BOOLEAN = "boolean",
+            DECIMAL = "decimal",
+            INTEGER = "integer",
+            DOUBLE = "double",
+            FLOAT = "float",
+            LONG = "long",
+            INT = "int",
+            SHORT = "short",
+            BYTE = "byte",
+            NON_NEGATIVE_INTEGER = "nonNegativeInteger",
+            POSITIVE_INTEGER = "positiveInteger",
+            DATE = "date",
+            DATE_TIME = "dateTime",
+            DATE_TIME_STAMP = "dateTimeStamp",
+            TIME = "time",
+            DURATION = "duration",
+            G_YEAR = "gYear",
+            ANY_URI = "anyURI",
+            LANGUAGE = "language",
+            HEX_BINARY = "hexBinary",
+            BASE64_BINARY = "base64Binary",
+        }
+    }
+}
+
+crate::vocabulary! {
+    /// The SKOS vocabulary.
+    pub mod skos, Skos {
+        prefix: "skos",
+        namespace: "http://www.w3.org/2004/02/skos/core#",
+        terms: {
+            CONCEPT = "Concept",
+            CONCEPT_SCHEME = "ConceptScheme",
+            COLLECTION = "Collection",
+            IN_SCHEME = "inScheme",
+            HAS_TOP_CONCEPT = "hasTopConcept",
+            TOP_CONCEPT_OF = "topConceptOf",
+            PREF_LABEL = "prefLabel",
+            ALT_LABEL = "altLabel",
+            HIDDEN_LABEL = "hiddenLabel",
+            NOTATION = "notation",
+            DEFINITION = "definition",
+            NOTE = "note",
+            SCOPE_NOTE = "scopeNote",
+            EXAMPLE = "example",
+            BROADER = "broader",
+            NARROWER = "narrower",
+            RELATED = "related",
+            MEMBER = "member",
+            EXACT_MATCH = "exactMatch",
+            CLOSE_MATCH = "closeMatch",
+            BROAD_MATCH = "broadMatch",
+            NARROW_MATCH = "narrowMatch",
+            RELATED_MATCH = "relatedMatch",
+        }
+    }
+}
+
+crate::vocabulary! {
+    /// The Friend of a Friend (FOAF) vocabulary.
+    pub mod foaf, Foaf {
+        prefix: "foaf",
+        namespace: "http://xmlns.com/foaf/0.1/",
+        terms: {
+            AGENT = "Agent",
+            PERSON = "Person",
+            ORGANIZATION = "Organization",
+            GROUP = "Group",
+            DOCUMENT = "Document",
+            IMAGE = "Image",
+            ONLINE_ACCOUNT = "OnlineAccount",
+            NAME = "name",
+            GIVEN_NAME = "givenName",
+            FAMILY_NAME = "familyName",
+            NICK = "nick",
+            MBOX = "mbox",
+            HOMEPAGE = "homepage",
+            PAGE = "page",
+            DEPICTION = "depiction",
+            IMG = "img",
+            KNOWS = "knows",
+            MEMBER = "member",
+            ACCOUNT = "account",
+            ACCOUNT_NAME = "accountName",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Vocabulary;
+
+    #[test]
+    fn lookup() {
+        assert_eq!(
+            owl::Owl::term_for_iri("http://www.w3.org/2002/07/owl#Class"),
+            Some(&owl::CLASS)
+        );
+        assert_eq!(
+            xsd::DATE_TIME.iri(),
+            "http://www.w3.org/2001/XMLSchema#dateTime"
+        );
+        assert_eq!(skos::Skos::term("prefLabel"), Some(&skos::PREF_LABEL));
+        assert_eq!(foaf::Foaf::NAMESPACE, "http://xmlns.com/foaf/0.1/");
+    }
+}
@@ -0,0 +1,258 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{Curie, InvalidPrefixError, PrefixMapping};
+
+/// A term within a [`Vocabulary`].
+///
+/// A term knows both its CURIE, using the preferred prefix of its
+/// vocabulary, and its complete IRI. Terms are usually defined with
+/// the [`vocabulary!`](crate::vocabulary) macro.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Term {
+    prefix: &'static str,
+    name: &'static str,
+    iri: &'static str,
+}
+
+impl Term {
+    /// Construct a `Term`.
+    ///
+    /// The `iri` should be the namespace of the vocabulary followed
+    /// by the `name`.
+    #[must_use]
+    pub const fn new(prefix: &'static str, name: &'static str, iri: &'static str) -> Self {
+        Term { prefix, name, iri }
+    }
+
+    /// The name of the term within its vocabulary.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The complete IRI of the term.
+    #[must_use]
+    pub fn iri(&self) -> &'static str {
+        self.iri
+    }
+
+    /// The CURIE for the term, using the preferred prefix of its
+    /// vocabulary.
+    #[must_use]
+    pub fn curie(&self) -> Curie<'static> {
+        Curie::new(Some(self.prefix), self.name)
+    }
+}
+
+/// A vocabulary: a namespace, its preferred prefix and the terms that
+/// it defines.
+///
+/// This is usually implemented with the [`vocabulary!`](crate::vocabulary)
+/// macro.
+///
+/// # Examples
+///
+/// ```
+/// use curie::{vocabulary, Curie, PrefixMapping, Vocabulary};
+///
+/// vocabulary! {
+///     /// Part of the FOAF vocabulary.
+///     pub mod foaf, Foaf {
+///         prefix: "foaf",
+///         namespace: "http://xmlns.com/foaf/0.1/",
+///         terms: {
+///             AGENT = "Agent",
+///             NAME = "name",
+///         }
+///     }
+/// }
+///
+/// assert_eq!(foaf::NAME.iri(), "http://xmlns.com/foaf/0.1/name");
+/// assert_eq!(foaf::Foaf::term("Agent"), Some(&foaf::AGENT));
+///
+/// let mut mapping = PrefixMapping::default();
+/// mapping.add_vocabulary::<foaf::Foaf>().unwrap();
+///
+/// // Compaction uses the preferred prefix ...
+/// assert_eq!(mapping.shrink_iri(foaf::NAME.iri()), Ok(foaf::NAME.curie()));
+///
+/// // ... and validation checks that a CURIE names a known term.
+/// assert_eq!(foaf::Foaf::resolve_curie(&mapping, &Curie::new(Some("foaf"), "name")),
+///            Some(&foaf::NAME));
+/// assert_eq!(foaf::Foaf::resolve_curie(&mapping, &Curie::new(Some("foaf"), "nick")),
+///            None);
+/// ```
+pub trait Vocabulary {
+    /// The preferred prefix for this vocabulary.
+    const PREFIX: &'static str;
+    /// The namespace IRI of this vocabulary.
+    const NAMESPACE: &'static str;
+    /// The terms defined by this vocabulary.
+    const TERMS: &'static [Term];
+
+    /// Iterate over the terms defined by this vocabulary.
+    fn terms() -> std::slice::Iter<'static, Term> {
+        Self::TERMS.iter()
+    }
+
+    /// Find a term by its name.
+    #[must_use]
+    fn term(name: &str) -> Option<&'static Term> {
+        Self::TERMS.iter().find(|term| term.name == name)
+    }
+
+    /// Find a term by its complete IRI.
+    #[must_use]
+    fn term_for_iri(iri: &str) -> Option<&'static Term> {
+        Self::TERMS.iter().find(|term| term.iri == iri)
+    }
+
+    /// Find the term that a CURIE refers to when expanded with `mapping`.
+    ///
+    /// This works whatever prefix `mapping` uses for this vocabulary.
+    /// Returns `None` if the CURIE can't be expanded or doesn't refer
+    /// to a term of this vocabulary.
+    #[must_use]
+    fn resolve_curie(mapping: &PrefixMapping, curie: &Curie) -> Option<&'static Term> {
+        mapping
+            .expand_curie(curie)
+            .ok()
+            .and_then(|iri| Self::term_for_iri(&iri))
+    }
+}
+
+impl PrefixMapping {
+    /// Add the preferred prefix of a [`Vocabulary`] to the mapping.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidPrefixError`] when the prefix of the vocabulary
+    /// is invalid.
+    ///
+    /// # See also
+    ///
+    /// * [`PrefixMapping::add_prefix()`]
+    pub fn add_vocabulary<V: Vocabulary>(&mut self) -> Result<(), InvalidPrefixError> {
+        self.add_prefix(V::PREFIX, V::NAMESPACE)
+    }
+}
+
+/// Define a module for a [`Vocabulary`](crate::Vocabulary), with a
+/// [`Term`](crate::Term) constant for each of its terms.
+///
+/// The module contains a unit struct implementing `Vocabulary` and
+/// the constants. Attributes, including doc comments, are passed
+/// through to the module and to the constants. The prefix of the
+/// vocabulary is registered with [`PrefixMapping::add_vocabulary()`].
+///
+/// # Examples
+///
+/// ```
+/// use curie::{vocabulary, Vocabulary};
+///
+/// vocabulary! {
+///     /// Part of the Dublin Core terms vocabulary.
+///     pub mod dcterms, DcTerms {
+///         prefix: "dcterms",
+///         namespace: "http://purl.org/dc/terms/",
+///         terms: {
+///             /// The name given to the resource.
+///             TITLE = "title",
+///             CREATOR = "creator",
+///         }
+///     }
+/// }
+///
+/// assert_eq!(dcterms::TITLE.iri(), "http://purl.org/dc/terms/title");
+/// assert_eq!(dcterms::DcTerms::NAMESPACE, "http://purl.org/dc/terms/");
+/// assert_eq!(dcterms::DcTerms::terms().count(), 2);
+///
+/// let mut mapping = curie::PrefixMapping::default();
+/// mapping.add_vocabulary::<dcterms::DcTerms>().unwrap();
+/// assert_eq!(mapping.expand_curie(&dcterms::CREATOR.curie()),
+///            Ok(String::from(dcterms::CREATOR.iri())));
+/// ```
+#[macro_export]
+macro_rules! vocabulary {
+    (
+        $(#[$meta:meta])*
+        $vis:vis mod $module:ident, $name:ident {
+            prefix: $prefix:literal,
+            namespace: $namespace:literal,
+            terms: {
+                $(
+                    $(#[$term_meta:meta])*
+                    $constant:ident = $term:literal
+                ),* $(,)?
+            } $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis mod $module {
+            #[doc = concat!("The `", $prefix, "` vocabulary, `", $namespace, "`.")]
+            #[derive(Clone, Copy, Debug)]
+            pub struct $name;
+
+            impl $crate::Vocabulary for $name {
+                const PREFIX: &'static str = $prefix;
+                const NAMESPACE: &'static str = $namespace;
+                const TERMS: &'static [$crate::Term] = &[$($constant),*];
+            }
+
+            $(
+                #[doc = concat!("`", $prefix, ":", $term, "`")]
+                #[doc = ""]
+                $(#[$term_meta])*
+                pub const $constant: $crate::Term =
+                    $crate::Term::new($prefix, $term, concat!($namespace, $term));
+            )*
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Curie, PrefixMapping, Vocabulary};
+
+    vocabulary! {
+        mod ex, Example {
+            prefix: "ex",
+            namespace: "http://example.com/ns#",
+            terms: {
+                THING = "Thing",
+                PART_OF = "partOf",
+            }
+        }
+    }
+
+    #[test]
+    fn vocabulary_lookup() {
+        assert_eq!(ex::Example::PREFIX, "ex");
+        assert_eq!(ex::THING.iri(), "http://example.com/ns#Thing");
+        assert_eq!(ex::PART_OF.curie(), Curie::new(Some("ex"), "partOf"));
+        assert_eq!(
+            ex::Example::term_for_iri("http://example.com/ns#partOf"),
+            Some(&ex::PART_OF)
+        );
+        assert_eq!(ex::Example::term("hasPart"), None);
+    }
+
+    #[test]
+    fn resolve_curie_with_other_prefix() {
+        let mut mapping = PrefixMapping::default();
+        mapping.add_prefix("e", "http://example.com/ns#").unwrap();
+
+        assert_eq!(
+            ex::Example::resolve_curie(&mapping, &Curie::new(Some("e"), "Thing")),
+            Some(&ex::THING)
+        );
+        assert_eq!(
+            ex::Example::resolve_curie(&mapping, &Curie::new(Some("ex"), "Thing")),
+            None
+        );
+    }
+}
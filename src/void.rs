@@ -0,0 +1,146 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{CoverageReport, PrefixMapping};
+use std::fmt;
+
+impl PrefixMapping {
+    /// Describe the namespaces of this mapping as a
+    /// [VoID](https://www.w3.org/TR/void/) dataset, in Turtle.
+    ///
+    /// Each namespace is listed as a `void:vocabulary` of `dataset`,
+    /// and as a subset partitioning the dataset by `void:uriSpace`,
+    /// labelled with its prefix using
+    /// [VANN](https://vocab.org/vann/). The default namespace and URI
+    /// templates are not described.
+    ///
+    /// When a [`CoverageReport`] is given, only the prefixes that it
+    /// found in use are described, and the number of uses of each is
+    /// given as the `void:entities` of its subset. For that to be the
+    /// number of entities, the corpus passed to
+    /// [`PrefixMapping::coverage()`] should list each entity once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::PrefixMapping;
+    ///
+    /// let mut mapping = PrefixMapping::default();
+    /// mapping.add_prefix("foaf", "http://xmlns.com/foaf/0.1/").unwrap();
+    /// mapping.add_prefix("dc", "http://purl.org/dc/terms/").unwrap();
+    ///
+    /// let usage = mapping.coverage(["foaf:Person", "foaf:Agent"]);
+    /// assert_eq!(mapping.void_description("http://example.com/dataset", Some(&usage)), "\
+    /// @prefix void: <http://rdfs.org/ns/void#> .
+    /// @prefix vann: <http://purl.org/vocab/vann/> .
+    ///
+    /// <http://example.com/dataset> a void:Dataset ;
+    ///     void:vocabulary <http://xmlns.com/foaf/0.1/> ;
+    ///     void:subset [
+    ///         a void:Dataset ;
+    ///         void:uriSpace \"http://xmlns.com/foaf/0.1/\" ;
+    ///         vann:preferredNamespacePrefix \"foaf\" ;
+    ///         void:entities 2
+    ///     ] .
+    /// ");
+    /// ```
+    #[must_use]
+    pub fn void_description(&self, dataset: &str, usage: Option<&CoverageReport>) -> String {
+        let mut out = String::new();
+        // Writing to a `String` can't fail.
+        let _ = self.write_void_description(dataset, usage, &mut out);
+        out
+    }
+
+    /// Write a [VoID](https://www.w3.org/TR/void/) description of the namespaces of this mapping.
+    ///
+    /// See [`PrefixMapping::void_description()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to `out` fails.
+    pub fn write_void_description<W: fmt::Write + ?Sized>(
+        &self,
+        dataset: &str,
+        usage: Option<&CoverageReport>,
+        out: &mut W,
+    ) -> fmt::Result {
+        let entries: Vec<(&str, &str, Option<usize>)> = self
+            .mappings()
+            .filter_map(|(prefix, namespace)| match usage {
+                Some(report) => report
+                    .used_prefixes()
+                    .find(|&(used, _)| used == prefix)
+                    .map(|(_, uses)| (prefix, namespace, Some(uses))),
+                None => Some((prefix, namespace, None)),
+            })
+            .collect();
+
+        out.write_str("@prefix void: <http://rdfs.org/ns/void#> .\n")?;
+        out.write_str("@prefix vann: <http://purl.org/vocab/vann/> .\n\n")?;
+        write!(out, "<{dataset}> a void:Dataset")?;
+        if !entries.is_empty() {
+            out.write_str(" ;\n    void:vocabulary ")?;
+            for (index, (_, namespace, _)) in entries.iter().enumerate() {
+                if index > 0 {
+                    out.write_str(", ")?;
+                }
+                write!(out, "<{namespace}>")?;
+            }
+            out.write_str(" ;\n    void:subset ")?;
+            for (index, &(prefix, namespace, uses)) in entries.iter().enumerate() {
+                if index > 0 {
+                    out.write_str(", ")?;
+                }
+                out.write_str("[\n        a void:Dataset ;\n        void:uriSpace ")?;
+                write_literal(out, namespace)?;
+                out.write_str(" ;\n        vann:preferredNamespacePrefix ")?;
+                write_literal(out, prefix)?;
+                if let Some(uses) = uses {
+                    write!(out, " ;\n        void:entities {uses}")?;
+                }
+                out.write_str("\n    ]")?;
+            }
+        }
+        out.write_str(" .\n")
+    }
+}
+
+/// Write a Turtle string literal.
+fn write_literal<W: fmt::Write + ?Sized>(out: &mut W, value: &str) -> fmt::Result {
+    out.write_char('"')?;
+    for c in value.chars() {
+        match c {
+            '"' => out.write_str("\\\"")?,
+            '\\' => out.write_str("\\\\")?,
+            '\n' => out.write_str("\\n")?,
+            '\r' => out.write_str("\\r")?,
+            c => out.write_char(c)?,
+        }
+    }
+    out.write_char('"')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn void() {
+        let mut mapping = PrefixMapping::default();
+        assert!(mapping
+            .void_description("http://example.com/d", None)
+            .ends_with("\n<http://example.com/d> a void:Dataset .\n"));
+
+        mapping.add_prefix("a", "http://a.example/").unwrap();
+        mapping.add_prefix("b", "urn:x:\"q\"").unwrap();
+        let description = mapping.void_description("http://example.com/d", None);
+        assert!(description.contains("void:vocabulary <http://a.example/>, <urn:x:\"q\"> ;\n"));
+        assert!(description.contains("void:uriSpace \"urn:x:\\\"q\\\"\" ;\n"));
+        assert!(description.contains("\n    ], [\n"));
+        assert!(!description.contains("void:entities"));
+    }
+}
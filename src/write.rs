@@ -0,0 +1,182 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{Curie, ExpansionError, PrefixMapping};
+use std::{fmt, io};
+
+/// Errors that might occur when writing an expanded CURIE with
+/// [`PrefixMapping::write_expanded()`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum WriteError {
+    /// The CURIE could not be expanded. Nothing was written.
+    Expansion(ExpansionError),
+    /// The writer returned an error.
+    Fmt(fmt::Error),
+}
+
+impl fmt::Display for WriteError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WriteError::Expansion(err) => write!(f, "expansion failed: {err}"),
+            WriteError::Fmt(err) => write!(f, "writing failed: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for WriteError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            WriteError::Expansion(err) => Some(err),
+            WriteError::Fmt(err) => Some(err),
+        }
+    }
+}
+
+impl From<ExpansionError> for WriteError {
+    fn from(err: ExpansionError) -> Self {
+        WriteError::Expansion(err)
+    }
+}
+
+impl From<fmt::Error> for WriteError {
+    fn from(err: fmt::Error) -> Self {
+        WriteError::Fmt(err)
+    }
+}
+
+impl PrefixMapping {
+    /// Expand a parsed [`Curie`], writing the complete IRI to `out`.
+    ///
+    /// The CURIE is expanded as by [`PrefixMapping::expand_curie()`],
+    /// including terms, URI templates and
+    /// [`Unprefixed::Base`](crate::Unprefixed::Base), but the parts of
+    /// the IRI are written directly, without building it as a `String`
+    /// first. Writing to a `String` that is cleared between calls
+    /// avoids allocating for each CURIE.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::{Curie, PrefixMapping};
+    ///
+    /// let mut mapping = PrefixMapping::default();
+    /// mapping.add_prefix("foaf", "http://xmlns.com/foaf/0.1/").unwrap();
+    ///
+    /// let mut out = String::from("<");
+    /// mapping.write_expanded(&Curie::new(Some("foaf"), "Agent"), &mut out).unwrap();
+    /// out.push('>');
+    /// assert_eq!(out, "<http://xmlns.com/foaf/0.1/Agent>");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This will return [`WriteError::Expansion`] if the expansion fails,
+    /// in which case nothing is written, or [`WriteError::Fmt`] if `out`
+    /// returns an error.
+    ///
+    /// # See also
+    ///
+    /// * [`PrefixMapping::write_expanded_io()`]
+    pub fn write_expanded<W: fmt::Write + ?Sized>(
+        &self,
+        curie: &Curie,
+        out: &mut W,
+    ) -> Result<(), WriteError> {
+        let resolved = self.resolve(curie)?;
+        for part in resolved.parts() {
+            out.write_str(part)?;
+        }
+        Ok(())
+    }
+
+    /// Expand a parsed [`Curie`], writing the complete IRI to `out`.
+    ///
+    /// This is like [`PrefixMapping::write_expanded()`], but writes to an
+    /// [`io::Write`] rather than a [`fmt::Write`].
+    ///
+    /// # Errors
+    ///
+    /// If the expansion fails, nothing is written and an error of kind
+    /// [`io::ErrorKind::InvalidInput`] wrapping the [`ExpansionError`] is
+    /// returned. Errors from `out` are returned as they are.
+    pub fn write_expanded_io<W: io::Write + ?Sized>(
+        &self,
+        curie: &Curie,
+        out: &mut W,
+    ) -> io::Result<()> {
+        let resolved = self
+            .resolve(curie)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+        resolved
+            .parts()
+            .iter()
+            .try_for_each(|part| out.write_all(part.as_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Unprefixed;
+
+    #[test]
+    fn write_expanded_errors() {
+        let mut mapping = PrefixMapping::default();
+        let curie = Curie::new(None, "Entity");
+
+        let mut out = String::new();
+        assert_eq!(
+            mapping.write_expanded(&curie, &mut out),
+            Err(WriteError::Expansion(ExpansionError::MissingDefault))
+        );
+        assert!(out.is_empty());
+
+        let mut bytes = Vec::new();
+        let err = mapping.write_expanded_io(&curie, &mut bytes).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert!(bytes.is_empty());
+
+        mapping.set_default("http://example.com/");
+        mapping.write_expanded_io(&curie, &mut bytes).unwrap();
+        assert_eq!(bytes, b"http://example.com/Entity");
+    }
+
+    #[test]
+    fn write_expanded_agrees() {
+        let mut mapping = PrefixMapping::default();
+        mapping.add_prefix("ex", "http://example.com/").unwrap();
+        mapping.add_term("name", "http://xmlns.com/foaf/0.1/name");
+        mapping
+            .add_prefix_template("doi", "https://doi.org/{id}")
+            .unwrap();
+        mapping.set_base("http://example.org/people/alice");
+        mapping.set_unprefixed(Unprefixed::Base);
+
+        let mut out = String::new();
+        for curie_str in ["ex:a", "name", "doi:10.1/x", "#me"] {
+            let curie = Curie::split(curie_str);
+            out.clear();
+            mapping.write_expanded(&curie, &mut out).unwrap();
+            assert_eq!(Ok(out.clone()), mapping.expand_curie(&curie));
+        }
+    }
+
+    #[test]
+    fn write_expanded_term() {
+        let mut mapping = PrefixMapping::default();
+        mapping.add_term("name", "http://xmlns.com/foaf/0.1/name");
+        let curie = Curie::new(None, "name");
+        let expanded = mapping.expand_curie(&curie).unwrap();
+
+        let mut out = String::new();
+        mapping.write_expanded(&curie, &mut out).unwrap();
+        assert_eq!(out, expanded);
+
+        let mut bytes = Vec::new();
+        mapping.write_expanded_io(&curie, &mut bytes).unwrap();
+        assert_eq!(bytes, expanded.as_bytes());
+    }
+}
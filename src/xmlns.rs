@@ -0,0 +1,368 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::turtle::Directive;
+use crate::{syntax, InvalidPrefixError, PrefixMapping};
+use std::fmt;
+use std::iter::FusedIterator;
+
+/// The namespace that the `xml` prefix is always bound to.
+const XML_NAMESPACE: &str = "http://www.w3.org/XML/1998/namespace";
+
+/// The namespace of the `xmlns` attributes themselves, which can't be
+/// bound to a prefix.
+const XMLNS_NAMESPACE: &str = "http://www.w3.org/2000/xmlns/";
+
+/// Errors that might occur when reading XML namespace declarations.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum XmlnsError {
+    /// The prefix of an `xmlns:` attribute is not an `NCName`.
+    InvalidPrefix(String),
+    /// The declaration binds `xmlns`, binds `xml` to a namespace other
+    /// than its own, or binds another prefix to one of their
+    /// namespaces, which the [Namespaces in XML] recommendation
+    /// forbids. It may also bind `_`, which is reserved in a mapping.
+    ///
+    /// [Namespaces in XML]: https://www.w3.org/TR/xml-names/
+    ReservedPrefix(String),
+    /// The prefix is protected in the mapping.
+    ProtectedPrefix(String),
+    /// The namespace is not an absolute IRI, when the mapping is in
+    /// strict mode. See `PrefixMapping::set_strict_namespaces()`.
+    InvalidNamespace(String),
+    /// The prefix is already bound to a different namespace, when the
+    /// mapping doesn't allow rebinding. See
+    /// `PrefixMapping::set_strict_rebinding()`.
+    AlreadyBound(String),
+}
+
+impl fmt::Display for XmlnsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            XmlnsError::InvalidPrefix(prefix) => {
+                write!(f, "the prefix `{prefix}` is not a valid NCName")
+            }
+            XmlnsError::ReservedPrefix(prefix) => {
+                write!(f, "the prefix `{prefix}` is reserved")
+            }
+            XmlnsError::ProtectedPrefix(prefix) => {
+                write!(f, "the prefix `{prefix}` is protected")
+            }
+            XmlnsError::InvalidNamespace(namespace) => {
+                write!(f, "the namespace `{namespace}` is not an absolute IRI")
+            }
+            XmlnsError::AlreadyBound(prefix) => {
+                write!(f, "the prefix `{prefix}` is already bound")
+            }
+        }
+    }
+}
+
+impl std::error::Error for XmlnsError {}
+
+/// Read the XML namespace declarations among the attributes of an
+/// element, without applying them to a mapping.
+///
+/// An `xmlns:prefix` attribute is yielded as a
+/// [`Directive::Prefix`], and an `xmlns` attribute as a
+/// [`Directive::DefaultPrefix`]. An empty namespace undeclares the
+/// prefix or the default, as in XML 1.1, and is yielded as it is.
+/// Other attributes are skipped. Reading stops after the first
+/// declaration that the [Namespaces in XML] recommendation forbids.
+///
+/// # Examples
+///
+/// ```
+/// use curie::turtle::Directive;
+/// use curie::xmlns_directives;
+///
+/// let directives = xmlns_directives([
+///     ("xmlns", "http://www.w3.org/1999/xhtml"),
+///     ("xmlns:foaf", "http://xmlns.com/foaf/0.1/"),
+///     ("lang", "en"),
+/// ]).collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(directives, [
+///     Directive::DefaultPrefix(String::from("http://www.w3.org/1999/xhtml")),
+///     Directive::Prefix {
+///         prefix: String::from("foaf"),
+///         iri: String::from("http://xmlns.com/foaf/0.1/"),
+///     },
+/// ]);
+/// ```
+///
+/// [Namespaces in XML]: https://www.w3.org/TR/xml-names/
+pub fn xmlns_directives<'a, I>(attributes: I) -> XmlnsDirectives<I::IntoIter>
+where
+    I: IntoIterator<Item = (&'a str, &'a str)>,
+{
+    XmlnsDirectives {
+        attributes: attributes.into_iter(),
+        done: false,
+    }
+}
+
+/// An iterator over the XML namespace declarations among attributes.
+///
+/// This is created by [`xmlns_directives()`].
+#[derive(Clone, Debug)]
+pub struct XmlnsDirectives<I> {
+    attributes: I,
+    /// Whether an error has been reached.
+    done: bool,
+}
+
+impl<'a, I> Iterator for XmlnsDirectives<I>
+where
+    I: Iterator<Item = (&'a str, &'a str)>,
+{
+    type Item = Result<Directive, XmlnsError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        for (name, namespace) in self.attributes.by_ref() {
+            if name == "xmlns" {
+                return Some(Ok(Directive::DefaultPrefix(String::from(namespace))));
+            }
+            let Some(prefix) = name.strip_prefix("xmlns:") else {
+                continue;
+            };
+            let reserved = match prefix {
+                "xml" => namespace != XML_NAMESPACE,
+                "xmlns" => true,
+                _ => namespace == XML_NAMESPACE || namespace == XMLNS_NAMESPACE,
+            };
+            let result = if !syntax::is_ncname(prefix) {
+                Err(XmlnsError::InvalidPrefix(String::from(prefix)))
+            } else if reserved {
+                Err(XmlnsError::ReservedPrefix(String::from(prefix)))
+            } else {
+                Ok(Directive::Prefix {
+                    prefix: String::from(prefix),
+                    iri: String::from(namespace),
+                })
+            };
+            self.done = result.is_err();
+            return Some(result);
+        }
+        self.done = true;
+        None
+    }
+}
+
+impl<'a, I> FusedIterator for XmlnsDirectives<I> where I: Iterator<Item = (&'a str, &'a str)> {}
+
+impl PrefixMapping {
+    /// Apply the XML namespace declarations among the attributes of an
+    /// element.
+    ///
+    /// An `xmlns:prefix="namespace"` attribute binds a prefix, and an
+    /// `xmlns="namespace"` attribute sets the default prefix. An empty
+    /// namespace undeclares the prefix, or removes the default, as in
+    /// XML 1.1. Other attributes are ignored.
+    ///
+    /// Call this on a copy of the mapping of the parent element to get
+    /// the mapping in scope for the element, or use a
+    /// [`ScopedPrefixMapping`](crate::ScopedPrefixMapping) to avoid the
+    /// copy. To also handle RDFa attributes, use
+    /// [`PrefixMapping::apply_rdfa_element()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`XmlnsError`] for the first declaration that can't be
+    /// applied. Declarations before it remain applied.
+    ///
+    /// # See also
+    ///
+    /// * [`xmlns_directives()`](crate::xmlns_directives)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use curie::PrefixMapping;
+    ///
+    /// let mut mapping = PrefixMapping::default();
+    /// mapping.extend_from_xmlns([
+    ///     ("xmlns", "http://www.w3.org/1999/xhtml"),
+    ///     ("xmlns:foaf", "http://xmlns.com/foaf/0.1/"),
+    ///     ("lang", "en"),
+    /// ]).unwrap();
+    ///
+    /// assert_eq!(mapping.expand_curie_string("foaf:name"),
+    ///            Ok(String::from("http://xmlns.com/foaf/0.1/name")));
+    /// assert_eq!(mapping.to_xmlns(),
+    ///            r#"xmlns="http://www.w3.org/1999/xhtml" xmlns:foaf="http://xmlns.com/foaf/0.1/""#);
+    /// ```
+    pub fn extend_from_xmlns<'a, I>(&mut self, attributes: I) -> Result<(), XmlnsError>
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        for directive in xmlns_directives(attributes) {
+            let (prefix, namespace) = match directive? {
+                Directive::DefaultPrefix(namespace) => {
+                    self.default = (!namespace.is_empty()).then_some(namespace);
+                    continue;
+                }
+                Directive::Prefix { prefix, iri } => (prefix, iri),
+                Directive::Base(_) | Directive::Term { .. } => continue,
+            };
+            let result = if namespace.is_empty() {
+                self.remove_prefix(&prefix).map(|_| ())
+            } else {
+                self.add_prefix(&prefix, &namespace)
+            };
+            match result {
+                Ok(()) => {}
+                Err(InvalidPrefixError::ReservedPrefix) => {
+                    return Err(XmlnsError::ReservedPrefix(prefix))
+                }
+                Err(InvalidPrefixError::InvalidName) => {
+                    return Err(XmlnsError::InvalidPrefix(prefix))
+                }
+                Err(InvalidPrefixError::InvalidNamespace) => {
+                    return Err(XmlnsError::InvalidNamespace(namespace))
+                }
+                Err(InvalidPrefixError::ProtectedPrefix) => {
+                    return Err(XmlnsError::ProtectedPrefix(prefix))
+                }
+                Err(InvalidPrefixError::AlreadyBound) => {
+                    return Err(XmlnsError::AlreadyBound(prefix))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The XML namespace declarations for this mapping, as attribute
+    /// names and values.
+    ///
+    /// The default prefix, if any, is declared with `xmlns`, followed by
+    /// an `xmlns:` attribute for each prefix that is an `NCName`, in the
+    /// order they were added. Other prefixes, such as the empty prefix,
+    /// can't be declared in XML and are left out.
+    pub fn xmlns_attributes(&self) -> impl Iterator<Item = (String, &str)> {
+        let default = self
+            .default
+            .as_deref()
+            .map(|namespace| (String::from("xmlns"), namespace));
+        let prefixes = self
+            .mapping
+            .iter()
+            .filter(|(prefix, _)| syntax::is_ncname(prefix))
+            .map(|(prefix, namespace)| (format!("xmlns:{prefix}"), namespace.as_str()));
+        default.into_iter().chain(prefixes)
+    }
+
+    /// Write the XML namespace declarations for this mapping as
+    /// attributes, separated by spaces.
+    ///
+    /// See [`PrefixMapping::xmlns_attributes()`] for which are written.
+    /// Values are escaped for use in a double-quoted attribute.
+    #[must_use]
+    pub fn to_xmlns(&self) -> String {
+        let mut out = String::new();
+        for (name, namespace) in self.xmlns_attributes() {
+            if !out.is_empty() {
+                out.push(' ');
+            }
+            out.push_str(&name);
+            out.push_str("=\"");
+            for c in namespace.chars() {
+                match c {
+                    '&' => out.push_str("&amp;"),
+                    '<' => out.push_str("&lt;"),
+                    '"' => out.push_str("&quot;"),
+                    c => out.push(c),
+                }
+            }
+            out.push('"');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn declarations() {
+        let mut mapping = PrefixMapping::default();
+        mapping
+            .extend_from_xmlns([
+                ("xmlns:a", "http://example.com/a?x=1&y=2"),
+                ("xmlns:b", "http://example.com/b/"),
+                ("xmlns:xml", XML_NAMESPACE),
+            ])
+            .unwrap();
+        mapping.add_prefix("", "http://example.com/empty/").unwrap();
+        assert_eq!(
+            mapping.to_xmlns(),
+            "xmlns:a=\"http://example.com/a?x=1&amp;y=2\" \
+             xmlns:b=\"http://example.com/b/\" \
+             xmlns:xml=\"http://www.w3.org/XML/1998/namespace\""
+        );
+
+        mapping
+            .extend_from_xmlns([("xmlns:b", ""), ("xmlns", "http://example.com/")])
+            .unwrap();
+        assert_eq!(mapping.expand_curie_string("b:x").ok(), None);
+        assert_eq!(
+            mapping.expand_curie_string("x"),
+            Ok(String::from("http://example.com/x"))
+        );
+        mapping.extend_from_xmlns([("xmlns", "")]).unwrap();
+        assert_eq!(mapping.expand_curie_string("x").ok(), None);
+    }
+
+    #[test]
+    fn directives() {
+        let mut directives = xmlns_directives([
+            ("xmlns:b", ""),
+            ("id", "x"),
+            ("xmlns:1a", "http://example.com/"),
+            ("xmlns:c", "http://example.com/c/"),
+        ]);
+        assert_eq!(
+            directives.next(),
+            Some(Ok(Directive::Prefix {
+                prefix: String::from("b"),
+                iri: String::new(),
+            }))
+        );
+        assert_eq!(
+            directives.next(),
+            Some(Err(XmlnsError::InvalidPrefix(String::from("1a"))))
+        );
+        assert_eq!(directives.next(), None);
+    }
+
+    #[test]
+    fn invalid_declarations() {
+        let check = |name, namespace| {
+            PrefixMapping::default()
+                .extend_from_xmlns([(name, namespace)])
+                .unwrap_err()
+        };
+        assert_eq!(
+            check("xmlns:1a", "http://example.com/"),
+            XmlnsError::InvalidPrefix(String::from("1a"))
+        );
+        assert_eq!(
+            check("xmlns:xml", "http://example.com/"),
+            XmlnsError::ReservedPrefix(String::from("xml"))
+        );
+        assert_eq!(
+            check("xmlns:x", XMLNS_NAMESPACE),
+            XmlnsError::ReservedPrefix(String::from("x"))
+        );
+        assert_eq!(
+            check("xmlns:_", "http://example.com/"),
+            XmlnsError::ReservedPrefix(String::from("_"))
+        );
+    }
+}
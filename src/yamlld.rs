@@ -0,0 +1,206 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Importing and exporting prefixes as [YAML-LD] contexts.
+//!
+//! This requires the `yaml` feature.
+//!
+//! YAML-LD is JSON-LD written as YAML, so contexts are processed with
+//! the same rules as [JSON-LD contexts](crate::jsonld). YAML features
+//! without a JSON equivalent are handled as follows:
+//!
+//! * Anchors and aliases are resolved, so a namespace can be written
+//!   once and reused.
+//! * Merge keys (`<<`) are applied, with the keys of the mapping taking
+//!   precedence over the merged ones.
+//! * Mapping keys that aren't strings, and values with custom tags, are
+//!   rejected with [`JsonLdError::UnsupportedYaml`].
+//!
+//! ```
+//! use curie::PrefixMapping;
+//!
+//! let mapping = PrefixMapping::from_yamlld_context("
+//! base: &base
+//!   ex: http://example.com/
+//! '@context':
+//!   <<: *base
+//!   '@vocab': http://schema.org/
+//!   foaf: http://xmlns.com/foaf/0.1/
+//! ").unwrap();
+//!
+//! assert_eq!(mapping.expand_curie_string("ex:a"),
+//!            Ok(String::from("http://example.com/a")));
+//! assert_eq!(mapping.expand_curie_string("Person"),
+//!            Ok(String::from("http://schema.org/Person")));
+//!
+//! assert_eq!(mapping.to_yamlld_context(), "\
+//! '@context':
+//!   '@vocab': http://schema.org/
+//!   foaf: http://xmlns.com/foaf/0.1/
+//!   ex: http://example.com/
+//! ");
+//! ```
+//!
+//! [YAML-LD]: https://www.w3.org/TR/yaml-ld/
+
+use crate::jsonld::JsonLdError;
+use crate::parse_error::token_end;
+use crate::{ParseError, ParseErrorKind, PrefixMapping};
+use serde_json::{Map, Number, Value};
+
+/// Locate a YAML syntax error in `yaml`.
+fn syntax_error(yaml: &str, err: &serde_yaml::Error) -> ParseError {
+    let offset = err
+        .location()
+        .map_or(yaml.len(), |location| location.index().min(yaml.len()));
+    ParseError::at(
+        ParseErrorKind::InvalidYaml,
+        yaml,
+        offset,
+        token_end(yaml, offset),
+    )
+}
+
+/// Convert YAML to the equivalent JSON.
+fn to_json(yaml: serde_yaml::Value) -> Result<Value, JsonLdError> {
+    Ok(match yaml {
+        serde_yaml::Value::Null => Value::Null,
+        serde_yaml::Value::Bool(b) => Value::Bool(b),
+        serde_yaml::Value::Number(n) => {
+            let number = if let Some(n) = n.as_u64() {
+                Some(Number::from(n))
+            } else if let Some(n) = n.as_i64() {
+                Some(Number::from(n))
+            } else {
+                n.as_f64().and_then(Number::from_f64)
+            };
+            Value::Number(number.ok_or_else(|| {
+                JsonLdError::UnsupportedYaml(format!("the number {n} can't be used in JSON"))
+            })?)
+        }
+        serde_yaml::Value::String(s) => Value::String(s),
+        serde_yaml::Value::Sequence(values) => {
+            Value::Array(values.into_iter().map(to_json).collect::<Result<_, _>>()?)
+        }
+        serde_yaml::Value::Mapping(entries) => {
+            let mut object = Map::new();
+            for (key, value) in entries {
+                let serde_yaml::Value::String(key) = key else {
+                    return Err(JsonLdError::UnsupportedYaml(format!(
+                        "the mapping key {key:?} is not a string"
+                    )));
+                };
+                object.insert(key, to_json(value)?);
+            }
+            Value::Object(object)
+        }
+        serde_yaml::Value::Tagged(tagged) => {
+            return Err(JsonLdError::UnsupportedYaml(format!(
+                "the tag {} is not supported",
+                tagged.tag
+            )))
+        }
+    })
+}
+
+impl PrefixMapping {
+    /// Create a mapping from the prefixes defined in a YAML-LD context.
+    ///
+    /// See [`PrefixMapping::extend_from_yamlld_context()`] for details.
+    ///
+    /// This requires the `yaml` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`JsonLdError`] if the YAML is not valid or the context
+    /// is not valid.
+    pub fn from_yamlld_context(yaml: &str) -> Result<PrefixMapping, JsonLdError> {
+        let mut mapping = PrefixMapping::default();
+        mapping.extend_from_yamlld_context(yaml)?;
+        Ok(mapping)
+    }
+
+    /// Add the prefixes defined in a YAML-LD context to this mapping.
+    ///
+    /// As with [`PrefixMapping::extend_from_jsonld_context()`], `yaml`
+    /// may either be a document with an `@context` entry or a context
+    /// itself. See the [module documentation](crate::yamlld) for how
+    /// YAML is handled.
+    ///
+    /// This requires the `yaml` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`JsonLdError`] if the YAML is not valid or the context
+    /// is not valid. Definitions processed before the error was found
+    /// remain in the mapping.
+    pub fn extend_from_yamlld_context(&mut self, yaml: &str) -> Result<(), JsonLdError> {
+        let mut value: serde_yaml::Value = serde_yaml::from_str(yaml)
+            .map_err(|err| JsonLdError::Yaml(syntax_error(yaml, &err)))?;
+        value
+            .apply_merge()
+            .map_err(|err| JsonLdError::UnsupportedYaml(err.to_string()))?;
+        self.extend_from_jsonld_value(&to_json(value)?)
+    }
+
+    /// Write the default prefix, the base IRI and the prefixes of this
+    /// mapping as a YAML-LD document with an `@context` entry.
+    ///
+    /// The context is the same as the one written by
+    /// [`PrefixMapping::to_jsonld_context()`].
+    ///
+    /// This requires the `yaml` feature.
+    #[must_use]
+    pub fn to_yamlld_context(&self) -> String {
+        serde_yaml::to_string(&self.jsonld_context_document())
+            .expect("JSON values can be written as YAML")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut mapping = PrefixMapping::default();
+        mapping.add_prefix("ex", "http://example.com/").unwrap();
+        mapping
+            .add_prefix("item", "http://example.com/item")
+            .unwrap();
+        mapping
+            .add_prefix("rdf", "http://www.w3.org/1999/02/22-rdf-syntax-ns#")
+            .unwrap();
+        mapping.protect_prefix("rdf");
+
+        let yaml = mapping.to_yamlld_context();
+        assert!(yaml.contains("  item:\n    '@id': http://example.com/item\n    '@prefix': true\n"));
+        let imported = PrefixMapping::from_yamlld_context(&yaml).unwrap();
+        assert_eq!(imported, mapping);
+        assert!(imported.is_protected("rdf"));
+    }
+
+    #[test]
+    fn unsupported_yaml() {
+        let err =
+            PrefixMapping::from_yamlld_context("ex: http://example.com/\n  a: b\n").unwrap_err();
+        match err {
+            JsonLdError::Yaml(err) => {
+                assert_eq!(err.kind(), ParseErrorKind::InvalidYaml);
+                assert_eq!(err.line(), 2);
+            }
+            err => panic!("unexpected error {err:?}"),
+        }
+        assert!(matches!(
+            PrefixMapping::from_yamlld_context("1: http://example.com/"),
+            Err(JsonLdError::UnsupportedYaml(_))
+        ));
+        assert!(matches!(
+            PrefixMapping::from_yamlld_context("ex: !iri http://example.com/"),
+            Err(JsonLdError::UnsupportedYaml(_))
+        ));
+    }
+}